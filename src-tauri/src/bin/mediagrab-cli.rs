@@ -0,0 +1,688 @@
+//! Command-line downloader for scripts and servers
+//!
+//! Runs downloads through the same queue, argument builder and yt-dlp
+//! process handling as the app, without Tauri:
+//!
+//! ```text
+//! mediagrab-cli https://youtu.be/dQw4w9WgXcQ
+//! mediagrab-cli --batch links.csv --preset podcast.json --jobs 3 --json
+//! ```
+//!
+//! The exit code is 0 when every link was downloaded (or skipped by the
+//! download archive or filter), 64 for bad arguments, and otherwise the code
+//! of the first failure's error category (see `DownloadError::exit_code`).
+
+use std::collections::HashMap;
+use std::io::{IsTerminal, Read};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::sync::Arc;
+
+use tokio::sync::mpsc;
+use tokio::task::JoinSet;
+
+use mediagrab_lib::commands::folder::prepare_output_folder;
+use mediagrab_lib::commands::preferences::Preferences;
+use mediagrab_lib::download::queue::{
+    create_download_queue, QueueEvent, QueueItem, QueueItemId, QueueItemStatus, SharedDownloadQueue,
+};
+use mediagrab_lib::download::{
+    extract_urls, find_subtitle_files, parse_batch, parse_format, run_loudnorm, run_transcode, spawn_ytdlp,
+    stream_process_output, ProcessOutput, SpawnConfig,
+};
+use mediagrab_lib::models::{DownloadConfig, DownloadError, ProgressEvent};
+use mediagrab_lib::utils::paths::{self, FFMPEG_NAME, YTDLP_NAME};
+
+const USAGE: &str = "\
+Usage: mediagrab-cli [OPTIONS] [URL]...
+
+Options:
+  -b, --batch FILE      Download the links in a .txt or .csv file (- for stdin)
+  -p, --preset FILE     JSON download settings, e.g. {\"format\": \"audio-mp3\"}
+  -f, --format FORMAT   Output format (video-mp4, audio-mp3, mkv, flac, ...)
+  -q, --quality QUALITY Video quality (best, 2160p, 1080p, 720p, ...)
+  -o, --output DIR      Output folder (default: the current folder)
+  -j, --jobs N          Downloads to run at once (default: 1)
+      --json            Print queue events and a summary as JSON lines
+      --archive FILE    Skip and record downloads in a yt-dlp archive file
+      --yt-dlp PATH     yt-dlp executable (default: the app's copy or PATH)
+      --ffmpeg PATH     ffmpeg executable (default: the app's copy or PATH)
+  -v, --verbose         Log to stderr
+  -h, --help            Show this help
+  -V, --version         Show the version
+
+Batch lines can set their own format, quality and folder:
+  https://youtu.be/dQw4w9WgXcQ | format=mp3 | folder=/srv/music
+";
+
+/// Exit code for bad arguments (`EX_USAGE` from sysexits.h; 2 is taken by
+/// validation errors)
+const EXIT_USAGE: u8 = 64;
+
+/// Exit code after Ctrl+C (128 + SIGINT)
+const EXIT_INTERRUPTED: i32 = 130;
+
+/// Parsed command-line options
+#[derive(Debug, Default)]
+struct Options {
+    urls: Vec<String>,
+    batch_files: Vec<String>,
+    preset: Option<String>,
+    format: Option<String>,
+    quality: Option<String>,
+    output: Option<String>,
+    jobs: usize,
+    json: bool,
+    archive: Option<String>,
+    ytdlp: Option<String>,
+    ffmpeg: Option<String>,
+    verbose: bool,
+    help: bool,
+    version: bool,
+}
+
+/// How a download ended, short of failing
+enum Outcome {
+    Downloaded,
+    Skipped(String),
+}
+
+type DownloadResult = (QueueItem, Result<Outcome, DownloadError>);
+
+fn parse_args(args: impl Iterator<Item = String>) -> Result<Options, String> {
+    let mut options = Options {
+        jobs: 1,
+        ..Options::default()
+    };
+
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        // Both "--format mp3" and "--format=mp3"
+        let (name, inline) = match arg.split_once('=') {
+            Some((name, value)) if arg.starts_with("--") => (name.to_string(), Some(value.to_string())),
+            _ => (arg.clone(), None),
+        };
+        let mut value = || inline.clone().or_else(|| args.next()).ok_or(format!("{} needs a value", name));
+
+        match name.as_str() {
+            "-h" | "--help" => options.help = true,
+            "-V" | "--version" => options.version = true,
+            "-b" | "--batch" => options.batch_files.push(value()?),
+            "-p" | "--preset" => options.preset = Some(value()?),
+            "-f" | "--format" => options.format = Some(value()?),
+            "-q" | "--quality" => options.quality = Some(value()?),
+            "-o" | "--output" => options.output = Some(value()?),
+            "-j" | "--jobs" => {
+                let jobs = value()?;
+                options.jobs = jobs
+                    .parse()
+                    .ok()
+                    .filter(|jobs| *jobs > 0)
+                    .ok_or(format!("--jobs must be a positive number, not \"{}\"", jobs))?;
+            }
+            "--archive" => options.archive = Some(value()?),
+            "--yt-dlp" => options.ytdlp = Some(value()?),
+            "--ffmpeg" => options.ffmpeg = Some(value()?),
+            "--json" => options.json = true,
+            "-v" | "--verbose" => options.verbose = true,
+            other if other.starts_with('-') => return Err(format!("unknown option {}", other)),
+            _ => options.urls.push(arg),
+        }
+    }
+
+    Ok(options)
+}
+
+/// Settings every link starts from: the app's defaults, then the preset,
+/// then the format/quality/output options
+fn base_config(options: &Options) -> Result<DownloadConfig, String> {
+    let mut config = Preferences::default().download_config("");
+    config.output_folder = String::new();
+
+    if let Some(path) = &options.preset {
+        config = apply_preset(config, path)?;
+    }
+    if let Some(format) = &options.format {
+        config.format = parse_format(format)?;
+    }
+    if let Some(quality) = &options.quality {
+        config.quality = quality.parse().map_err(|_| format!("unknown quality \"{}\"", quality))?;
+    }
+    if let Some(output) = &options.output {
+        config.output_folder = output.clone();
+    }
+
+    Ok(config)
+}
+
+/// Overlays a JSON preset on `config`
+///
+/// A preset has the shape of a download config (like a subscription
+/// preset); fields it leaves out keep their value.
+fn apply_preset(config: DownloadConfig, path: &str) -> Result<DownloadConfig, String> {
+    let text = std::fs::read_to_string(path).map_err(|e| format!("Failed to read preset {}: {}", path, e))?;
+    let serde_json::Value::Object(fields) =
+        serde_json::from_str(&text).map_err(|e| format!("Invalid preset {}: {}", path, e))?
+    else {
+        return Err(format!("Invalid preset {}: expected a JSON object", path));
+    };
+
+    let mut merged = serde_json::to_value(&config).map_err(|e| e.to_string())?;
+    if let Some(object) = merged.as_object_mut() {
+        object.extend(fields);
+    }
+
+    let mut config: DownloadConfig =
+        serde_json::from_value(merged).map_err(|e| format!("Invalid preset {}: {}", path, e))?;
+    config.url.clear();
+    Ok(config)
+}
+
+/// Download configs for the URL arguments and batch files
+fn collect_configs(options: &Options, base: &DownloadConfig, cwd: &Path) -> Result<Vec<DownloadConfig>, String> {
+    let mut configs = Vec::new();
+
+    for arg in &options.urls {
+        let urls = extract_urls(arg);
+        if urls.is_empty() {
+            return Err(format!("not a link: {}", arg));
+        }
+        configs.extend(urls.into_iter().map(|url| DownloadConfig { url, ..base.clone() }));
+    }
+
+    for path in &options.batch_files {
+        let (text, csv) = if path == "-" {
+            let mut text = String::new();
+            std::io::stdin()
+                .read_to_string(&mut text)
+                .map_err(|e| format!("Failed to read stdin: {}", e))?;
+            (text, false)
+        } else {
+            let bytes = std::fs::read(path).map_err(|e| format!("Failed to read {}: {}", path, e))?;
+            let bytes = bytes.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(&bytes);
+            (String::from_utf8_lossy(bytes).into_owned(), path.to_lowercase().ends_with(".csv"))
+        };
+
+        let batch = parse_batch(&text, csv);
+        for issue in &batch.issues {
+            eprintln!("{}:{}: skipped: {}", path, issue.line, issue.error);
+        }
+        configs.extend(batch.entries.iter().map(|entry| entry.overrides.apply(base, &entry.url)));
+    }
+
+    // yt-dlp runs inside the output folder, so relative paths would nest
+    for config in &mut configs {
+        let folder = match config.output_folder.as_str() {
+            "" => cwd.to_path_buf(),
+            folder => cwd.join(folder),
+        };
+        config.output_folder = folder.to_string_lossy().to_string();
+    }
+
+    Ok(configs)
+}
+
+/// An executable given on the command line, made absolute against `cwd`
+/// (yt-dlp runs elsewhere); bare names are left for a PATH lookup
+fn executable_path(cwd: &Path, path: &str) -> PathBuf {
+    let path = Path::new(path);
+    if path.components().count() > 1 {
+        cwd.join(path)
+    } else {
+        path.to_path_buf()
+    }
+}
+
+/// yt-dlp settings and the ffmpeg executable (needed for post-processing)
+fn resolve_tools(options: &Options, cwd: &Path) -> Result<(SpawnConfig, Option<PathBuf>), DownloadError> {
+    let ytdlp = match &options.ytdlp {
+        Some(path) => executable_path(cwd, path),
+        None => paths::find_executable(YTDLP_NAME)?,
+    };
+    let ffmpeg = match &options.ffmpeg {
+        Some(path) => Some(executable_path(cwd, path)),
+        None => paths::find_executable(FFMPEG_NAME).ok(),
+    };
+
+    let spawn_config = SpawnConfig {
+        ytdlp_path: ytdlp.to_string_lossy().to_string(),
+        ffmpeg_location: ffmpeg
+            .as_deref()
+            .and_then(Path::parent)
+            .filter(|dir| !dir.as_os_str().is_empty())
+            .map(|dir| dir.to_string_lossy().to_string()),
+        // One archive for every output folder
+        download_archive: options
+            .archive
+            .as_ref()
+            .map(|archive| cwd.join(archive).to_string_lossy().to_string()),
+    };
+    Ok((spawn_config, ffmpeg))
+}
+
+/// Downloads every queued item, as many at once as the queue allows
+async fn run_queue(
+    queue: &SharedDownloadQueue,
+    count: usize,
+    spawn_config: Arc<SpawnConfig>,
+    ffmpeg: Option<Arc<PathBuf>>,
+) -> Vec<DownloadResult> {
+    let semaphore = queue.semaphore();
+    let mut tasks = JoinSet::new();
+
+    for _ in 0..count {
+        let Ok(permit) = semaphore.clone().acquire_owned().await else {
+            break;
+        };
+        let Some(item) = queue.pop_next().await else {
+            break;
+        };
+
+        let queue = queue.clone();
+        let spawn_config = spawn_config.clone();
+        let ffmpeg = ffmpeg.clone();
+        tasks.spawn(async move {
+            let result = download_item(&queue, &item, &spawn_config, ffmpeg.as_deref().map(PathBuf::as_path)).await;
+            if let Err(error) = &result {
+                queue.fail(item.id, error.clone()).await;
+            }
+            drop(permit);
+            (item, result)
+        });
+    }
+
+    let mut results = Vec::with_capacity(count);
+    while let Some(joined) = tasks.join_next().await {
+        if let Ok(result) = joined {
+            results.push(result);
+        }
+    }
+    results.sort_by_key(|(item, _)| item.id);
+    results
+}
+
+/// Runs one download, reporting progress through the queue
+async fn download_item(
+    queue: &SharedDownloadQueue,
+    item: &QueueItem,
+    spawn_config: &SpawnConfig,
+    ffmpeg: Option<&Path>,
+) -> Result<Outcome, DownloadError> {
+    let id = item.id;
+    let config = &item.config;
+
+    // Unlike the app, which only offers existing folders, create it like yt-dlp would
+    std::fs::create_dir_all(&config.output_folder)
+        .map_err(|e| DownloadError::FolderNotAccessible(format!("{}: {}", config.output_folder, e)))?;
+    prepare_output_folder(config)?;
+    let child = spawn_ytdlp(config, spawn_config).await?;
+
+    let (tx, mut rx) = mpsc::channel::<ProcessOutput>(100);
+    let stream = tokio::spawn(stream_process_output(child, tx));
+
+    let mut file_path = String::new();
+    let mut chapter_files = Vec::new();
+    let mut completed = false;
+    while let Some(output) = rx.recv().await {
+        match output {
+            ProcessOutput::Progress(event) => queue.update_progress(id, &event).await,
+            ProcessOutput::Merging => queue.update_status(id, QueueItemStatus::Merging).await,
+            ProcessOutput::WaitingForLive => queue.update_status(id, QueueItemStatus::WaitingForLive).await,
+            ProcessOutput::FilePath(path) => file_path = path,
+            ProcessOutput::ChapterFile(path) => chapter_files.push(path),
+            ProcessOutput::AlreadyArchived(title) => {
+                let reason = DownloadError::AlreadyArchived(title);
+                queue.skip(id, reason.clone()).await;
+                return Ok(Outcome::Skipped(reason.to_string()));
+            }
+            ProcessOutput::FilteredOut(reason) => {
+                let reason = DownloadError::FilteredOut(reason);
+                queue.skip(id, reason.clone()).await;
+                return Ok(Outcome::Skipped(reason.to_string()));
+            }
+            ProcessOutput::Error(error) => {
+                // Dropping the stream task kills yt-dlp
+                stream.abort();
+                return Err(error);
+            }
+            ProcessOutput::ExitError(code) => {
+                return Err(DownloadError::DownloadFailed(format!("Process exited with code {}", code)));
+            }
+            ProcessOutput::Terminated => {
+                return Err(DownloadError::DownloadFailed("yt-dlp was terminated".to_string()));
+            }
+            ProcessOutput::Completed(_) => {
+                completed = true;
+                break;
+            }
+        }
+    }
+    if !completed {
+        return Err(DownloadError::DownloadFailed("yt-dlp stopped without finishing".to_string()));
+    }
+
+    let subtitle_files = if config.subtitles.download_subtitles && !file_path.is_empty() {
+        find_subtitle_files(&file_path)
+    } else {
+        Vec::new()
+    };
+
+    // Same post-processing as the app's queue: loudness first (a failure
+    // keeps the file as it is), then the transcode preset
+    if let Some(target) = config
+        .loudness_target_lufs
+        .filter(|_| config.format.is_audio() && !file_path.is_empty())
+    {
+        match ffmpeg {
+            Some(ffmpeg) => {
+                queue.update_status(id, QueueItemStatus::Transcoding).await;
+                if let Err(e) = run_loudnorm(&ffmpeg.to_string_lossy(), &file_path, target).await {
+                    tracing::warn!("Skipping loudness normalization: {}", e);
+                }
+            }
+            None => tracing::warn!("Skipping loudness normalization: ffmpeg not found"),
+        }
+    }
+
+    if let Some(preset) = config.transcode_preset.filter(|_| !file_path.is_empty()) {
+        let ffmpeg = ffmpeg.ok_or_else(|| DownloadError::ExecutableNotFound(FFMPEG_NAME.to_string()))?;
+        queue.update_status(id, QueueItemStatus::Transcoding).await;
+
+        let (tx, mut rx) = mpsc::channel::<ProgressEvent>(100);
+        let progress = async {
+            while let Some(event) = rx.recv().await {
+                queue.update_progress(id, &event).await;
+            }
+        };
+        let ffmpeg = ffmpeg.to_string_lossy();
+        let (transcoded, _) = tokio::join!(run_transcode(&ffmpeg, &file_path, preset, tx), progress);
+        file_path = transcoded?;
+    }
+
+    queue
+        .complete_with_files(id, file_path.clone(), chapter_files, subtitle_files)
+        .await;
+    Ok(Outcome::Downloaded)
+}
+
+/// Prints queue events: JSON lines, or status lines plus a live progress
+/// line on a terminal
+async fn report_events(mut events: mpsc::UnboundedReceiver<QueueEvent>, json: bool) {
+    let live_progress = !json && std::io::stderr().is_terminal();
+    let mut statuses: HashMap<QueueItemId, QueueItemStatus> = HashMap::new();
+
+    while let Some(event) = events.recv().await {
+        if json {
+            if let Ok(line) = serde_json::to_string(&event) {
+                println!("{}", line);
+            }
+            continue;
+        }
+
+        let QueueEvent::ItemUpdated { item } = event else {
+            continue;
+        };
+
+        if statuses.insert(item.id, item.status) == Some(item.status) {
+            if live_progress && matches!(item.status, QueueItemStatus::Downloading | QueueItemStatus::Recording) {
+                let eta = item.eta_seconds.map(format_eta).unwrap_or_else(|| "--:--".to_string());
+                eprint!("\r\x1b[2K[{}] {:5.1}%  {}  ETA {}", item.id, item.progress, item.speed, eta);
+            }
+            continue;
+        }
+
+        if live_progress {
+            eprint!("\r\x1b[2K");
+        }
+        let name = item.title.as_deref().unwrap_or(&item.config.url);
+        match item.status {
+            QueueItemStatus::Downloading => println!("[{}] Downloading {}", item.id, name),
+            QueueItemStatus::Recording => println!("[{}] Recording {}", item.id, name),
+            QueueItemStatus::WaitingForLive => println!("[{}] Waiting for the stream to start", item.id),
+            QueueItemStatus::Merging => println!("[{}] Merging", item.id),
+            QueueItemStatus::Transcoding => println!("[{}] Post-processing", item.id),
+            QueueItemStatus::Completed => {
+                let path = item.file_path.as_deref().filter(|path| !path.is_empty());
+                println!("[{}] Saved {}", item.id, path.unwrap_or(name))
+            }
+            QueueItemStatus::Skipped => {
+                println!("[{}] Skipped: {}", item.id, item.skip_reason.as_deref().unwrap_or(name))
+            }
+            QueueItemStatus::Failed => {
+                println!("[{}] Failed: {}", item.id, item.error.as_deref().unwrap_or("unknown error"))
+            }
+            _ => {}
+        }
+    }
+
+    if live_progress {
+        eprint!("\r\x1b[2K");
+    }
+}
+
+fn format_eta(seconds: u64) -> String {
+    match seconds {
+        s if s >= 3600 => format!("{}:{:02}:{:02}", s / 3600, s % 3600 / 60, s % 60),
+        s => format!("{}:{:02}", s / 60, s % 60),
+    }
+}
+
+/// Prints how every download went and returns the exit code
+fn print_summary(results: &[DownloadResult], json: bool) -> u8 {
+    let downloaded = results
+        .iter()
+        .filter(|(_, result)| matches!(result, Ok(Outcome::Downloaded)))
+        .count();
+    let skipped: Vec<(&QueueItem, &String)> = results
+        .iter()
+        .filter_map(|(item, result)| match result {
+            Ok(Outcome::Skipped(reason)) => Some((item, reason)),
+            _ => None,
+        })
+        .collect();
+    let failed: Vec<(&QueueItem, &DownloadError)> = results
+        .iter()
+        .filter_map(|(item, result)| result.as_ref().err().map(|error| (item, error)))
+        .collect();
+
+    let exit_code = summary_exit_code(results);
+
+    if json {
+        let failures: Vec<_> = failed
+            .iter()
+            .map(|(item, error)| {
+                serde_json::json!({
+                    "id": item.id,
+                    "url": item.config.url,
+                    "category": error.category(),
+                    "error": error.to_string(),
+                })
+            })
+            .collect();
+        let summary = serde_json::json!({
+            "type": "summary",
+            "downloaded": downloaded,
+            "skipped": skipped.len(),
+            "failed": failures.len(),
+            "failures": failures,
+            "exitCode": exit_code,
+        });
+        println!("{}", summary);
+    } else {
+        for (item, reason) in &skipped {
+            eprintln!("{}: {}", item.config.url, reason);
+        }
+        for (item, error) in &failed {
+            eprintln!("{}: {}", item.config.url, error);
+            if let Some(action) = error.suggested_action() {
+                eprintln!("  {}", action);
+            }
+        }
+        println!("{} downloaded, {} skipped, {} failed", downloaded, skipped.len(), failed.len());
+    }
+
+    exit_code
+}
+
+/// The first failure's exit code, or 0 when every link was downloaded or
+/// skipped
+fn summary_exit_code(results: &[DownloadResult]) -> u8 {
+    results
+        .iter()
+        .filter_map(|(_, result)| result.as_ref().err())
+        .map(DownloadError::exit_code)
+        .find(|code| *code != 0)
+        .unwrap_or(0)
+        .clamp(0, 255) as u8
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let options = match parse_args(std::env::args().skip(1)) {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("mediagrab-cli: {}\n\n{}", e, USAGE);
+            return ExitCode::from(EXIT_USAGE);
+        }
+    };
+    if options.help {
+        print!("{}", USAGE);
+        return ExitCode::SUCCESS;
+    }
+    if options.version {
+        println!("mediagrab-cli {}", env!("CARGO_PKG_VERSION"));
+        return ExitCode::SUCCESS;
+    }
+
+    if options.verbose {
+        tracing_subscriber::fmt().with_writer(std::io::stderr).init();
+    }
+
+    let cwd = match std::env::current_dir() {
+        Ok(cwd) => cwd,
+        Err(e) => {
+            eprintln!("mediagrab-cli: {}", e);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let configs = match base_config(&options).and_then(|base| collect_configs(&options, &base, &cwd)) {
+        Ok(configs) if !configs.is_empty() => configs,
+        Ok(_) => {
+            eprintln!("mediagrab-cli: no links to download\n\n{}", USAGE);
+            return ExitCode::from(EXIT_USAGE);
+        }
+        Err(e) => {
+            eprintln!("mediagrab-cli: {}", e);
+            return ExitCode::from(EXIT_USAGE);
+        }
+    };
+
+    let (spawn_config, ffmpeg) = match resolve_tools(&options, &cwd) {
+        Ok(tools) => tools,
+        Err(e) => {
+            eprintln!("mediagrab-cli: {}", e);
+            return ExitCode::from(e.exit_code() as u8);
+        }
+    };
+
+    let (event_tx, event_rx) = mpsc::unbounded_channel();
+    let queue = create_download_queue(options.jobs, event_tx);
+    let reporter = tokio::spawn(report_events(event_rx, options.json));
+
+    let count = queue.add_batch(configs).await.len();
+    let results = tokio::select! {
+        results = run_queue(&queue, count, Arc::new(spawn_config), ffmpeg.map(Arc::new)) => results,
+        _ = tokio::signal::ctrl_c() => {
+            // yt-dlp gets the same Ctrl+C from the terminal
+            eprintln!("\nInterrupted");
+            std::process::exit(EXIT_INTERRUPTED);
+        }
+    };
+
+    // Closes the event channel so the reporter finishes
+    drop(queue);
+    let _ = reporter.await;
+
+    ExitCode::from(print_summary(&results, options.json))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use mediagrab_lib::models::{AudioCodec, OutputFormat, Quality};
+
+    fn parse(args: &[&str]) -> Result<Options, String> {
+        parse_args(args.iter().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn test_parse_args() {
+        let args = ["--format=mp3", "-j", "3", "https://youtu.be/dQw4w9WgXcQ", "--archive", "a.txt", "--json"];
+        let options = parse(&args).unwrap();
+        assert_eq!(options.format.as_deref(), Some("mp3"));
+        assert_eq!(options.jobs, 3);
+        assert_eq!(options.urls, ["https://youtu.be/dQw4w9WgXcQ"]);
+        assert_eq!(options.archive.as_deref(), Some("a.txt"));
+        assert!(options.json);
+        assert_eq!(parse(&[]).unwrap().jobs, 1);
+
+        assert!(parse(&["-j", "0"]).is_err());
+        assert!(parse(&["--output"]).is_err());
+        assert!(parse(&["--bogus"]).is_err());
+    }
+
+    #[test]
+    fn test_preset_and_options() {
+        let path = std::env::temp_dir().join(format!("mediagrab-cli-preset-{}.json", std::process::id()));
+        std::fs::write(&path, r#"{"format": "audio-flac", "quality": "720p", "splitChapters": true}"#).unwrap();
+        let preset = path.to_string_lossy().to_string();
+
+        let options = parse(&["--preset", &preset, "--format", "mp3"]).unwrap();
+        let config = base_config(&options).unwrap();
+        // Options win over the preset, which wins over the defaults
+        assert_eq!(config.format, OutputFormat::Audio(AudioCodec::Mp3));
+        assert_eq!(config.quality, Quality::P720);
+        assert!(config.split_chapters);
+
+        std::fs::write(&path, "[1, 2]").unwrap();
+        assert!(base_config(&options).is_err());
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_relative_paths_use_the_working_directory() {
+        let cwd = std::env::temp_dir().join("work");
+        let args = [
+            "-o", "music", "--archive", "archive.txt", "--yt-dlp", "bin/yt-dlp", "--ffmpeg", "ffmpeg",
+            "https://youtu.be/dQw4w9WgXcQ",
+        ];
+        let options = parse(&args).unwrap();
+
+        let configs = collect_configs(&options, &base_config(&options).unwrap(), &cwd).unwrap();
+        assert_eq!(configs[0].output_folder, cwd.join("music").to_string_lossy());
+
+        let (spawn_config, ffmpeg) = resolve_tools(&options, &cwd).unwrap();
+        assert_eq!(spawn_config.download_archive, Some(cwd.join("archive.txt").to_string_lossy().to_string()));
+        assert_eq!(spawn_config.ytdlp_path, cwd.join("bin").join("yt-dlp").to_string_lossy());
+        // A bare name is looked up on PATH
+        assert_eq!(ffmpeg, Some(PathBuf::from("ffmpeg")));
+    }
+
+    #[test]
+    fn test_summary_exit_code() {
+        let item = |id| QueueItem::new(id, Preferences::default().download_config("https://youtu.be/dQw4w9WgXcQ"));
+        let mut results: Vec<DownloadResult> = vec![
+            (item(1), Ok(Outcome::Downloaded)),
+            (item(2), Ok(Outcome::Skipped("archived".to_string()))),
+        ];
+        assert_eq!(summary_exit_code(&results), 0);
+
+        results.push((item(3), Err(DownloadError::NetworkError("timeout".to_string()))));
+        results.push((item(4), Err(DownloadError::InvalidUrl("x".to_string()))));
+        assert_eq!(summary_exit_code(&results), 3);
+
+        // Bad arguments are told apart from failed downloads
+        assert_ne!(i32::from(EXIT_USAGE), DownloadError::InvalidUrl("x".to_string()).exit_code());
+    }
+}
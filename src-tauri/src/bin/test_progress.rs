@@ -83,6 +83,15 @@ async fn main() {
                 ParsedLine::Merging => {
                     println!("[MERGING] {}", line);
                 }
+                ParsedLine::AlreadyArchived(title) => {
+                    println!("[ARCHIVED] {}", title);
+                }
+                ParsedLine::FilteredOut(reason) => {
+                    println!("[FILTERED] {}", reason);
+                }
+                ParsedLine::WaitingForLive => {
+                    println!("[WAITING] {}", line);
+                }
                 ParsedLine::Unknown => {
                     println!("[OTHER] {}", line);
                 }
@@ -0,0 +1,609 @@
+//! Local HTTP API
+//!
+//! An opt-in JSON API on 127.0.0.1 for browser extensions and scripts,
+//! mirroring the queue commands:
+//!
+//! - `GET /api/queue` lists the queue items
+//! - `POST /api/queue` queues a link: `{"url": "...", "force": false}`,
+//!   optionally with a `format` and `quality` replacing the defaults
+//! - `POST /api/queue/{id}/cancel` cancels an item
+//! - `GET /api/events` streams `QueueEvent`s as server-sent events
+//!
+//! Every request needs `Authorization: Bearer <token>`, or `?token=<token>`
+//! for event streams (EventSource can't set headers). The token is created
+//! on first use and kept in the app data folder.
+
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+
+use serde::Deserialize;
+use serde_json::{json, Value};
+use tauri::AppHandle;
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncReadExt, AsyncWrite, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::broadcast;
+use tokio::task::{JoinHandle, JoinSet};
+
+use crate::commands::preferences::{get_saved_preferences, Preferences};
+use crate::commands::queue::{add_to_queue, QueueAddResult};
+use crate::download::extract_urls;
+use crate::download::queue::{QueueEvent, QueueItemId, SharedDownloadQueue};
+use crate::models::{DownloadConfig, DownloadError, OutputFormat, Quality};
+use crate::utils::paths;
+
+/// Seconds between checks of the API settings
+const POLL_INTERVAL_SECONDS: u64 = 2;
+
+/// Time a client gets to send its request
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Comment sent on idle event streams so dead connections get noticed
+const KEEPALIVE_INTERVAL: Duration = Duration::from_secs(15);
+
+/// Largest request line plus headers
+const MAX_HEAD_BYTES: usize = 16 * 1024;
+
+/// Largest request body (an add request is a few hundred bytes)
+const MAX_BODY_BYTES: usize = 1024 * 1024;
+
+/// Random bytes in a token (hex-encoded, so twice as many characters)
+const TOKEN_BYTES: usize = 32;
+
+/// Where to reach the API and how to authenticate
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiInfo {
+    /// Base URL, e.g. `http://127.0.0.1:47821/api`
+    pub url: String,
+    pub token: String,
+}
+
+/// Returns the API address and token, creating the token if needed
+#[tauri::command]
+pub async fn api_get_info(app: AppHandle) -> Result<ApiInfo, String> {
+    let port = get_saved_preferences(&app).api.port;
+    let token = load_or_create_token().map_err(|e| e.to_string())?;
+    Ok(ApiInfo {
+        url: format!("http://127.0.0.1:{}/api", port),
+        token,
+    })
+}
+
+/// Replaces the API token; clients using the old one are disconnected
+#[tauri::command]
+pub async fn api_regenerate_token(app: AppHandle) -> Result<ApiInfo, String> {
+    let path = paths::get_api_token_path().map_err(|e| e.to_string())?;
+    save_new_token(&path).map_err(|e| e.to_string())?;
+    api_get_info(app).await
+}
+
+/// Reads the API token, creating one on first use
+pub fn load_or_create_token() -> Result<String, DownloadError> {
+    let path = paths::get_api_token_path()?;
+    if let Ok(token) = fs::read_to_string(&path) {
+        let token = token.trim();
+        if token.len() >= TOKEN_BYTES {
+            return Ok(token.to_string());
+        }
+    }
+    save_new_token(&path)
+}
+
+fn save_new_token(path: &Path) -> Result<String, DownloadError> {
+    let mut bytes = [0u8; TOKEN_BYTES];
+    getrandom::getrandom(&mut bytes)
+        .map_err(|e| DownloadError::GenericError(format!("Failed to generate API token: {}", e)))?;
+    let token: String = bytes.iter().map(|b| format!("{:02x}", b)).collect();
+
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir).map_err(|e| DownloadError::GenericError(format!("Failed to save API token: {}", e)))?;
+    }
+    fs::write(path, &token).map_err(|e| DownloadError::GenericError(format!("Failed to save API token: {}", e)))?;
+
+    // Only the user should be able to read it
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let _ = fs::set_permissions(path, fs::Permissions::from_mode(0o600));
+    }
+
+    Ok(token)
+}
+
+/// Runs the API server while it's enabled in the preferences
+///
+/// The server is restarted when the port or token changes.
+pub async fn start_api_server(app: AppHandle, queue: SharedDownloadQueue, events: broadcast::Sender<QueueEvent>) {
+    // Port and token the current server was started with
+    let mut current: Option<(u16, String)> = None;
+    let mut server: Option<JoinHandle<()>> = None;
+
+    loop {
+        let options = get_saved_preferences(&app).api;
+        let wanted = if options.enabled {
+            match load_or_create_token() {
+                Ok(token) => Some((options.port, token)),
+                Err(e) => {
+                    tracing::error!("Local API disabled: {}", e);
+                    None
+                }
+            }
+        } else {
+            None
+        };
+
+        if wanted != current {
+            if let Some(task) = server.take() {
+                task.abort();
+                // Wait for the port to be released before binding it again
+                let _ = task.await;
+                tracing::info!("Local API stopped");
+            }
+
+            if let Some((port, token)) = &wanted {
+                if !options.is_valid_port() {
+                    tracing::warn!("Local API not started: port {} is reserved, choose 1024 or above", port);
+                } else {
+                    match TcpListener::bind(("127.0.0.1", *port)).await {
+                        Ok(listener) => {
+                            tracing::info!("Local API listening on http://127.0.0.1:{}/api", port);
+                            let context = Arc::new(ApiContext {
+                                app: app.clone(),
+                                queue: queue.clone(),
+                                events: events.clone(),
+                                token: token.clone(),
+                            });
+                            server = Some(tokio::spawn(serve(listener, context)));
+                        }
+                        Err(e) => tracing::warn!("Local API not started: port {} unavailable: {}", port, e),
+                    }
+                }
+            }
+            current = wanted;
+        }
+
+        tokio::time::sleep(Duration::from_secs(POLL_INTERVAL_SECONDS)).await;
+    }
+}
+
+/// What request handlers need
+struct ApiContext {
+    app: AppHandle,
+    queue: SharedDownloadQueue,
+    events: broadcast::Sender<QueueEvent>,
+    token: String,
+}
+
+/// Accepts connections until the task is aborted, which also closes every
+/// open connection
+async fn serve(listener: TcpListener, context: Arc<ApiContext>) {
+    let mut connections = JoinSet::new();
+    loop {
+        match listener.accept().await {
+            Ok((stream, _)) => {
+                while connections.try_join_next().is_some() {}
+                connections.spawn(handle_connection(stream, context.clone()));
+            }
+            Err(e) => {
+                tracing::warn!("Local API accept failed: {}", e);
+                tokio::time::sleep(Duration::from_millis(100)).await;
+            }
+        }
+    }
+}
+
+async fn handle_connection(stream: TcpStream, context: Arc<ApiContext>) {
+    let (read, mut write) = stream.into_split();
+    let mut reader = BufReader::new(read);
+
+    let request = match tokio::time::timeout(REQUEST_TIMEOUT, read_request(&mut reader)).await {
+        Ok(Ok(request)) => request,
+        Ok(Err(error)) => return write_error(&mut write, error).await,
+        Err(_) => return,
+    };
+
+    // CORS preflight from extension or page scripts
+    if request.method == "OPTIONS" {
+        let _ = write_response(&mut write, 204, None).await;
+        return;
+    }
+
+    let route = match route(&request.method, &request.path) {
+        Ok(route) => route,
+        Err(error) => return write_error(&mut write, error).await,
+    };
+    if !is_authorized(&request, &context.token) {
+        return write_error(&mut write, HttpError::new(401, "Missing or wrong API token")).await;
+    }
+
+    let result = match route {
+        Route::Events => return stream_events(&mut write, context.events.subscribe()).await,
+        Route::ListQueue => to_json(&context.queue.get_all().await).map(|items| (200, items)),
+        Route::AddToQueue => add_link(&context, &request.body).await,
+        Route::Cancel(id) => cancel_item(&context, id).await,
+    };
+    match result {
+        Ok((status, body)) => {
+            let _ = write_response(&mut write, status, Some(&body)).await;
+        }
+        Err(error) => write_error(&mut write, error).await,
+    }
+}
+
+async fn add_link(context: &ApiContext, body: &[u8]) -> Result<(u16, Value), HttpError> {
+    let preferences = get_saved_preferences(&context.app);
+    let (config, force) = parse_add_body(body, &preferences).map_err(|e| HttpError::new(400, e))?;
+    tracing::info!("Queueing {} from the local API", config.url);
+
+    let result = add_to_queue(&context.app, &context.queue, config, force, None)
+        .await
+        .map_err(|e| HttpError::new(400, e))?;
+    let status = match result {
+        QueueAddResult::Added { .. } => 201,
+        QueueAddResult::Duplicate { .. } => 200,
+    };
+    to_json(&result).map(|body| (status, body))
+}
+
+async fn cancel_item(context: &ApiContext, id: QueueItemId) -> Result<(u16, Value), HttpError> {
+    if context.queue.get(id).await.is_none() {
+        return Err(HttpError::new(404, format!("No queue item {}", id)));
+    }
+    context
+        .queue
+        .cancel(id)
+        .await
+        .map_err(|e| HttpError::new(400, e.to_string()))?;
+    to_json(&context.queue.get(id).await).map(|item| (200, item))
+}
+
+/// Body of an add request
+///
+/// Only these fields are accepted; folders, cookies and the proxy always
+/// come from the preferences, so a caller can't write files elsewhere or
+/// read the user's cookies.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+struct ApiAddRequest {
+    url: String,
+    #[serde(default)]
+    format: Option<OutputFormat>,
+    #[serde(default)]
+    quality: Option<Quality>,
+    /// Queue duplicates anyway
+    #[serde(default)]
+    force: Option<bool>,
+}
+
+/// Download config for an add request, and whether to queue duplicates
+fn parse_add_body(body: &[u8], preferences: &Preferences) -> Result<(DownloadConfig, bool), String> {
+    let request: ApiAddRequest =
+        serde_json::from_slice(body).map_err(|e| format!("Invalid request: {}", e))?;
+    let url = extract_urls(&request.url)
+        .into_iter()
+        .next()
+        .ok_or_else(|| format!("Not a link: {}", request.url))?;
+
+    let mut config = preferences.download_config(&url);
+    config.format = request.format.unwrap_or(config.format);
+    config.quality = request.quality.unwrap_or(config.quality);
+    // Left empty, the folder is picked for the final format when queued
+    config.output_folder = String::new();
+    Ok((config, request.force.unwrap_or(false)))
+}
+
+fn to_json<T: serde::Serialize>(value: &T) -> Result<Value, HttpError> {
+    serde_json::to_value(value).map_err(|e| HttpError::new(500, e.to_string()))
+}
+
+/// Sends queue events until the client disconnects
+async fn stream_events<W: AsyncWrite + Unpin>(write: &mut W, mut events: broadcast::Receiver<QueueEvent>) {
+    let head = format!(
+        "HTTP/1.1 200 OK\r\nContent-Type: text/event-stream\r\nCache-Control: no-cache\r\n{}\r\n",
+        CORS_HEADERS
+    );
+    if write.write_all(head.as_bytes()).await.is_err() {
+        return;
+    }
+
+    let mut keepalive = tokio::time::interval(KEEPALIVE_INTERVAL);
+    loop {
+        let chunk = tokio::select! {
+            event = events.recv() => match event {
+                Ok(event) => match serde_json::to_string(&event) {
+                    Ok(json) => format!("data: {}\n\n", json),
+                    Err(_) => continue,
+                },
+                // Too slow to keep up; the client should reload the queue
+                Err(broadcast::error::RecvError::Lagged(skipped)) => format!("event: lagged\ndata: {}\n\n", skipped),
+                Err(broadcast::error::RecvError::Closed) => return,
+            },
+            _ = keepalive.tick() => ": keepalive\n\n".to_string(),
+        };
+        if write.write_all(chunk.as_bytes()).await.is_err() || write.flush().await.is_err() {
+            return;
+        }
+    }
+}
+
+/// Headers letting extension and page scripts call the API (the token is
+/// what keeps other pages out)
+const CORS_HEADERS: &str = "Access-Control-Allow-Origin: *\r\n\
+    Access-Control-Allow-Methods: GET, POST, OPTIONS\r\n\
+    Access-Control-Allow-Headers: Authorization, Content-Type\r\n";
+
+async fn write_response<W: AsyncWrite + Unpin>(write: &mut W, status: u16, body: Option<&Value>) -> std::io::Result<()> {
+    let body = body.map(Value::to_string).unwrap_or_default();
+    let content_type = if body.is_empty() { "" } else { "Content-Type: application/json\r\n" };
+    let response = format!(
+        "HTTP/1.1 {} {}\r\n{}Content-Length: {}\r\n{}Connection: close\r\n\r\n{}",
+        status,
+        status_text(status),
+        content_type,
+        body.len(),
+        CORS_HEADERS,
+        body
+    );
+    write.write_all(response.as_bytes()).await?;
+    write.shutdown().await
+}
+
+async fn write_error<W: AsyncWrite + Unpin>(write: &mut W, error: HttpError) {
+    let _ = write_response(write, error.status, Some(&json!({ "error": error.message }))).await;
+}
+
+fn status_text(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        201 => "Created",
+        204 => "No Content",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        413 => "Payload Too Large",
+        431 => "Request Header Fields Too Large",
+        501 => "Not Implemented",
+        _ => "Internal Server Error",
+    }
+}
+
+/// An error answered with its status and a JSON message
+#[derive(Debug, PartialEq)]
+struct HttpError {
+    status: u16,
+    message: String,
+}
+
+impl HttpError {
+    fn new(status: u16, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            message: message.into(),
+        }
+    }
+}
+
+/// A parsed HTTP request
+#[derive(Debug, Default)]
+struct Request {
+    method: String,
+    path: String,
+    /// Query parameters (not percent-decoded; tokens and ids don't need it)
+    query: HashMap<String, String>,
+    /// Header names are lowercase
+    headers: HashMap<String, String>,
+    body: Vec<u8>,
+}
+
+/// Reads one HTTP/1.1 request with a `Content-Length` body (if any)
+async fn read_request<R: AsyncBufRead + Unpin>(reader: &mut R) -> Result<Request, HttpError> {
+    let mut budget = MAX_HEAD_BYTES;
+    let request_line = read_line(reader, &mut budget).await?;
+    let mut parts = request_line.split_whitespace();
+    let (Some(method), Some(target), Some(version)) = (parts.next(), parts.next(), parts.next()) else {
+        return Err(HttpError::new(400, "Malformed request line"));
+    };
+    if !version.starts_with("HTTP/1.") {
+        return Err(HttpError::new(400, "Unsupported HTTP version"));
+    }
+
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+    let mut request = Request {
+        method: method.to_string(),
+        path: path.to_string(),
+        query: query
+            .split('&')
+            .filter_map(|pair| pair.split_once('='))
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect(),
+        ..Request::default()
+    };
+
+    loop {
+        let line = read_line(reader, &mut budget).await?;
+        if line.is_empty() {
+            break;
+        }
+        let (name, value) = line
+            .split_once(':')
+            .ok_or_else(|| HttpError::new(400, "Malformed header"))?;
+        request
+            .headers
+            .insert(name.trim().to_lowercase(), value.trim().to_string());
+    }
+
+    if request.headers.contains_key("transfer-encoding") {
+        return Err(HttpError::new(501, "Chunked requests aren't supported"));
+    }
+    let length = match request.headers.get("content-length") {
+        Some(length) => length
+            .parse::<usize>()
+            .map_err(|_| HttpError::new(400, "Invalid Content-Length"))?,
+        None => 0,
+    };
+    if length > MAX_BODY_BYTES {
+        return Err(HttpError::new(413, "Request body too large"));
+    }
+    request.body = vec![0; length];
+    reader
+        .read_exact(&mut request.body)
+        .await
+        .map_err(|_| HttpError::new(400, "Incomplete request body"))?;
+
+    Ok(request)
+}
+
+/// Reads a CRLF- or LF-terminated line, counting it against `budget`
+async fn read_line<R: AsyncBufRead + Unpin>(reader: &mut R, budget: &mut usize) -> Result<String, HttpError> {
+    let mut line = String::new();
+    let read = (&mut *reader)
+        .take(*budget as u64)
+        .read_line(&mut line)
+        .await
+        .map_err(|_| HttpError::new(400, "Unreadable request"))?;
+    if !line.ends_with('\n') {
+        return Err(if read == *budget {
+            HttpError::new(431, "Request headers too large")
+        } else {
+            HttpError::new(400, "Incomplete request")
+        });
+    }
+    *budget -= read;
+    Ok(line.trim_end_matches(['\r', '\n']).to_string())
+}
+
+/// API endpoints
+#[derive(Debug, PartialEq)]
+enum Route {
+    ListQueue,
+    AddToQueue,
+    Cancel(QueueItemId),
+    Events,
+}
+
+fn route(method: &str, path: &str) -> Result<Route, HttpError> {
+    let segments: Vec<&str> = path.trim_matches('/').split('/').collect();
+    match (method, segments.as_slice()) {
+        ("GET", ["api", "queue"]) => Ok(Route::ListQueue),
+        ("POST", ["api", "queue"]) => Ok(Route::AddToQueue),
+        ("POST", ["api", "queue", id, "cancel"]) => id
+            .parse()
+            .map(Route::Cancel)
+            .map_err(|_| HttpError::new(404, format!("No queue item {}", id))),
+        ("GET", ["api", "events"]) => Ok(Route::Events),
+        (_, ["api", "queue"] | ["api", "queue", _, "cancel"] | ["api", "events"]) => {
+            Err(HttpError::new(405, format!("{} isn't allowed on {}", method, path)))
+        }
+        _ => Err(HttpError::new(404, format!("No endpoint {}", path))),
+    }
+}
+
+/// Whether the request carries the token, compared in constant time
+fn is_authorized(request: &Request, token: &str) -> bool {
+    let given = request
+        .headers
+        .get("authorization")
+        .and_then(|value| value.strip_prefix("Bearer "))
+        .or_else(|| request.query.get("token").map(String::as_str));
+    given.is_some_and(|given| {
+        given.len() == token.len()
+            && given
+                .bytes()
+                .zip(token.bytes())
+                .fold(0u8, |diff, (a, b)| diff | (a ^ b))
+                == 0
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_read_request() {
+        let raw = b"POST /api/queue?token=abc HTTP/1.1\r\nHost: 127.0.0.1\r\nContent-Type: application/json\r\nContent-Length: 13\r\n\r\n{\"url\":\"x\"}..";
+        let request = read_request(&mut &raw[..]).await.unwrap();
+        assert_eq!(request.method, "POST");
+        assert_eq!(request.path, "/api/queue");
+        assert_eq!(request.query.get("token").map(String::as_str), Some("abc"));
+        assert_eq!(request.headers.get("content-type").map(String::as_str), Some("application/json"));
+        assert_eq!(request.body, b"{\"url\":\"x\"}..");
+
+        let error = read_request(&mut &b"GET /api/queue HTTP/1.1\r\nContent-Length: 5\r\n\r\nab"[..]).await;
+        assert_eq!(error.unwrap_err().status, 400);
+        let error = read_request(&mut &b"GET /api/queue HTTP/1.1\r\nContent-Length: 99999999\r\n\r\n"[..]).await;
+        assert_eq!(error.unwrap_err().status, 413);
+        let huge = format!("GET /{} HTTP/1.1\r\n\r\n", "a".repeat(MAX_HEAD_BYTES));
+        assert_eq!(read_request(&mut huge.as_bytes()).await.unwrap_err().status, 431);
+    }
+
+    #[test]
+    fn test_route() {
+        assert_eq!(route("GET", "/api/queue"), Ok(Route::ListQueue));
+        assert_eq!(route("POST", "/api/queue/"), Ok(Route::AddToQueue));
+        assert_eq!(route("POST", "/api/queue/42/cancel"), Ok(Route::Cancel(42)));
+        assert_eq!(route("GET", "/api/events"), Ok(Route::Events));
+        assert_eq!(route("DELETE", "/api/queue").unwrap_err().status, 405);
+        assert_eq!(route("POST", "/api/queue/abc/cancel").unwrap_err().status, 404);
+        assert_eq!(route("GET", "/").unwrap_err().status, 404);
+    }
+
+    #[test]
+    fn test_is_authorized() {
+        let mut request = Request::default();
+        assert!(!is_authorized(&request, "secret"));
+
+        request.headers.insert("authorization".to_string(), "Bearer secret".to_string());
+        assert!(is_authorized(&request, "secret"));
+        assert!(!is_authorized(&request, "secret2"));
+
+        let request = Request {
+            query: HashMap::from([("token".to_string(), "secret".to_string())]),
+            ..Request::default()
+        };
+        assert!(is_authorized(&request, "secret"));
+    }
+
+    #[test]
+    fn test_parse_add_body() {
+        let preferences = Preferences::default();
+
+        let (config, force) = parse_add_body(
+            br#"{"url": "https://youtu.be/dQw4w9WgXcQ", "format": "audio-mp3", "force": true}"#,
+            &preferences,
+        )
+        .unwrap();
+        assert_eq!(config.url, "https://youtu.be/dQw4w9WgXcQ");
+        assert_eq!(config.format, "audio-mp3".parse::<OutputFormat>().unwrap());
+        assert_eq!(config.quality, preferences.quality);
+        assert!(config.output_folder.is_empty());
+        assert!(force);
+
+        let (config, force) = parse_add_body(br#"{"url": "https://youtu.be/dQw4w9WgXcQ"}"#, &preferences).unwrap();
+        assert_eq!(config.format, preferences.format);
+        assert!(!force);
+
+        assert!(parse_add_body(br#"{"format": "audio-mp3"}"#, &preferences).is_err());
+        assert!(parse_add_body(br#"{"url": "not a link"}"#, &preferences).is_err());
+        assert!(parse_add_body(br#"{"url": "https://youtu.be/x", "quality": "huge"}"#, &preferences).is_err());
+        assert!(parse_add_body(b"[]", &preferences).is_err());
+    }
+
+    #[test]
+    fn test_parse_add_body_rejects_paths_and_credentials() {
+        let preferences = Preferences::default();
+        for field in [
+            r#""outputFolder": "/tmp/elsewhere""#,
+            r#""cookiesFilePath": "/home/user/cookies.txt""#,
+            r#""cookiesFromBrowser": "chrome""#,
+            r#""proxyUrl": "http://example.com:8080""#,
+        ] {
+            let body = format!(r#"{{"url": "https://youtu.be/dQw4w9WgXcQ", {}}}"#, field);
+            assert!(parse_add_body(body.as_bytes(), &preferences).is_err(), "{} accepted", field);
+        }
+    }
+}
@@ -0,0 +1,98 @@
+//! Download archive commands
+//!
+//! List, import, export and prune the yt-dlp download archive kept in the
+//! app data directory.
+
+use std::path::Path;
+
+use tauri_plugin_dialog::DialogExt;
+
+use crate::download::{merge_entries, read_archive, remove_entries, write_archive};
+use crate::models::ArchiveEntry;
+use crate::utils::paths;
+
+/// Reads the app's archive file
+fn load_archive() -> Result<(std::path::PathBuf, Vec<ArchiveEntry>), String> {
+    let path = paths::get_download_archive_path().map_err(|e| e.to_string())?;
+    let entries = read_archive(&path).map_err(|e| e.to_string())?;
+    Ok((path, entries))
+}
+
+/// Lists every entry in the download archive, oldest first
+#[tauri::command]
+pub async fn archive_list() -> Result<Vec<ArchiveEntry>, String> {
+    load_archive().map(|(_, entries)| entries)
+}
+
+/// Merges an archive file (e.g. from another machine or a plain yt-dlp setup)
+/// into the app's archive
+///
+/// Returns the number of entries that were new.
+#[tauri::command]
+pub async fn archive_import(path: String) -> Result<usize, String> {
+    let imported = read_archive(Path::new(&path)).map_err(|e| e.to_string())?;
+    let (archive_path, mut entries) = load_archive()?;
+
+    let added = merge_entries(&mut entries, imported);
+    if added > 0 {
+        write_archive(&archive_path, &entries).map_err(|e| e.to_string())?;
+    }
+
+    tracing::info!("Imported {} archive entries from {}", added, path);
+    Ok(added)
+}
+
+/// Writes the download archive to `path` in yt-dlp's format
+///
+/// Returns the number of entries written.
+#[tauri::command]
+pub async fn archive_export(path: String) -> Result<usize, String> {
+    let (_, entries) = load_archive()?;
+    write_archive(Path::new(&path), &entries).map_err(|e| e.to_string())?;
+
+    tracing::info!("Exported {} archive entries to {}", entries.len(), path);
+    Ok(entries.len())
+}
+
+/// Removes entries from the download archive so those videos can be
+/// downloaded again
+///
+/// Returns the number of entries removed.
+#[tauri::command]
+pub async fn archive_prune(entries: Vec<ArchiveEntry>) -> Result<usize, String> {
+    let (archive_path, mut archive) = load_archive()?;
+
+    let removed = remove_entries(&mut archive, &entries);
+    if removed > 0 {
+        write_archive(&archive_path, &archive).map_err(|e| e.to_string())?;
+    }
+
+    Ok(removed)
+}
+
+/// Open native file picker for an archive file to import
+#[tauri::command]
+pub async fn pick_archive_import_file(app: tauri::AppHandle) -> Result<Option<String>, String> {
+    let file = app
+        .dialog()
+        .file()
+        .set_title("Import Download Archive")
+        .add_filter("Download Archive", &["txt"])
+        .blocking_pick_file();
+
+    Ok(file.map(|path| path.to_string()))
+}
+
+/// Open native save dialog for exporting the archive
+#[tauri::command]
+pub async fn pick_archive_export_file(app: tauri::AppHandle) -> Result<Option<String>, String> {
+    let file = app
+        .dialog()
+        .file()
+        .set_title("Export Download Archive")
+        .set_file_name("download-archive.txt")
+        .add_filter("Download Archive", &["txt"])
+        .blocking_save_file();
+
+    Ok(file.map(|path| path.to_string()))
+}
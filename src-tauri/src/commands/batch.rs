@@ -0,0 +1,128 @@
+//! Batch URL import commands
+//!
+//! Links are parsed from pasted text or a .txt/.csv file for a preview, then
+//! queued together with one queue event for the whole batch.
+
+use tauri::{AppHandle, State};
+use tauri_plugin_dialog::DialogExt;
+
+use crate::commands::history::{find_history_duplicate, load_history};
+use crate::commands::preferences::get_saved_preferences;
+use crate::download::queue::{QueueItem, SharedDownloadQueue};
+use crate::download::{normalize_url, parse_batch, BatchEntry, BatchParse, Duplicate};
+use crate::models::DownloadConfig;
+
+/// Largest batch file read (text files with links are far smaller)
+const MAX_BATCH_FILE_BYTES: u64 = 5 * 1024 * 1024;
+
+/// Result of queueing a batch
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchAddResult {
+    pub items: Vec<QueueItem>,
+    /// Links that weren't queued because they're duplicates
+    pub duplicates: Vec<Duplicate>,
+}
+
+/// Parses pasted text, or the file at `path`, into links to preview
+///
+/// Files ending in .csv are read as CSV; anything else as plain text. `csv`
+/// marks `text` as the contents of a CSV file (e.g. one dropped on the window).
+#[tauri::command]
+pub async fn batch_parse(
+    text: Option<String>,
+    path: Option<String>,
+    csv: Option<bool>,
+) -> Result<BatchParse, String> {
+    let Some(path) = path else {
+        return Ok(parse_batch(&text.unwrap_or_default(), csv.unwrap_or(false)));
+    };
+
+    let size = std::fs::metadata(&path)
+        .map_err(|e| format!("Failed to read {}: {}", path, e))?
+        .len();
+    if size > MAX_BATCH_FILE_BYTES {
+        return Err(format!("{} is too large for a link list", path));
+    }
+
+    let bytes = std::fs::read(&path).map_err(|e| format!("Failed to read {}: {}", path, e))?;
+    // Spreadsheet exports often start with a byte order mark
+    let bytes = bytes.strip_prefix(b"\xEF\xBB\xBF").unwrap_or(&bytes);
+    let contents = String::from_utf8_lossy(bytes);
+    let csv = path.to_lowercase().ends_with(".csv");
+
+    let batch = parse_batch(&contents, csv);
+    tracing::info!(
+        "Parsed batch file {}: {} links, {} issues",
+        path,
+        batch.entries.len(),
+        batch.issues.len()
+    );
+    Ok(batch)
+}
+
+/// Open native file picker for a link list
+#[tauri::command]
+pub async fn pick_batch_file(app: AppHandle) -> Result<Option<String>, String> {
+    let file = app
+        .dialog()
+        .file()
+        .set_title("Import Links")
+        .add_filter("Link List", &["txt", "csv"])
+        .blocking_pick_file();
+
+    Ok(file.map(|path| path.to_string()))
+}
+
+/// Queues previewed batch entries
+///
+/// `config` holds the settings for every link; each entry's overrides are
+/// applied on top. Links already in the queue or history are skipped
+/// unless `force` is set.
+#[tauri::command]
+pub async fn queue_add_batch(
+    entries: Vec<BatchEntry>,
+    config: DownloadConfig,
+    force: Option<bool>,
+    app: AppHandle,
+    queue: State<'_, SharedDownloadQueue>,
+) -> Result<BatchAddResult, String> {
+    if entries.is_empty() {
+        return Err("No links to import".to_string());
+    }
+
+    let force = force.unwrap_or(false);
+    let history = if force { Vec::new() } else { load_history(&app) };
+    let preferences = get_saved_preferences(&app);
+
+    let mut seen = std::collections::HashSet::new();
+    let mut configs = Vec::with_capacity(entries.len());
+    let mut duplicates = Vec::new();
+    for entry in &entries {
+        if !seen.insert(normalize_url(&entry.url)) {
+            continue;
+        }
+        if !force {
+            let duplicate = match queue.find_duplicate(&entry.url).await {
+                Some(duplicate) => Some(duplicate),
+                None => find_history_duplicate(&history, &entry.url),
+            };
+            if let Some(duplicate) = duplicate {
+                duplicates.push(duplicate);
+                continue;
+            }
+        }
+
+        let mut entry_config = entry.overrides.apply(&config, &entry.url);
+        entry_config.output_folder = preferences.resolve_output_folder(&entry_config);
+        configs.push(entry_config);
+    }
+
+    let items = queue.add_batch(configs).await;
+    tracing::info!(
+        "Queued {} links from batch import ({} duplicates skipped)",
+        items.len(),
+        duplicates.len()
+    );
+    Ok(BatchAddResult { items, duplicates })
+}
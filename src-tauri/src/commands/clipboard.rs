@@ -0,0 +1,171 @@
+//! Clipboard monitoring
+//!
+//! A background task polls the clipboard while monitoring is enabled in the
+//! preferences. Links on allowed sites are reported to the frontend with the
+//! "clipboard-link" event, or queued when the action is `Enqueue`.
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use tauri::{AppHandle, Emitter};
+use tauri_plugin_clipboard_manager::ClipboardExt;
+
+use crate::commands::history::{find_history_duplicate, load_history};
+use crate::commands::preferences::get_saved_preferences;
+use crate::download::queue::{QueueItem, SharedDownloadQueue};
+use crate::download::{extract_urls, normalize_url, Duplicate};
+use crate::models::{ClipboardAction, ClipboardOptions};
+
+/// Event name for links found on the clipboard
+const EVENT_CLIPBOARD_LINK: &str = "clipboard-link";
+
+/// Seconds between clipboard reads
+const POLL_INTERVAL_SECONDS: u64 = 1;
+
+/// The same link copied again within this window is ignored
+const REPEAT_WINDOW: Duration = Duration::from_secs(60);
+
+/// A link found on the clipboard
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClipboardLink {
+    pub url: String,
+    /// Queue item, when the link was queued
+    pub item: Option<QueueItem>,
+    /// Existing download, when queueing was skipped as a duplicate
+    pub duplicate: Option<Duplicate>,
+}
+
+/// Turns clipboard contents into newly copied links
+///
+/// Text is only looked at when it changes, and a link seen within
+/// `REPEAT_WINDOW` is dropped, so copying the same link twice (or apps that
+/// rewrite the clipboard) doesn't trigger it again.
+#[derive(Debug, Default)]
+pub struct ClipboardDebouncer {
+    last_text: Option<String>,
+    recent: HashMap<String, Instant>,
+}
+
+impl ClipboardDebouncer {
+    /// Links in `text` that haven't been seen recently
+    pub fn new_links(&mut self, text: &str, now: Instant) -> Vec<String> {
+        if self.last_text.as_deref() == Some(text) {
+            return Vec::new();
+        }
+        // Whatever was on the clipboard before watching started isn't new
+        let first_read = self.last_text.is_none();
+        self.last_text = Some(text.to_string());
+
+        self.recent.retain(|_, seen| now.duration_since(*seen) < REPEAT_WINDOW);
+        let mut links = Vec::new();
+        for url in extract_urls(text) {
+            if self.recent.insert(normalize_url(&url), now).is_none() && !first_read {
+                links.push(url);
+            }
+        }
+        links
+    }
+
+    /// Forgets the clipboard contents, e.g. when watching is turned off
+    pub fn reset(&mut self) {
+        self.last_text = None;
+        self.recent.clear();
+    }
+}
+
+/// Queues a copied link with the monitoring format and quality
+///
+/// Links already in the queue or history aren't queued again.
+async fn enqueue_link(
+    app: &AppHandle,
+    queue: &SharedDownloadQueue,
+    options: &ClipboardOptions,
+    url: &str,
+) -> ClipboardLink {
+    let duplicate = match queue.find_duplicate(url).await {
+        Some(duplicate) => Some(duplicate),
+        None => find_history_duplicate(&load_history(app), url),
+    };
+    if duplicate.is_some() {
+        return ClipboardLink {
+            url: url.to_string(),
+            item: None,
+            duplicate,
+        };
+    }
+
+    let mut preferences = get_saved_preferences(app);
+    preferences.format = options.format.unwrap_or(preferences.format);
+    preferences.quality = options.quality.unwrap_or(preferences.quality);
+    let item = queue.add(preferences.download_config(url)).await;
+    tracing::info!("Queued {} from the clipboard", url);
+
+    ClipboardLink {
+        url: url.to_string(),
+        item: Some(item),
+        duplicate: None,
+    }
+}
+
+/// Watches the clipboard for media links in the background
+pub async fn start_clipboard_watch(app: AppHandle, queue: SharedDownloadQueue) {
+    let mut debouncer = ClipboardDebouncer::default();
+
+    loop {
+        tokio::time::sleep(Duration::from_secs(POLL_INTERVAL_SECONDS)).await;
+
+        let options = get_saved_preferences(&app).clipboard;
+        if !options.enabled {
+            debouncer.reset();
+            continue;
+        }
+
+        // Fails when the clipboard holds something other than text
+        let Ok(text) = app.clipboard().read_text() else {
+            continue;
+        };
+
+        for url in debouncer.new_links(&text, Instant::now()) {
+            if !options.allows(&url) {
+                continue;
+            }
+
+            let link = match options.action {
+                ClipboardAction::Notify => ClipboardLink {
+                    url,
+                    item: None,
+                    duplicate: None,
+                },
+                ClipboardAction::Enqueue => enqueue_link(&app, &queue, &options, &url).await,
+            };
+            let _ = app.emit(EVENT_CLIPBOARD_LINK, &link);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_debouncer_ignores_repeats() {
+        let mut debouncer = ClipboardDebouncer::default();
+        let start = Instant::now();
+
+        // Contents from before watching started
+        assert!(debouncer.new_links("https://youtu.be/aaaaaaaaaaa", start).is_empty());
+
+        let copied = "see https://youtu.be/bbbbbbbbbbb";
+        assert_eq!(debouncer.new_links(copied, start), vec!["https://youtu.be/bbbbbbbbbbb"]);
+        assert!(debouncer.new_links(copied, start).is_empty());
+
+        // Same link, different text, within the window
+        let later = start + Duration::from_secs(5);
+        assert!(debouncer.new_links("https://youtu.be/bbbbbbbbbbb?si=share", later).is_empty());
+
+        // Copied again after the window
+        let much_later = later + REPEAT_WINDOW + Duration::from_secs(1);
+        assert_eq!(debouncer.new_links(copied, much_later).len(), 1);
+    }
+}
@@ -0,0 +1,108 @@
+//! Deep link handling
+//!
+//! `mediagrab://add?url=...` links (see `DeepLink`) are queued with the
+//! default settings and the main window is brought to the front. Links
+//! opened while the app is running reach it through the single-instance
+//! plugin instead of starting a second copy.
+
+use tauri::{AppHandle, Emitter, Manager, Url};
+
+use crate::commands::preferences::{get_saved_preferences, Preferences};
+use crate::commands::queue::{add_to_queue, QueueAddResult};
+use crate::download::queue::{QueueItem, SharedDownloadQueue};
+use crate::download::Duplicate;
+use crate::models::{DeepLink, DownloadConfig};
+
+/// Event name for handled deep links
+const EVENT_DEEP_LINK: &str = "deep-link";
+
+/// What became of a deep link
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeepLinkResult {
+    /// The deep link as opened
+    pub link: String,
+    /// Media link it carried, when valid
+    pub url: Option<String>,
+    /// Queue item, when the link was queued
+    pub item: Option<QueueItem>,
+    /// Existing download, when queueing was skipped as a duplicate
+    pub duplicate: Option<Duplicate>,
+    /// Why the link couldn't be queued
+    pub error: Option<String>,
+}
+
+/// Download config for a deep link, with the defaults filled in as the
+/// download form would
+pub fn deep_link_config(deep_link: &DeepLink, preferences: &Preferences) -> DownloadConfig {
+    let mut preferences = preferences.clone();
+    preferences.format = deep_link.format(preferences.format);
+    preferences.quality = deep_link.quality.unwrap_or(preferences.quality);
+    preferences.download_config(&deep_link.url)
+}
+
+/// Handles deep links from a cold start or forwarded by another instance
+pub fn open_deep_links(app: &AppHandle, links: Vec<Url>) {
+    for link in links {
+        let app = app.clone();
+        tauri::async_runtime::spawn(async move {
+            handle_deep_link(&app, link.as_str()).await;
+        });
+    }
+}
+
+/// Queues the download a deep link asks for and reports it to the frontend
+async fn handle_deep_link(app: &AppHandle, link: &str) {
+    crate::show_main_window(app);
+
+    let mut result = DeepLinkResult {
+        link: link.to_string(),
+        url: None,
+        item: None,
+        duplicate: None,
+        error: None,
+    };
+    match DeepLink::parse(link) {
+        Ok(deep_link) => {
+            let config = deep_link_config(&deep_link, &get_saved_preferences(app));
+            let queue = app.state::<SharedDownloadQueue>();
+            result.url = Some(deep_link.url);
+            match add_to_queue(app, &queue, config, false, None).await {
+                Ok(QueueAddResult::Added { item }) => {
+                    tracing::info!("Queued {} from a deep link", item.config.url);
+                    result.item = Some(*item);
+                }
+                Ok(QueueAddResult::Duplicate { duplicate }) => result.duplicate = Some(duplicate),
+                Err(e) => result.error = Some(e),
+            }
+        }
+        Err(e) => {
+            tracing::warn!("Ignoring deep link {}: {}", link, e);
+            result.error = Some(e.to_string());
+        }
+    }
+
+    let _ = app.emit(EVENT_DEEP_LINK, &result);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{AudioCodec, OutputFormat, Quality};
+
+    #[test]
+    fn test_deep_link_config() {
+        let preferences = Preferences {
+            audio_output_folder: Some("/music".to_string()),
+            ..Preferences::default()
+        };
+        let deep_link =
+            DeepLink::parse("mediagrab://add?url=https%3A%2F%2Fyoutu.be%2FdQw4w9WgXcQ&preset=audio&quality=720p").unwrap();
+
+        let config = deep_link_config(&deep_link, &preferences);
+        assert_eq!(config.url, "https://youtu.be/dQw4w9WgXcQ");
+        assert_eq!(config.format, OutputFormat::Audio(AudioCodec::Mp3));
+        assert_eq!(config.quality, Quality::P720);
+        assert_eq!(config.output_folder, "/music");
+    }
+}
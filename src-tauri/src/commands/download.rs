@@ -9,9 +9,11 @@ use tauri_plugin_notification::NotificationExt;
 use tokio::sync::mpsc;
 
 use crate::download::{
-    spawn_ytdlp, stream_process_output, ProcessOutput,
+    find_subtitle_files, run_loudnorm, run_transcode, spawn_ytdlp, stream_process_output, ProcessOutput,
     SharedDownloadManager, SpawnConfig,
 };
+use crate::commands::folder::prepare_output_folder;
+use crate::commands::preferences::get_saved_preferences;
 use crate::models::{DownloadConfig, DownloadError, DownloadResult, DownloadState, ProgressEvent, RetryConfig};
 use crate::utils::paths;
 
@@ -46,12 +48,16 @@ const EVENT_RETRY: &str = "download-retry";
 /// **Validates: Requirements 1.1, 1.5, 4.1, 4.2**
 #[tauri::command]
 pub async fn start_download(
-    config: DownloadConfig,
+    mut config: DownloadConfig,
     app: AppHandle,
     state: State<'_, SharedDownloadManager>,
 ) -> Result<DownloadResult, String> {
     let manager = state.inner().clone();
     
+    // Audio and video can have separate default folders
+    let preferences = get_saved_preferences(&app);
+    config.output_folder = preferences.resolve_output_folder(&config);
+    
     // Try to start the download
     manager
         .start_download(config.clone())
@@ -73,10 +79,24 @@ pub async fn start_download(
         }
     };
     
+    // Create organization subfolders and validate where the file will land
+    let output_folder = match prepare_output_folder(&config) {
+        Ok(folder) => folder,
+        Err(e) => {
+            let result = manager.fail(e.clone()).await;
+            emit_state_change(&app, DownloadState::Failed, None);
+            emit_error(&app, &e.to_string());
+            return Ok(result);
+        }
+    };
+    
     // Spawn the yt-dlp process with resolved paths
     let spawn_config = SpawnConfig {
         ytdlp_path: exec_paths.ytdlp.to_string_lossy().to_string(),
         ffmpeg_location: Some(exec_paths.ffmpeg_dir.to_string_lossy().to_string()),
+        // An explicit single download always runs; the archive only skips
+        // queued items
+        download_archive: None,
     };
     
     // Use retry configuration
@@ -105,7 +125,10 @@ pub async fn start_download(
     let (tx, mut rx) = mpsc::channel::<ProcessOutput>(100);
     
     // Spawn task to stream process output
-    let output_folder = config.output_folder.clone();
+    let write_subtitles = config.subtitles.download_subtitles;
+    let transcode_preset = config.transcode_preset;
+    let loudness_target = config.loudness_target_lufs.filter(|_| config.format.is_audio());
+    let ffmpeg_path = exec_paths.ffmpeg.to_string_lossy().to_string();
     
     tokio::spawn(async move {
         // Stream process output
@@ -119,6 +142,8 @@ pub async fn start_download(
     tokio::spawn(async move {
         // Track the detected file path from --print after_move:filepath
         let mut detected_file_path: Option<String> = None;
+        // Files written by --split-chapters
+        let mut chapter_files: Vec<String> = Vec::new();
         let mut progress_count = 0u32;
         
         tracing::info!("Starting to receive process output events...");
@@ -129,6 +154,14 @@ pub async fn start_download(
                     progress_count += 1;
                     tracing::info!("Progress event #{}: {}% speed={}", progress_count, event.percentage, event.speed);
                     
+                    // Livestream output has no percentage; switch to recording once
+                    if event.status == "recording"
+                        && manager_for_events.get_state().await != DownloadState::Recording
+                        && manager_for_events.start_recording().await.is_ok()
+                    {
+                        emit_state_change(&app_for_events, DownloadState::Recording, None);
+                    }
+                    
                     // Update manager progress
                     manager_for_events.update_progress(event.clone()).await;
                     
@@ -152,15 +185,44 @@ pub async fn start_download(
                             speed: String::new(),
                             eta_seconds: None,
                             status: "merging".to_string(),
+                            elapsed_seconds: None,
                         };
                         emit_progress(&app_for_events, &merging_event);
                     }
                 }
+                ProcessOutput::WaitingForLive => {
+                    // yt-dlp prints the countdown repeatedly; only emit the first one
+                    if manager_for_events.get_state().await != DownloadState::WaitingForLive
+                        && manager_for_events.wait_for_live().await.is_ok()
+                    {
+                        emit_state_change(&app_for_events, DownloadState::WaitingForLive, None);
+                    }
+                }
                 ProcessOutput::FilePath(path) => {
                     // Store the file path from --print after_move:filepath
                     tracing::info!("Detected output file path: {}", path);
                     detected_file_path = Some(path);
                 }
+                ProcessOutput::ChapterFile(path) => {
+                    tracing::info!("Detected chapter file: {}", path);
+                    chapter_files.push(path);
+                }
+                ProcessOutput::AlreadyArchived(title) => {
+                    // Nothing was downloaded; the reason goes out on the error
+                    // channel so the form can tell the user why
+                    let reason = DownloadError::AlreadyArchived(title);
+                    manager_for_events.skip(reason.clone()).await;
+                    emit_state_change(&app_for_events, DownloadState::Skipped, None);
+                    emit_error(&app_for_events, &reason.to_string());
+                    break;
+                }
+                ProcessOutput::FilteredOut(reason) => {
+                    let reason = DownloadError::FilteredOut(reason);
+                    manager_for_events.skip(reason.clone()).await;
+                    emit_state_change(&app_for_events, DownloadState::Skipped, None);
+                    emit_error(&app_for_events, &reason.to_string());
+                    break;
+                }
                 ProcessOutput::Error(error) => {
                     // Note: yt-dlp level retries are handled by --retries and --fragment-retries flags
                     // This error means all internal retries failed
@@ -173,7 +235,7 @@ pub async fn start_download(
                 }
                 ProcessOutput::Completed(_) => {
                     // Use the detected file path from --print, fallback to find_latest_file
-                    let file_path = detected_file_path.clone()
+                    let mut file_path = detected_file_path.clone()
                         .or_else(|| {
                             // Fallback: try to find the latest file in output folder
                             // This is a sync operation but should be fast
@@ -182,7 +244,66 @@ pub async fn start_download(
                             })
                         });
                     
-                    if let Ok(result) = manager_for_events.complete(file_path.clone().unwrap_or_default()).await {
+                    // Sidecar subtitles are written next to the main file
+                    let subtitle_files = match (&file_path, write_subtitles) {
+                        (Some(path), true) => find_subtitle_files(path),
+                        _ => Vec::new(),
+                    };
+                    
+                    // Loudness normalization (audio only) runs before any re-encode.
+                    // A failure keeps the download as it is.
+                    let mut loudness = None;
+                    if let (Some(target), Some(input)) = (loudness_target, file_path.as_deref()) {
+                        match run_loudnorm(&ffmpeg_path, input, target).await {
+                            Ok(measurement) => loudness = Some(measurement),
+                            Err(e) => tracing::warn!("Skipping loudness normalization: {}", e),
+                        }
+                    }
+                    
+                    // Optional re-encode; the transcoded file becomes the final file
+                    if let (Some(preset), Some(input)) = (transcode_preset, file_path.clone()) {
+                        let (progress_tx, mut progress_rx) = mpsc::channel::<ProgressEvent>(100);
+                        let ffmpeg = ffmpeg_path.clone();
+                        let transcode = tokio::spawn(async move {
+                            run_transcode(&ffmpeg, &input, preset, progress_tx).await
+                        });
+                        
+                        while let Some(event) = progress_rx.recv().await {
+                            if matches!(
+                                manager_for_events.get_state().await,
+                                DownloadState::Cancelling | DownloadState::Cancelled
+                            ) {
+                                // Dropping the task kills ffmpeg
+                                transcode.abort();
+                                break;
+                            }
+                            manager_for_events.update_progress(event.clone()).await;
+                            emit_progress(&app_for_events, &event);
+                        }
+                        
+                        match transcode.await {
+                            Ok(Ok(path)) => file_path = Some(path),
+                            Ok(Err(error)) => {
+                                let _ = manager_for_events.fail(error.clone()).await;
+                                emit_state_change(&app_for_events, DownloadState::Failed, None);
+                                emit_error(&app_for_events, &error.to_string());
+                                send_completion_notification(&app_for_events, "Download", false);
+                                continue;
+                            }
+                            // Aborted after cancellation
+                            Err(_) => continue,
+                        }
+                    }
+                    
+                    if let Ok(mut result) = manager_for_events
+                        .complete_with_files(
+                            file_path.clone().unwrap_or_default(),
+                            std::mem::take(&mut chapter_files),
+                            subtitle_files,
+                        )
+                        .await
+                    {
+                        result.loudness = loudness;
                         emit_state_change(&app_for_events, DownloadState::Completed, file_path.clone());
                         emit_complete(&app_for_events, &result);
                         
@@ -220,6 +341,9 @@ pub async fn start_download(
     Ok(DownloadResult {
         success: true,
         file_path: None,
+        file_paths: Vec::new(),
+        subtitle_files: Vec::new(),
+        loudness: None,
         error: None,
     })
 }
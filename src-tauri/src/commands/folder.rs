@@ -6,7 +6,8 @@ use std::fs;
 use std::path::Path;
 use tauri_plugin_dialog::DialogExt;
 
-use crate::models::FilesystemError;
+use crate::download::ArgumentBuilder;
+use crate::models::{DownloadConfig, DownloadError, FilesystemError};
 use crate::utils::create_hidden_command;
 
 /// Open native folder picker dialog and return selected path
@@ -165,6 +166,36 @@ pub fn validate_folder_for_download(
     })
 }
 
+/// Creates the organized output folder for a download and validates it
+///
+/// Returns the deepest folder known before the download starts (see
+/// `ArgumentBuilder::organized_base_folder`). Without organization rules this
+/// is the output root itself, which must already exist. Accessibility and
+/// disk space are checked against that folder, i.e. on the chosen root.
+pub fn prepare_output_folder(config: &DownloadConfig) -> Result<String, DownloadError> {
+    let folder = ArgumentBuilder::new(config.clone()).organized_base_folder();
+    let path = folder.to_string_lossy().to_string();
+
+    // Never create the output root itself, only subfolders of an existing one
+    if !Path::new(&config.output_folder).is_dir() {
+        return Err(DownloadError::FolderNotAccessible(config.output_folder.clone()));
+    }
+    if !config.organize_rules.is_empty() {
+        fs::create_dir_all(&folder)
+            .map_err(|e| DownloadError::FolderNotAccessible(format!("{}: {}", path, e)))?;
+    }
+
+    let validation = validate_folder_for_download(path.clone(), None)
+        .map_err(DownloadError::FolderNotAccessible)?;
+    if !validation.is_valid {
+        return Err(DownloadError::FolderNotAccessible(
+            validation.warning.unwrap_or(path),
+        ));
+    }
+
+    Ok(path)
+}
+
 /// Result of folder validation
 #[derive(serde::Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -5,6 +5,9 @@
 use serde::{Deserialize, Serialize};
 use tauri_plugin_store::StoreExt;
 
+use crate::download::{Duplicate, DuplicateSource, MediaKey};
+use crate::models::LoudnessMeasurement;
+
 const STORE_PATH: &str = "history.json";
 const HISTORY_KEY: &str = "downloads";
 const STATS_KEY: &str = "stats";
@@ -26,6 +29,9 @@ pub struct HistoryItem {
     pub downloaded_at: i64, // Unix timestamp
     pub status: String,     // "completed" | "failed"
     pub error: Option<String>,
+    /// Loudness measured before normalization (normalized audio downloads only)
+    #[serde(default)]
+    pub loudness: Option<LoudnessMeasurement>,
 }
 
 /// Download statistics
@@ -148,6 +154,40 @@ pub async fn history_remove(id: String, app: tauri::AppHandle) -> Result<(), Str
     Ok(())
 }
 
+/// Reads the saved history for backend use (empty if missing or unreadable)
+pub fn load_history(app: &tauri::AppHandle) -> Vec<HistoryItem> {
+    app.store(STORE_PATH)
+        .ok()
+        .and_then(|store| store.get(HISTORY_KEY))
+        .and_then(|value| serde_json::from_value(value).ok())
+        .unwrap_or_default()
+}
+
+/// Finds the most recent completed download of the same media as `url`
+///
+/// `file_path` is only reported if the file still exists.
+pub fn find_history_duplicate(history: &[HistoryItem], url: &str) -> Option<Duplicate> {
+    let key = MediaKey::from_url(url);
+    history
+        .iter()
+        .filter(|item| item.status == "completed")
+        .find_map(|item| {
+            key.match_url(&item.url).map(|matched_by| Duplicate {
+                source: DuplicateSource::History,
+                matched_by,
+                url: item.url.clone(),
+                title: Some(item.title.clone()),
+                queue_item_id: None,
+                history_id: Some(item.id.clone()),
+                file_path: item
+                    .file_path
+                    .clone()
+                    .filter(|path| std::path::Path::new(path).exists()),
+                downloaded_at: Some(item.downloaded_at),
+            })
+        })
+}
+
 /// Clear all history
 #[tauri::command]
 pub async fn history_clear(app: tauri::AppHandle) -> Result<(), String> {
@@ -183,6 +223,7 @@ mod tests {
             downloaded_at: 1703001234,
             status: "completed".to_string(),
             error: None,
+            loudness: None,
         };
 
         let json = serde_json::to_string(&item).unwrap();
@@ -192,6 +233,67 @@ mod tests {
         assert_eq!(item.title, parsed.title);
     }
 
+    #[test]
+    fn test_history_item_with_loudness() {
+        // Entries saved before loudness normalization existed have no field
+        let json = r#"{"id":"1","url":"u","title":"t","thumbnail":null,"format":"audio-mp3",
+            "quality":"best","filePath":null,"fileSize":null,"duration":null,
+            "downloadedAt":0,"status":"completed","error":null}"#;
+        let parsed: HistoryItem = serde_json::from_str(json).unwrap();
+        assert!(parsed.loudness.is_none());
+
+        let with_loudness = HistoryItem {
+            loudness: Some(LoudnessMeasurement {
+                input_i: -27.61,
+                input_tp: -4.47,
+                input_lra: 18.06,
+                input_thresh: -39.2,
+                target_offset: 0.58,
+                target_i: -16.0,
+                output_i: Some(-16.1),
+            }),
+            ..parsed
+        };
+        let json = serde_json::to_string(&with_loudness).unwrap();
+        assert!(json.contains(r#""inputI":-27.61"#));
+        let parsed: HistoryItem = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.loudness, with_loudness.loudness);
+    }
+
+    #[test]
+    fn test_find_history_duplicate() {
+        let item = |id: &str, url: &str, status: &str| HistoryItem {
+            id: id.to_string(),
+            url: url.to_string(),
+            title: format!("Video {}", id),
+            thumbnail: None,
+            format: "video-mp4".to_string(),
+            quality: "best".to_string(),
+            file_path: Some("/nonexistent/video.mp4".to_string()),
+            file_size: None,
+            duration: None,
+            downloaded_at: 1703001234,
+            status: status.to_string(),
+            error: None,
+            loudness: None,
+        };
+        // Newest first, like the store
+        let history = vec![
+            item("3", "https://youtu.be/dQw4w9WgXcQ", "failed"),
+            item("2", "https://www.youtube.com/watch?v=dQw4w9WgXcQ", "completed"),
+            item("1", "https://vimeo.com/76979871", "completed"),
+        ];
+
+        let duplicate = find_history_duplicate(&history, "https://youtu.be/dQw4w9WgXcQ?t=5").unwrap();
+        assert_eq!(duplicate.history_id.as_deref(), Some("2"));
+        assert_eq!(duplicate.source, DuplicateSource::History);
+        assert_eq!(duplicate.matched_by, crate::download::DuplicateMatch::VideoId);
+        // The file is gone, so there's nothing to open
+        assert!(duplicate.file_path.is_none());
+
+        assert!(find_history_duplicate(&history, "https://vimeo.com/1").is_none());
+    }
+
     #[test]
     fn test_stats_default() {
         let stats = DownloadStats::default();
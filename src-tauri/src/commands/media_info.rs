@@ -29,6 +29,10 @@ pub async fn fetch_media_info(url: String, app: tauri::AppHandle) -> Result<Medi
     // Build command arguments
     let mut args = vec!["--simulate", "-J", "--no-playlist"];
     
+    // Scheduled livestreams have no formats yet; still return their metadata
+    // (live_status "is_upcoming") so the stream can be waited for
+    args.push("--ignore-no-formats-error");
+    
     // Add cookies arguments if available
     let cookies_file_arg: String;
     let cookies_browser_arg: String;
@@ -129,6 +133,12 @@ fn parse_media_info_json(json_str: &str) -> Result<MediaInfo, String> {
         duration,
         uploader,
         filesize_approx,
+        id: json["id"].as_str().map(|s| s.to_string()),
+        channel: json["channel"].as_str().map(|s| s.to_string()),
+        upload_date: json["upload_date"].as_str().map(|s| s.to_string()),
+        width: json["width"].as_u64().map(|w| w as u32),
+        height: json["height"].as_u64().map(|h| h as u32),
+        live_status: json["live_status"].as_str().map(|s| s.to_string()),
     })
 }
 
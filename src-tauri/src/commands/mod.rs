@@ -2,7 +2,12 @@
 //!
 //! This module exports all Tauri commands for frontend invocation.
 
+pub mod api;
+pub mod archive;
+pub mod batch;
+pub mod clipboard;
 pub mod debug;
+pub mod deep_link;
 pub mod download;
 pub mod executables;
 pub mod folder;
@@ -11,10 +16,17 @@ pub mod media_info;
 pub mod playlist;
 pub mod preferences;
 pub mod queue;
+pub mod subscriptions;
 pub mod subtitles;
+pub mod template;
 pub mod update;
 
+pub use api::*;
+pub use archive::*;
+pub use batch::*;
+pub use clipboard::*;
 pub use debug::*;
+pub use deep_link::*;
 pub use download::*;
 pub use executables::*;
 pub use folder::*;
@@ -23,5 +35,7 @@ pub use media_info::*;
 pub use playlist::*;
 pub use preferences::*;
 pub use queue::*;
+pub use subscriptions::*;
 pub use subtitles::*;
+pub use template::*;
 pub use update::*;
@@ -1,13 +1,73 @@
 //! Playlist handling commands
 //!
-//! Implements playlist detection and video listing using yt-dlp.
+//! Implements playlist detection and video listing using yt-dlp. Listings
+//! are streamed one entry per line, with progress events while a long
+//! playlist or channel is being enumerated, and can be cancelled.
 
-use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::process::Stdio;
+use std::sync::{Arc, Mutex};
+
+use tauri::{Emitter, Manager};
 use tauri_plugin_store::StoreExt;
+use tokio::io::{AsyncBufReadExt, BufReader};
+use tokio::sync::Notify;
 
+use crate::download::{playlist_args, PlaylistCollector};
+pub use crate::models::{PlaylistEntry, PlaylistFilter, PlaylistInfo};
 use crate::utils::create_hidden_async_command;
 
+/// Event name for playlist enumeration progress
+const EVENT_PLAYLIST_PROGRESS: &str = "playlist-progress";
+
+/// Entries between progress events
+const PROGRESS_EVERY: usize = 25;
+
+/// Progress of a playlist being listed
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlaylistProgress {
+    pub url: String,
+    /// Entries listed so far
+    pub loaded: usize,
+    /// Playlist size reported by the site, if known
+    pub total: Option<u32>,
+}
+
+/// Running playlist listings, by URL, so they can be cancelled
+#[derive(Default)]
+pub struct PlaylistFetches(Mutex<HashMap<String, Arc<Notify>>>);
+
+impl PlaylistFetches {
+    fn register(&self, url: &str) -> Arc<Notify> {
+        let cancel = Arc::new(Notify::new());
+        if let Ok(mut fetches) = self.0.lock() {
+            fetches.insert(url.to_string(), cancel.clone());
+        }
+        cancel
+    }
+
+    fn unregister(&self, url: &str, cancel: &Arc<Notify>) {
+        if let Ok(mut fetches) = self.0.lock() {
+            // A newer listing of the same URL may have replaced this one
+            if fetches.get(url).is_some_and(|current| Arc::ptr_eq(current, cancel)) {
+                fetches.remove(url);
+            }
+        }
+    }
+
+    fn cancel(&self, url: &str) -> bool {
+        match self.0.lock().ok().and_then(|mut fetches| fetches.remove(url)) {
+            Some(cancel) => {
+                // notify_one stores a permit if the listing isn't waiting yet
+                cancel.notify_one();
+                true
+            }
+            None => false,
+        }
+    }
+}
+
 /// Gets cookies settings from preferences store
 fn get_cookies_from_preferences(app: &tauri::AppHandle) -> (Option<String>, Option<String>) {
     let store = match app.store("preferences.json") {
@@ -35,46 +95,6 @@ fn get_cookies_from_preferences(app: &tauri::AppHandle) -> (Option<String>, Opti
     (cookies_file_path, cookies_from_browser)
 }
 
-/// A single video entry in a playlist
-#[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct PlaylistEntry {
-    /// Video ID
-    pub id: String,
-    /// Video title
-    pub title: String,
-    /// Video URL
-    pub url: String,
-    /// Thumbnail URL
-    pub thumbnail: Option<String>,
-    /// Duration in seconds
-    pub duration: Option<f64>,
-    /// Position in playlist (1-indexed)
-    pub playlist_index: u32,
-    /// Uploader/channel name
-    pub uploader: Option<String>,
-}
-
-/// Playlist information
-#[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct PlaylistInfo {
-    /// Playlist ID
-    pub id: String,
-    /// Playlist title
-    pub title: String,
-    /// Playlist URL
-    pub url: String,
-    /// Playlist thumbnail
-    pub thumbnail: Option<String>,
-    /// Channel/uploader name
-    pub uploader: Option<String>,
-    /// Total number of videos
-    pub video_count: u32,
-    /// List of videos in the playlist
-    pub entries: Vec<PlaylistEntry>,
-}
-
 /// Check if a URL is a playlist
 #[tauri::command]
 pub async fn check_is_playlist(url: String, app: tauri::AppHandle) -> Result<bool, String> {
@@ -99,7 +119,8 @@ pub async fn check_is_playlist(url: String, app: tauri::AppHandle) -> Result<boo
     let (cookies_file_path, cookies_from_browser) = get_cookies_from_preferences(&app);
 
     // Build command arguments
-    let mut args = vec!["--flat-playlist", "-J", "--no-download"];
+    // Only the first entry is listed; the header is enough to tell
+    let mut args = vec!["--flat-playlist", "-J", "--no-download", "--playlist-items", "1"];
     
     let cookies_file_arg: String;
     let cookies_browser_arg: String;
@@ -138,189 +159,149 @@ pub async fn check_is_playlist(url: String, app: tauri::AppHandle) -> Result<boo
 }
 
 /// Fetch playlist information and video list
+///
+/// `start` (1-based) and `limit` list one page of a long playlist or channel
+/// instead of all of it; `filter` drops entries by date, duration or title.
+/// Emits `playlist-progress` while entries come in; `cancel_playlist_fetch`
+/// stops the listing.
+#[tauri::command]
+pub async fn fetch_playlist_info(
+    url: String,
+    start: Option<u32>,
+    limit: Option<u32>,
+    filter: Option<PlaylistFilter>,
+    app: tauri::AppHandle,
+) -> Result<PlaylistInfo, String> {
+    let filter = filter.unwrap_or_default();
+    filter.validate().map_err(|e| e.to_string())?;
+
+    let fetches = app.state::<PlaylistFetches>();
+    let cancel = fetches.register(&url);
+    let result = enumerate_playlist(&app, &url, start, limit, &filter, Some(cancel.clone())).await;
+    fetches.unregister(&url, &cancel);
+    result
+}
+
+/// Stops a running `fetch_playlist_info` for `url`
 #[tauri::command]
-pub async fn fetch_playlist_info(url: String, app: tauri::AppHandle) -> Result<PlaylistInfo, String> {
+pub async fn cancel_playlist_fetch(url: String, app: tauri::AppHandle) -> Result<bool, String> {
+    Ok(app.state::<PlaylistFetches>().cancel(&url))
+}
+
+/// Lists a playlist with `yt-dlp --flat-playlist -j`, one entry per line
+pub async fn enumerate_playlist(
+    app: &tauri::AppHandle,
+    url: &str,
+    start: Option<u32>,
+    limit: Option<u32>,
+    filter: &PlaylistFilter,
+    cancel: Option<Arc<Notify>>,
+) -> Result<PlaylistInfo, String> {
     if url.trim().is_empty() {
         return Err("URL cannot be empty".to_string());
     }
 
-    // Get cookies settings from preferences
-    let (cookies_file_path, cookies_from_browser) = get_cookies_from_preferences(&app);
-
-    // Build command arguments
-    let mut args = vec!["--flat-playlist", "-J", "--no-download"];
-    
-    let cookies_file_arg: String;
-    let cookies_browser_arg: String;
-    
-    if let Some(ref file_path) = cookies_file_path {
-        cookies_file_arg = file_path.clone();
-        args.push("--cookies");
-        args.push(&cookies_file_arg);
-    } else if let Some(ref browser) = cookies_from_browser {
-        cookies_browser_arg = browser.clone();
-        args.push("--cookies-from-browser");
-        args.push(&cookies_browser_arg);
+    let mut args = Vec::new();
+    let (cookies_file_path, cookies_from_browser) = get_cookies_from_preferences(app);
+    if let Some(file_path) = cookies_file_path {
+        args.push("--cookies".to_string());
+        args.push(file_path);
+    } else if let Some(browser) = cookies_from_browser {
+        args.push("--cookies-from-browser".to_string());
+        args.push(browser);
     }
-    
-    args.push(&url);
+    args.extend(playlist_args(url, start, limit));
 
-    // Use --flat-playlist to get playlist info without downloading video details
-    let output = create_hidden_async_command("yt-dlp")
+    let mut child = create_hidden_async_command("yt-dlp")
         .args(&args)
         .stdout(Stdio::piped())
         .stderr(Stdio::piped())
-        .output()
-        .await
+        .kill_on_drop(true) // Cancelling drops the child and ends the listing
+        .spawn()
         .map_err(|e| format!("Failed to fetch playlist: {}", e))?;
 
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        return Err(format!("Failed to fetch playlist: {}", stderr.lines().next().unwrap_or("Unknown error")));
-    }
-
-    let stdout = String::from_utf8_lossy(&output.stdout);
-    parse_playlist_json(&stdout)
-}
-
-/// Parse playlist JSON from yt-dlp
-fn parse_playlist_json(json_str: &str) -> Result<PlaylistInfo, String> {
-    let json: serde_json::Value = serde_json::from_str(json_str)
-        .map_err(|e| format!("Failed to parse playlist info: {}", e))?;
-
-    // Check if this is actually a playlist
-    let playlist_type = json["_type"].as_str().unwrap_or("");
-    if playlist_type != "playlist" {
-        return Err("URL is not a playlist".to_string());
-    }
-
-    let id = json["id"].as_str().unwrap_or("").to_string();
-    let title = json["title"].as_str().unwrap_or("Unknown Playlist").to_string();
-    let url = json["webpage_url"].as_str()
-        .or_else(|| json["url"].as_str())
-        .unwrap_or("")
-        .to_string();
-    let thumbnail = json["thumbnail"].as_str().map(|s| s.to_string());
-    let uploader = json["uploader"].as_str()
-        .or_else(|| json["channel"].as_str())
-        .map(|s| s.to_string());
-
-    // Parse entries
-    let entries: Vec<PlaylistEntry> = json["entries"]
-        .as_array()
-        .map(|arr| {
-            arr.iter()
-                .enumerate()
-                .filter_map(|(idx, entry)| {
-                    // Skip unavailable videos
-                    if entry["title"].as_str() == Some("[Deleted video]") ||
-                       entry["title"].as_str() == Some("[Private video]") {
-                        return None;
+    let stdout = child.stdout.take().ok_or("Failed to capture yt-dlp output")?;
+    let stderr = child.stderr.take().ok_or("Failed to capture yt-dlp errors")?;
+
+    // Drain stderr alongside stdout so a chatty extractor can't block the
+    // listing; keep the first error for the message
+    let stderr_task = tokio::spawn(async move {
+        let mut lines = BufReader::new(stderr).lines();
+        let mut first_error: Option<String> = None;
+        let mut first_line: Option<String> = None;
+        while let Ok(Some(line)) = lines.next_line().await {
+            if first_error.is_none() && line.starts_with("ERROR") {
+                first_error = Some(line.clone());
+            }
+            if first_line.is_none() && !line.trim().is_empty() {
+                first_line = Some(line);
+            }
+        }
+        first_error.or(first_line)
+    });
+
+    let mut collector = PlaylistCollector::new(url, start).with_filter(filter.clone());
+    let mut lines = BufReader::new(stdout).lines();
+    let cancelled = async {
+        match &cancel {
+            Some(cancel) => cancel.notified().await,
+            None => std::future::pending::<()>().await,
+        }
+    };
+    tokio::pin!(cancelled);
+
+    loop {
+        tokio::select! {
+            _ = &mut cancelled => {
+                tracing::info!("Cancelled playlist listing for {} after {} entries", url, collector.loaded());
+                return Err("Playlist fetch cancelled".to_string());
+            }
+            line = lines.next_line() => match line {
+                Ok(Some(line)) => {
+                    if collector.push_line(&line) && collector.loaded().is_multiple_of(PROGRESS_EVERY) {
+                        emit_progress(app, url, &collector);
                     }
-
-                    let video_id = entry["id"].as_str()?.to_string();
-                    let video_title = entry["title"].as_str().unwrap_or("Unknown").to_string();
-                    
-                    // Build video URL
-                    let video_url = entry["url"].as_str()
-                        .map(|s| s.to_string())
-                        .unwrap_or_else(|| format!("https://www.youtube.com/watch?v={}", video_id));
-
-                    Some(PlaylistEntry {
-                        id: video_id,
-                        title: video_title,
-                        url: video_url,
-                        thumbnail: entry["thumbnail"].as_str().map(|s| s.to_string()),
-                        duration: entry["duration"].as_f64(),
-                        playlist_index: (idx + 1) as u32,
-                        uploader: entry["uploader"].as_str()
-                            .or_else(|| entry["channel"].as_str())
-                            .map(|s| s.to_string()),
-                    })
-                })
-                .collect()
-        })
-        .unwrap_or_default();
-
-    let video_count = entries.len() as u32;
-
-    Ok(PlaylistInfo {
-        id,
-        title,
-        url,
-        thumbnail,
-        uploader,
-        video_count,
-        entries,
-    })
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_parse_playlist_json() {
-        let json = r#"{
-            "_type": "playlist",
-            "id": "PLtest123",
-            "title": "Test Playlist",
-            "webpage_url": "https://www.youtube.com/playlist?list=PLtest123",
-            "uploader": "Test Channel",
-            "entries": [
-                {
-                    "id": "video1",
-                    "title": "First Video",
-                    "url": "https://www.youtube.com/watch?v=video1",
-                    "duration": 120.0
-                },
-                {
-                    "id": "video2",
-                    "title": "Second Video",
-                    "url": "https://www.youtube.com/watch?v=video2",
-                    "duration": 180.0
                 }
-            ]
-        }"#;
-
-        let info = parse_playlist_json(json).unwrap();
-        assert_eq!(info.id, "PLtest123");
-        assert_eq!(info.title, "Test Playlist");
-        assert_eq!(info.video_count, 2);
-        assert_eq!(info.entries.len(), 2);
-        assert_eq!(info.entries[0].title, "First Video");
-        assert_eq!(info.entries[1].playlist_index, 2);
+                Ok(None) => break,
+                Err(e) => {
+                    tracing::warn!("Failed to read playlist output: {}", e);
+                    break;
+                }
+            }
+        }
     }
 
-    #[test]
-    fn test_parse_playlist_skips_deleted() {
-        let json = r#"{
-            "_type": "playlist",
-            "id": "PLtest",
-            "title": "Test",
-            "entries": [
-                {"id": "v1", "title": "Good Video"},
-                {"id": "v2", "title": "[Deleted video]"},
-                {"id": "v3", "title": "[Private video]"},
-                {"id": "v4", "title": "Another Good Video"}
-            ]
-        }"#;
-
-        let info = parse_playlist_json(json).unwrap();
-        assert_eq!(info.video_count, 2);
-        assert_eq!(info.entries[0].title, "Good Video");
-        assert_eq!(info.entries[1].title, "Another Good Video");
+    let status = child.wait().await.map_err(|e| format!("Failed to fetch playlist: {}", e))?;
+    let stderr_line = stderr_task.await.ok().flatten();
+    emit_progress(app, url, &collector);
+
+    if collector.skipped() > 0 || collector.filtered() > 0 {
+        tracing::info!(
+            "Skipped {} unavailable and {} filtered playlist entries for {}",
+            collector.skipped(),
+            collector.filtered(),
+            url
+        );
     }
 
-    #[test]
-    fn test_not_a_playlist() {
-        let json = r#"{
-            "_type": "video",
-            "id": "abc123",
-            "title": "Single Video"
-        }"#;
-
-        let result = parse_playlist_json(json);
-        assert!(result.is_err());
+    // yt-dlp exits non-zero when some entries fail; keep what was listed
+    if !status.success() && collector.loaded() == 0 {
+        return Err(format!(
+            "Failed to fetch playlist: {}",
+            stderr_line.as_deref().unwrap_or("Unknown error")
+        ));
     }
+
+    collector.finish().map_err(|e| e.to_string())
+}
+
+fn emit_progress(app: &tauri::AppHandle, url: &str, collector: &PlaylistCollector) {
+    let _ = app.emit(
+        EVENT_PLAYLIST_PROGRESS,
+        PlaylistProgress {
+            url: url.to_string(),
+            loaded: collector.loaded(),
+            total: collector.total(),
+        },
+    );
 }
@@ -3,9 +3,19 @@
 //! Implements persistence of user preferences using tauri-plugin-store.
 //! Requirements: 9.1, 9.2, 9.3, 9.4
 
-use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Deserializer, Serialize};
 use tauri_plugin_store::StoreExt;
 
+use crate::download::ArgumentBuilder;
+use crate::models::{
+    ApiOptions, ClipboardOptions, DownloadConfig, LiveOptions, OrganizeRule, OutputFormat, Quality, TranscodePreset,
+    VideoContainer, DEFAULT_TARGET_LUFS,
+};
+use crate::utils::paths;
+
 /// User preferences - persisted settings
 /// Matches TypeScript Preferences interface
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -13,10 +23,12 @@ use tauri_plugin_store::StoreExt;
 pub struct Preferences {
     /// Output folder path for downloads
     pub output_folder: String,
-    /// Output format (video-mp4, audio-mp3, audio-best)
-    pub format: String,
-    /// Quality setting (best, 1080p, 720p)
-    pub quality: String,
+    /// Output format (serialized as "video-mp4", "audio-mp3", ...)
+    #[serde(default, deserialize_with = "lenient")]
+    pub format: OutputFormat,
+    /// Quality setting (serialized as "best", "1080p", "720p")
+    #[serde(default, deserialize_with = "lenient")]
+    pub quality: Quality,
     /// Whether to embed subtitles
     pub embed_subtitles: bool,
     /// Browser to import cookies from (chrome, firefox, edge, etc.)
@@ -39,18 +51,72 @@ pub struct Preferences {
     /// Path to custom cookies.txt file (Netscape format)
     #[serde(default)]
     pub cookies_file_path: Option<String>,
+    /// Subfolders downloads are sorted into, outermost first
+    #[serde(default, deserialize_with = "lenient")]
+    pub organize_rules: Vec<OrganizeRule>,
+    /// Default folder for audio downloads (None = `output_folder`)
+    #[serde(default)]
+    pub audio_output_folder: Option<String>,
+    /// Default folder for video downloads (None = `output_folder`)
+    #[serde(default)]
+    pub video_output_folder: Option<String>,
+    /// Per-format folders, taking precedence over the audio/video folders
+    #[serde(default, deserialize_with = "lenient")]
+    pub format_output_folders: HashMap<OutputFormat, String>,
+    /// Preset to re-encode finished downloads with (None = keep as downloaded)
+    #[serde(default, deserialize_with = "lenient")]
+    pub transcode_preset: Option<TranscodePreset>,
+    /// Whether audio downloads are normalized to `loudness_target_lufs`
+    #[serde(default)]
+    pub normalize_loudness: bool,
+    /// Integrated loudness target for normalization (LUFS, EBU R128)
+    #[serde(default = "default_target_lufs")]
+    pub loudness_target_lufs: f64,
+    /// Whether queued downloads are recorded in the download archive and
+    /// skipped when they come up again (opt-in)
+    #[serde(default)]
+    pub use_download_archive: bool,
+    /// Recording settings applied to livestream URLs (`enabled` turns live
+    /// mode on for them)
+    #[serde(default, deserialize_with = "lenient")]
+    pub live: LiveOptions,
+    /// Picking up media links copied to the clipboard
+    #[serde(default, deserialize_with = "lenient")]
+    pub clipboard: ClipboardOptions,
+    /// Local HTTP API for browser extensions and scripts
+    #[serde(default, deserialize_with = "lenient")]
+    pub api: ApiOptions,
 }
 
 fn default_true() -> bool {
     true
 }
 
+/// Reads a field, falling back to its default when the saved value is no
+/// longer valid (e.g. a quality written by an older build), so one stale
+/// value doesn't reset every preference
+fn lenient<'de, D, T>(deserializer: D) -> Result<T, D::Error>
+where
+    D: Deserializer<'de>,
+    T: DeserializeOwned + Default,
+{
+    let value = serde_json::Value::deserialize(deserializer)?;
+    Ok(serde_json::from_value(value.clone()).unwrap_or_else(|e| {
+        tracing::warn!("Ignoring saved preference {}: {}", value, e);
+        T::default()
+    }))
+}
+
+fn default_target_lufs() -> f64 {
+    DEFAULT_TARGET_LUFS
+}
+
 impl Default for Preferences {
     fn default() -> Self {
         Self {
             output_folder: get_default_downloads_folder(),
-            format: "video-mp4".to_string(),
-            quality: "best".to_string(),
+            format: OutputFormat::Video(VideoContainer::Mp4),
+            quality: Quality::Best,
             embed_subtitles: false,
             cookies_from_browser: None,
             check_updates_on_startup: true,
@@ -59,10 +125,92 @@ impl Default for Preferences {
             proxy_url: None,
             filename_template: None,
             cookies_file_path: None,
+            organize_rules: Vec::new(),
+            audio_output_folder: None,
+            video_output_folder: None,
+            format_output_folders: HashMap::new(),
+            transcode_preset: None,
+            normalize_loudness: false,
+            loudness_target_lufs: DEFAULT_TARGET_LUFS,
+            use_download_archive: false,
+            live: LiveOptions::default(),
+            clipboard: ClipboardOptions::default(),
+            api: ApiOptions::default(),
         }
     }
 }
 
+impl Preferences {
+    /// Picks the output root for a download
+    ///
+    /// A folder set on the config was chosen for this download and is kept;
+    /// callers leave it empty to use the defaults. Then the per-format override
+    /// wins over the audio/video folder, which wins over `output_folder`.
+    pub fn resolve_output_folder(&self, config: &DownloadConfig) -> String {
+        if !config.output_folder.is_empty() {
+            return config.output_folder.clone();
+        }
+
+        let media_folder = if ArgumentBuilder::new(config.clone()).is_audio_extraction() {
+            &self.audio_output_folder
+        } else {
+            &self.video_output_folder
+        };
+
+        let non_empty = |folder: &&String| !folder.trim().is_empty();
+        self.format_output_folders
+            .get(&config.format)
+            .filter(non_empty)
+            .or(media_folder.as_ref().filter(non_empty))
+            .cloned()
+            .unwrap_or_else(|| self.output_folder.clone())
+    }
+
+    /// Download config for `url` with the default settings, as the download
+    /// form would fill it in
+    pub fn download_config(&self, url: &str) -> DownloadConfig {
+        let mut config = DownloadConfig {
+            url: url.to_string(),
+            format: self.format,
+            quality: self.quality,
+            output_folder: String::new(),
+            embed_subtitles: self.embed_subtitles,
+            cookies_from_browser: self.cookies_from_browser.clone(),
+            filename_template: self.filename_template.clone(),
+            proxy_url: self.proxy_url.clone().filter(|_| self.proxy_enabled),
+            cookies_file_path: self.cookies_file_path.clone(),
+            max_fps: None,
+            allow_hdr: true,
+            video_codec: None,
+            sections: Vec::new(),
+            chapter_patterns: Vec::new(),
+            sponsorblock: Default::default(),
+            split_chapters: false,
+            subtitles: Default::default(),
+            organize_rules: self.organize_rules.clone(),
+            transcode_preset: self.transcode_preset,
+            loudness_target_lufs: self.normalize_loudness.then_some(self.loudness_target_lufs),
+            // Live settings are only applied to downloads known to be live;
+            // a duration limit would cut regular videos short
+            live: LiveOptions::default(),
+            playlist: None,
+            playlist_filter: Default::default(),
+        };
+        config.output_folder = self.resolve_output_folder(&config);
+        config
+    }
+
+    /// Download archive file to hand to yt-dlp, if the archive is enabled
+    pub fn download_archive(&self) -> Option<String> {
+        if !self.use_download_archive {
+            return None;
+        }
+        paths::get_download_archive_path()
+            .ok()
+            .map(|path| path.to_string_lossy().to_string())
+    }
+}
+
 /// Get the default downloads folder for the current user
 fn get_default_downloads_folder() -> String {
     dirs::download_dir()
@@ -78,6 +226,19 @@ fn get_default_downloads_folder() -> String {
 const STORE_PATH: &str = "preferences.json";
 const PREFERENCES_KEY: &str = "preferences";
 
+/// Reads the saved preferences for backend use, falling back to defaults
+pub fn get_saved_preferences(app: &tauri::AppHandle) -> Preferences {
+    app.store(STORE_PATH)
+        .ok()
+        .and_then(|store| store.get(PREFERENCES_KEY))
+        .and_then(|value| {
+            serde_json::from_value(value)
+                .map_err(|e| tracing::warn!("Saved preferences are invalid, using defaults: {}", e))
+                .ok()
+        })
+        .unwrap_or_default()
+}
+
 /// Load user preferences from persistent storage
 /// 
 /// Requirements: 9.3 - Restore previously saved preferences on launch
@@ -126,24 +287,26 @@ pub async fn save_preferences(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::models::AudioCodec;
 
     #[test]
     fn test_default_preferences() {
         let prefs = Preferences::default();
-        assert_eq!(prefs.format, "video-mp4");
-        assert_eq!(prefs.quality, "best");
+        assert_eq!(prefs.format, OutputFormat::Video(VideoContainer::Mp4));
+        assert_eq!(prefs.quality, Quality::Best);
         assert!(!prefs.embed_subtitles);
         assert!(prefs.cookies_from_browser.is_none());
         assert!(prefs.check_updates_on_startup);
         assert!(prefs.check_app_updates_on_startup);
+        assert!(prefs.organize_rules.is_empty());
     }
 
     #[test]
     fn test_preferences_serialization() {
         let prefs = Preferences {
             output_folder: "C:\\Downloads".to_string(),
-            format: "audio-mp3".to_string(),
-            quality: "720p".to_string(),
+            format: OutputFormat::Audio(AudioCodec::Mp3),
+            quality: Quality::P720,
             embed_subtitles: true,
             cookies_from_browser: Some("chrome".to_string()),
             check_updates_on_startup: false,
@@ -152,6 +315,31 @@ mod tests {
             proxy_url: None,
             filename_template: Some("{title} - {uploader}".to_string()),
             cookies_file_path: None,
+            organize_rules: vec![OrganizeRule::Site, OrganizeRule::Uploader],
+            audio_output_folder: Some("C:\\Music".to_string()),
+            video_output_folder: None,
+            format_output_folders: HashMap::from([(
+                OutputFormat::Audio(AudioCodec::Flac),
+                "C:\\Music\\Lossless".to_string(),
+            )]),
+            transcode_preset: Some(TranscodePreset::Phone720p),
+            normalize_loudness: true,
+            loudness_target_lufs: -23.0,
+            use_download_archive: false,
+            live: LiveOptions {
+                enabled: true,
+                max_duration_seconds: Some(7200),
+                ..LiveOptions::default()
+            },
+            clipboard: ClipboardOptions {
+                enabled: true,
+                allowed_domains: vec!["vimeo.com".to_string()],
+                ..ClipboardOptions::default()
+            },
+            api: ApiOptions {
+                enabled: true,
+                port: 8765,
+            },
         };
         
         let json = serde_json::to_string(&prefs).unwrap();
@@ -176,5 +364,115 @@ mod tests {
         // Should default to true
         assert!(parsed.check_updates_on_startup);
         assert!(parsed.check_app_updates_on_startup);
+        assert!(!parsed.normalize_loudness);
+        assert_eq!(parsed.loudness_target_lufs, DEFAULT_TARGET_LUFS);
+        assert!(!parsed.use_download_archive);
+        assert_eq!(parsed.live, LiveOptions::default());
+        assert!(!parsed.clipboard.enabled);
+        assert_eq!(parsed.api, ApiOptions::default());
+    }
+
+    fn config_for(format: OutputFormat, output_folder: &str) -> DownloadConfig {
+        serde_json::from_value(serde_json::json!({
+            "url": "https://youtube.com/watch?v=test",
+            "format": format,
+            "quality": "best",
+            "outputFolder": output_folder,
+            "embedSubtitles": false,
+            "cookiesFromBrowser": null
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_resolve_output_folder_by_media_type() {
+        let prefs = Preferences {
+            output_folder: "C:\\Downloads".to_string(),
+            audio_output_folder: Some("C:\\Music".to_string()),
+            video_output_folder: Some("C:\\Videos".to_string()),
+            format_output_folders: HashMap::from([(
+                OutputFormat::Audio(AudioCodec::Flac),
+                "C:\\Lossless".to_string(),
+            )]),
+            ..Preferences::default()
+        };
+
+        let resolve = |format, folder| prefs.resolve_output_folder(&config_for(format, folder));
+
+        assert_eq!(resolve(OutputFormat::Audio(AudioCodec::Mp3), ""), "C:\\Music");
+        assert_eq!(resolve(OutputFormat::Audio(AudioCodec::Flac), ""), "C:\\Lossless");
+        assert_eq!(resolve(OutputFormat::Video(VideoContainer::Mkv), ""), "C:\\Videos");
+        // An explicitly chosen folder is kept, even when it is the general default
+        assert_eq!(resolve(OutputFormat::Audio(AudioCodec::Mp3), "D:\\Other"), "D:\\Other");
+        assert_eq!(resolve(OutputFormat::Audio(AudioCodec::Mp3), "C:\\Downloads"), "C:\\Downloads");
+    }
+
+    #[test]
+    fn test_resolve_output_folder_defaults() {
+        let prefs = Preferences {
+            output_folder: "C:\\Downloads".to_string(),
+            audio_output_folder: Some("  ".to_string()),
+            ..Preferences::default()
+        };
+
+        let config = config_for(OutputFormat::Audio(AudioCodec::Mp3), "");
+        assert_eq!(prefs.resolve_output_folder(&config), "C:\\Downloads");
+    }
+
+    #[test]
+    fn test_download_config_uses_defaults() {
+        let prefs = Preferences {
+            output_folder: "C:\\Downloads".to_string(),
+            format: OutputFormat::Audio(AudioCodec::Mp3),
+            audio_output_folder: Some("C:\\Music".to_string()),
+            proxy_url: Some("socks5://127.0.0.1:1080".to_string()),
+            normalize_loudness: true,
+            live: LiveOptions {
+                enabled: true,
+                max_duration_seconds: Some(3600),
+                ..LiveOptions::default()
+            },
+            ..Preferences::default()
+        };
+
+        let config = prefs.download_config("https://youtu.be/dQw4w9WgXcQ");
+        assert_eq!(config.url, "https://youtu.be/dQw4w9WgXcQ");
+        assert_eq!(config.output_folder, "C:\\Music");
+        assert_eq!(config.loudness_target_lufs, Some(DEFAULT_TARGET_LUFS));
+        // A saved duration limit must not truncate a regular video
+        assert_eq!(config.live, LiveOptions::default());
+        let args = ArgumentBuilder::new(config.clone()).build();
+        assert!(!args.iter().any(|arg| arg.contains("-t ") || arg.contains("live-from-start")));
+        // The proxy is only used while enabled
+        assert!(config.proxy_url.is_none());
+    }
+
+    #[test]
+    fn test_preferences_unknown_values_fall_back_per_field() {
+        // Saved by an older build: free-form quality and format strings
+        let json = r#"{
+            "outputFolder": "C:\\Downloads",
+            "format": "video-mp5",
+            "quality": "4k",
+            "embedSubtitles": true,
+            "cookiesFromBrowser": "firefox",
+            "proxyEnabled": true,
+            "proxyUrl": "http://127.0.0.1:8080",
+            "transcodePreset": "vhs",
+            "organizeRules": ["site", "moon-phase"],
+            "live": {"enabled": true, "start": "yesterday"}
+        }"#;
+
+        let parsed: Preferences = serde_json::from_str(json).unwrap();
+        assert_eq!(parsed.format, OutputFormat::default());
+        assert_eq!(parsed.quality, Quality::Best);
+        assert_eq!(parsed.transcode_preset, None);
+        assert!(parsed.organize_rules.is_empty());
+        assert_eq!(parsed.live, LiveOptions::default());
+        // Everything else is kept
+        assert_eq!(parsed.output_folder, "C:\\Downloads");
+        assert!(parsed.embed_subtitles);
+        assert_eq!(parsed.cookies_from_browser.as_deref(), Some("firefox"));
+        assert_eq!(parsed.proxy_url.as_deref(), Some("http://127.0.0.1:8080"));
     }
 }
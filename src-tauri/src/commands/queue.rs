@@ -5,12 +5,22 @@
 use tauri::{AppHandle, Emitter, Manager, State};
 use tauri_plugin_notification::NotificationExt;
 use tauri_plugin_store::StoreExt;
-use tokio::sync::mpsc;
-
-use crate::commands::history::{HistoryItem, DownloadStats};
-use crate::download::queue::{QueueEvent, QueueItem, QueueItemId, QueueItemStatus, SharedDownloadQueue};
-use crate::download::{spawn_ytdlp, stream_process_output, ProcessOutput, SpawnConfig};
-use crate::models::{DownloadConfig, DownloadError};
+use tokio::sync::{broadcast, mpsc};
+
+use crate::commands::folder::prepare_output_folder;
+use crate::commands::preferences::get_saved_preferences;
+use crate::commands::history::{find_history_duplicate, load_history, HistoryItem, DownloadStats};
+use crate::commands::playlist::{PlaylistEntry, PlaylistInfo};
+use crate::download::queue::{
+    DownloadQueue, QueueEvent, QueueItem, QueueItemId, QueueItemStatus, QueueSchedule, SharedDownloadQueue,
+};
+use crate::download::{
+    find_subtitle_files, run_loudnorm, run_transcode, spawn_ytdlp, stream_process_output, Duplicate, ProcessOutput,
+    SpawnConfig,
+};
+use crate::models::{
+    DownloadConfig, DownloadError, LoudnessMeasurement, PlaylistContext, PlaylistSelection, ProgressEvent, TranscodePreset,
+};
 use crate::utils::paths;
 
 const HISTORY_STORE_PATH: &str = "history.json";
@@ -21,14 +31,176 @@ const MAX_HISTORY_ITEMS: usize = 500;
 /// Event name for queue updates
 const EVENT_QUEUE_UPDATE: &str = "queue-update";
 
+/// Result of adding a URL to the queue
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(tag = "type", rename_all = "camelCase")]
+pub enum QueueAddResult {
+    /// The item was queued
+    Added { item: Box<QueueItem> },
+    /// Not queued: the same media is already queued or was downloaded before
+    Duplicate { duplicate: Duplicate },
+}
+
 /// Adds a URL to the download queue
+///
+/// Pending/active queue items and completed history entries are checked
+/// first; pass `force` to queue a duplicate anyway. With a `schedule` the
+/// item waits until its start time and fails if it can't finish by the
+/// deadline.
 #[tauri::command]
 pub async fn queue_add(
     config: DownloadConfig,
+    force: Option<bool>,
+    schedule: Option<QueueSchedule>,
+    app: AppHandle,
     queue: State<'_, SharedDownloadQueue>,
-) -> Result<QueueItem, String> {
-    let item = queue.add(config).await;
-    Ok(item)
+) -> Result<QueueAddResult, String> {
+    add_to_queue(&app, &queue, config, force.unwrap_or(false), schedule).await
+}
+
+/// Queues a download the way `queue_add` does (also used by the local API)
+pub async fn add_to_queue(
+    app: &AppHandle,
+    queue: &SharedDownloadQueue,
+    mut config: DownloadConfig,
+    force: bool,
+    schedule: Option<QueueSchedule>,
+) -> Result<QueueAddResult, String> {
+    if !force {
+        let duplicate = match queue.find_duplicate(&config.url).await {
+            Some(duplicate) => Some(duplicate),
+            None => find_history_duplicate(&load_history(app), &config.url),
+        };
+        if let Some(duplicate) = duplicate {
+            tracing::info!("Not queueing {}: duplicate of {}", config.url, duplicate.url);
+            return Ok(QueueAddResult::Duplicate { duplicate });
+        }
+    }
+
+    // Audio and video can have separate default folders
+    config.output_folder = get_saved_preferences(app).resolve_output_folder(&config);
+    if let Some(deadline) = schedule.and_then(|s| s.deadline) {
+        if schedule.and_then(|s| s.not_before).is_some_and(|not_before| not_before >= deadline) {
+            return Err("The deadline must be after the start time".to_string());
+        }
+    }
+
+    let item = queue
+        .add_scheduled(config, schedule, force, chrono::Utc::now().timestamp())
+        .await;
+    Ok(QueueAddResult::Added { item: Box::new(item) })
+}
+
+/// Result of queueing a playlist
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlaylistAddResult {
+    /// Shared by every item queued from the playlist
+    pub group_id: String,
+    pub items: Vec<QueueItem>,
+    /// Selected entries that weren't queued because they're duplicates
+    pub duplicates: Vec<Duplicate>,
+}
+
+/// Queues the selected entries of a playlist as individual items
+///
+/// `config` holds the settings for every entry; its URL is replaced with
+/// the entry's. Each item knows its playlist position and title for
+/// `{playlist_index}`/`{playlist_title}` and the playlist organize rule.
+/// Entries outside `config.playlist_filter` are left out, matching the
+/// filtered preview; yt-dlp checks the filter again when downloading.
+#[tauri::command]
+pub async fn queue_add_playlist(
+    playlist: PlaylistInfo,
+    selection: PlaylistSelection,
+    config: DownloadConfig,
+    force: Option<bool>,
+    app: AppHandle,
+    queue: State<'_, SharedDownloadQueue>,
+) -> Result<PlaylistAddResult, String> {
+    config.playlist_filter.validate().map_err(|e| e.to_string())?;
+    let entries: Vec<&PlaylistEntry> = playlist
+        .entries
+        .iter()
+        .filter(|entry| config.playlist_filter.matches(entry))
+        .collect();
+    let positions: Vec<(u32, Option<&str>)> = entries
+        .iter()
+        .map(|entry| (entry.playlist_index, entry.upload_date.as_deref()))
+        .collect();
+    let selected = selection.apply(&positions);
+    if selected.is_empty() {
+        return Err("No playlist entries selected".to_string());
+    }
+
+    let group_id = format!("playlist-{}", chrono::Utc::now().timestamp_millis());
+    let count = playlist
+        .entries
+        .iter()
+        .map(|entry| entry.playlist_index)
+        .max()
+        .unwrap_or(0)
+        .max(playlist.video_count);
+    let history = if force.unwrap_or(false) { Vec::new() } else { load_history(&app) };
+    let preferences = get_saved_preferences(&app);
+
+    let mut items = Vec::with_capacity(selected.len());
+    let mut duplicates = Vec::new();
+    for entry in selected.into_iter().map(|i| entries[i]) {
+        if !force.unwrap_or(false) {
+            let duplicate = match queue.find_duplicate(&entry.url).await {
+                Some(duplicate) => Some(duplicate),
+                None => find_history_duplicate(&history, &entry.url),
+            };
+            if let Some(duplicate) = duplicate {
+                duplicates.push(duplicate);
+                continue;
+            }
+        }
+
+        let mut entry_config = DownloadConfig {
+            url: entry.url.clone(),
+            playlist: Some(PlaylistContext {
+                group_id: group_id.clone(),
+                index: entry.playlist_index,
+                count,
+                title: playlist.title.clone(),
+            }),
+            ..config.clone()
+        };
+        entry_config.output_folder = preferences.resolve_output_folder(&entry_config);
+        items.push(add_playlist_entry(&queue, entry_config, entry, force.unwrap_or(false)).await);
+    }
+
+    tracing::info!(
+        "Queued {} entries from playlist {} ({} duplicates skipped)",
+        items.len(),
+        playlist.title,
+        duplicates.len()
+    );
+    Ok(PlaylistAddResult {
+        group_id,
+        items,
+        duplicates,
+    })
+}
+
+/// Queues one playlist entry, with the title and thumbnail from the listing
+async fn add_playlist_entry(
+    queue: &DownloadQueue,
+    config: DownloadConfig,
+    entry: &PlaylistEntry,
+    force: bool,
+) -> QueueItem {
+    let mut item = queue
+        .add_scheduled(config, None, force, chrono::Utc::now().timestamp())
+        .await;
+    queue
+        .set_media_info(item.id, entry.title.clone(), entry.thumbnail.clone())
+        .await;
+    item.title = Some(entry.title.clone());
+    item.thumbnail = entry.thumbnail.clone();
+    item
 }
 
 /// Gets all items in the queue
@@ -114,6 +286,22 @@ pub async fn queue_resume_all(
 pub async fn start_queue_processor(app: AppHandle, queue: SharedDownloadQueue) {
     let semaphore = queue.semaphore();
 
+    // Scheduled items whose time has come become pending; ones that missed
+    // their deadline before starting fail. This runs on its own so it stays
+    // on time while every download slot is busy.
+    let scheduler_app = app.clone();
+    let scheduler_queue = queue.clone();
+    tokio::spawn(async move {
+        let mut interval = tokio::time::interval(tokio::time::Duration::from_secs(1));
+        loop {
+            interval.tick().await;
+            for item in scheduler_queue.release_due(chrono::Utc::now().timestamp()).await {
+                let error = item.error.as_deref();
+                add_to_history_internal(&scheduler_app, &item.config, &item, None, "failed", error, None).await;
+            }
+        }
+    });
+
     loop {
         // Wait for a permit (respects max concurrent limit)
         let permit = semaphore.clone().acquire_owned().await;
@@ -156,7 +344,17 @@ async fn process_queue_item(app: AppHandle, queue: SharedDownloadQueue, item: Qu
         Ok(paths) => paths,
         Err(e) => {
             queue.fail(id, e.clone()).await;
-            add_to_history_internal(&app, &config, &item, None, "failed", Some(&e.to_string())).await;
+            add_to_history_internal(&app, &config, &item, None, "failed", Some(&e.to_string()), None).await;
+            return;
+        }
+    };
+
+    // Create organization subfolders and validate where the file will land
+    let output_folder = match prepare_output_folder(&config) {
+        Ok(folder) => folder,
+        Err(e) => {
+            queue.fail(id, e.clone()).await;
+            add_to_history_internal(&app, &config, &item, None, "failed", Some(&e.to_string()), None).await;
             return;
         }
     };
@@ -165,31 +363,52 @@ async fn process_queue_item(app: AppHandle, queue: SharedDownloadQueue, item: Qu
     let spawn_config = SpawnConfig {
         ytdlp_path: exec_paths.ytdlp.to_string_lossy().to_string(),
         ffmpeg_location: Some(exec_paths.ffmpeg_dir.to_string_lossy().to_string()),
+        // Forced re-adds are downloaded again even if they're archived
+        download_archive: if item.force {
+            None
+        } else {
+            get_saved_preferences(&app).download_archive()
+        },
     };
 
     let child = match spawn_ytdlp(&config, &spawn_config).await {
         Ok(child) => child,
         Err(e) => {
             queue.fail(id, e.clone()).await;
-            add_to_history_internal(&app, &config, &item, None, "failed", Some(&e.to_string())).await;
+            add_to_history_internal(&app, &config, &item, None, "failed", Some(&e.to_string()), None).await;
             return;
         }
     };
 
     // Create channel for process output
     let (tx, mut rx) = mpsc::channel::<ProcessOutput>(100);
-    let output_folder = config.output_folder.clone();
 
     // Spawn task to stream process output
-    tokio::spawn(async move {
+    let stream = tokio::spawn(async move {
         let _ = stream_process_output(child, tx).await;
     });
 
+    // A scheduled item has to finish by its deadline, post-processing included
+    let deadline = item.schedule.and_then(|s| s.deadline).map(|deadline| {
+        let remaining = (deadline - chrono::Utc::now().timestamp()).max(0) as u64;
+        tokio::time::Instant::now() + tokio::time::Duration::from_secs(remaining)
+    });
+
     // Track detected file path
     let mut detected_file_path: Option<String> = None;
+    // Files written by --split-chapters
+    let mut chapter_files: Vec<String> = Vec::new();
 
     // Handle process output
-    while let Some(output) = rx.recv().await {
+    loop {
+        let Some(output) = within_deadline(deadline, rx.recv()).await else {
+            // Dropping the stream task kills yt-dlp
+            stream.abort();
+            fail_queue_item(&app, &queue, &item, deadline_missed()).await;
+            return;
+        };
+        let Some(output) = output else { break };
+
         // Check if cancelled
         if let Some(current) = queue.get(id).await {
             if current.status == QueueItemStatus::Cancelled {
@@ -204,25 +423,89 @@ async fn process_queue_item(app: AppHandle, queue: SharedDownloadQueue, item: Qu
             ProcessOutput::Merging => {
                 queue.update_status(id, QueueItemStatus::Merging).await;
             }
+            ProcessOutput::WaitingForLive => {
+                queue.update_status(id, QueueItemStatus::WaitingForLive).await;
+            }
             ProcessOutput::FilePath(path) => {
                 detected_file_path = Some(path);
             }
+            ProcessOutput::ChapterFile(path) => {
+                chapter_files.push(path);
+            }
+            ProcessOutput::AlreadyArchived(title) => {
+                // Downloaded before (e.g. an earlier run of the same playlist);
+                // history and notifications are left alone
+                tracing::info!("Queue item {} skipped by download archive: {}", id, title);
+                queue.skip(id, DownloadError::AlreadyArchived(title)).await;
+                return;
+            }
+            ProcessOutput::FilteredOut(reason) => {
+                // Outside the playlist filter once the full metadata is known
+                // (flat listings often lack upload dates)
+                tracing::info!("Queue item {} skipped by playlist filter: {}", id, reason);
+                queue.skip(id, DownloadError::FilteredOut(reason)).await;
+                return;
+            }
             ProcessOutput::Error(error) => {
                 let error_str = error.to_string();
                 queue.fail(id, error).await;
-                add_to_history_internal(&app, &config, &item, None, "failed", Some(&error_str)).await;
+                add_to_history_internal(&app, &config, &item, None, "failed", Some(&error_str), None).await;
                 send_notification(&app, "Download Failed", &config.url, false);
                 return;
             }
             ProcessOutput::Completed(_) => {
-                let file_path = detected_file_path
+                let mut file_path = detected_file_path
                     .clone()
                     .unwrap_or_else(|| find_latest_file_sync(&output_folder).unwrap_or_default());
 
-                queue.complete(id, file_path.clone()).await;
+                // Sidecar subtitles are written next to the main file
+                let subtitle_files = if config.subtitles.download_subtitles && !file_path.is_empty() {
+                    find_subtitle_files(&file_path)
+                } else {
+                    Vec::new()
+                };
+
+                // Loudness normalization (audio only) runs before any re-encode.
+                // A failure keeps the download as it is.
+                let mut loudness = None;
+                if let Some(target) = config
+                    .loudness_target_lufs
+                    .filter(|_| config.format.is_audio() && !file_path.is_empty())
+                {
+                    queue.update_status(id, QueueItemStatus::Transcoding).await;
+                    let ffmpeg = exec_paths.ffmpeg.to_string_lossy().to_string();
+                    match within_deadline(deadline, run_loudnorm(&ffmpeg, &file_path, target)).await {
+                        Some(Ok(measurement)) => loudness = Some(measurement),
+                        Some(Err(e)) => tracing::warn!("Skipping loudness normalization: {}", e),
+                        // Dropping the normalization kills ffmpeg
+                        None => {
+                            fail_queue_item(&app, &queue, &item, deadline_missed()).await;
+                            return;
+                        }
+                    }
+                }
+
+                // Optional re-encode; the transcoded file becomes the final file
+                if let Some(preset) = config.transcode_preset.filter(|_| !file_path.is_empty()) {
+                    match transcode_queue_item(&queue, id, &exec_paths.ffmpeg, &file_path, preset, deadline).await {
+                        Ok(path) => file_path = path,
+                        Err(_) if is_cancelled(&queue, id).await => return,
+                        Err(error) => {
+                            let error_str = error.to_string();
+                            queue.fail(id, error).await;
+                            add_to_history_internal(&app, &config, &item, None, "failed", Some(&error_str), None).await;
+                            send_notification(&app, "Download Failed", &config.url, false);
+                            return;
+                        }
+                    }
+                }
+
+                queue
+                    .complete_with_files(id, file_path.clone(), std::mem::take(&mut chapter_files), subtitle_files)
+                    .await;
                 
                 // Add to history
-                add_to_history_internal(&app, &config, &item, Some(&file_path), "completed", None).await;
+                add_to_history_internal(&app, &config, &item, Some(&file_path), "completed", None, loudness).await;
 
                 // Extract filename for notification
                 let title = std::path::Path::new(&file_path)
@@ -236,7 +519,7 @@ async fn process_queue_item(app: AppHandle, queue: SharedDownloadQueue, item: Qu
                 let error = DownloadError::DownloadFailed(format!("Process exited with code {}", code));
                 let error_str = error.to_string();
                 queue.fail(id, error).await;
-                add_to_history_internal(&app, &config, &item, None, "failed", Some(&error_str)).await;
+                add_to_history_internal(&app, &config, &item, None, "failed", Some(&error_str), None).await;
                 send_notification(&app, "Download Failed", &config.url, false);
                 return;
             }
@@ -248,6 +531,72 @@ async fn process_queue_item(app: AppHandle, queue: SharedDownloadQueue, item: Qu
     }
 }
 
+/// Runs `future` until `deadline`, if there is one; None once it's missed
+async fn within_deadline<F: std::future::Future>(
+    deadline: Option<tokio::time::Instant>,
+    future: F,
+) -> Option<F::Output> {
+    match deadline {
+        Some(at) => tokio::time::timeout_at(at, future).await.ok(),
+        None => Some(future.await),
+    }
+}
+
+/// Error for a scheduled item that ran past its deadline
+fn deadline_missed() -> DownloadError {
+    DownloadError::DeadlineMissed("the download didn't finish in time".to_string())
+}
+
+/// Fails a queue item, recording it in history and notifying the user
+async fn fail_queue_item(app: &AppHandle, queue: &SharedDownloadQueue, item: &QueueItem, error: DownloadError) {
+    let error_str = error.to_string();
+    queue.fail(item.id, error).await;
+    add_to_history_internal(app, &item.config, item, None, "failed", Some(&error_str), None).await;
+    send_notification(app, "Download Failed", &item.config.url, false);
+}
+
+/// Whether the user cancelled a queue item
+async fn is_cancelled(queue: &SharedDownloadQueue, id: QueueItemId) -> bool {
+    matches!(queue.get(id).await, Some(item) if item.status == QueueItemStatus::Cancelled)
+}
+
+/// Runs a transcode preset on a finished download, reporting ffmpeg progress
+/// through the queue item
+///
+/// Cancelling the item or missing its deadline kills ffmpeg.
+async fn transcode_queue_item(
+    queue: &SharedDownloadQueue,
+    id: QueueItemId,
+    ffmpeg: &std::path::Path,
+    input: &str,
+    preset: TranscodePreset,
+    deadline: Option<tokio::time::Instant>,
+) -> Result<String, DownloadError> {
+    queue.update_status(id, QueueItemStatus::Transcoding).await;
+
+    let (tx, mut rx) = mpsc::channel::<ProgressEvent>(100);
+    let ffmpeg = ffmpeg.to_string_lossy().to_string();
+    let input = input.to_string();
+    let transcode = tokio::spawn(async move { run_transcode(&ffmpeg, &input, preset, tx).await });
+
+    loop {
+        let Some(event) = within_deadline(deadline, rx.recv()).await else {
+            transcode.abort();
+            return Err(deadline_missed());
+        };
+        let Some(event) = event else { break };
+        if is_cancelled(queue, id).await {
+            transcode.abort();
+            return Err(DownloadError::TranscodeFailed("Cancelled".to_string()));
+        }
+        queue.update_progress(id, &event).await;
+    }
+
+    transcode
+        .await
+        .map_err(|e| DownloadError::TranscodeFailed(e.to_string()))?
+}
+
 /// Internal function to add download to history
 async fn add_to_history_internal(
     app: &AppHandle,
@@ -256,20 +605,22 @@ async fn add_to_history_internal(
     file_path: Option<&str>,
     status: &str,
     error: Option<&str>,
+    loudness: Option<LoudnessMeasurement>,
 ) {
     let history_item = HistoryItem {
         id: format!("{}-{}", chrono::Utc::now().timestamp_millis(), item.id),
         url: config.url.clone(),
         title: item.title.clone().unwrap_or_else(|| "Unknown".to_string()),
         thumbnail: item.thumbnail.clone(),
-        format: format!("{:?}", config.format).to_lowercase().replace("\"", ""),
-        quality: format!("{:?}", config.quality).to_lowercase().replace("\"", ""),
+        format: config.format.to_string(),
+        quality: config.quality.to_string(),
         file_path: file_path.map(|s| s.to_string()),
         file_size: get_file_size(file_path),
         duration: None,
         downloaded_at: chrono::Utc::now().timestamp(),
         status: status.to_string(),
         error: error.map(|s| s.to_string()),
+        loudness,
     };
 
     if let Err(e) = save_history_item(app, history_item).await {
@@ -403,11 +754,46 @@ fn find_latest_file_sync(folder: &str) -> Option<String> {
     latest.map(|(_, path)| path)
 }
 
-/// Sets up the queue event listener to forward events to frontend
-pub fn setup_queue_events(app: AppHandle, mut event_rx: mpsc::UnboundedReceiver<QueueEvent>) {
+/// Sets up the queue event listener to forward events to the frontend and
+/// to local API clients
+pub fn setup_queue_events(
+    app: AppHandle,
+    mut event_rx: mpsc::UnboundedReceiver<QueueEvent>,
+    api_events: broadcast::Sender<QueueEvent>,
+) {
     tauri::async_runtime::spawn(async move {
         while let Some(event) = event_rx.recv().await {
             let _ = app.emit(EVENT_QUEUE_UPDATE, &event);
+            // Fails only when no API client is listening
+            let _ = api_events.send(event);
         }
     });
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::preferences::Preferences;
+
+    #[tokio::test]
+    async fn test_forced_playlist_entry_keeps_force() {
+        let (tx, _rx) = mpsc::unbounded_channel();
+        let queue = DownloadQueue::new(1, tx);
+        let entry: PlaylistEntry = serde_json::from_value(serde_json::json!({
+            "id": "dQw4w9WgXcQ",
+            "title": "Never Gonna Give You Up",
+            "url": "https://youtu.be/dQw4w9WgXcQ",
+            "thumbnail": null,
+            "duration": 213.0,
+            "playlistIndex": 1,
+            "uploader": null
+        }))
+        .unwrap();
+        let config = Preferences::default().download_config(&entry.url);
+
+        let item = add_playlist_entry(&queue, config, &entry, true).await;
+        assert!(item.force);
+        assert!(queue.get(item.id).await.unwrap().force);
+        assert_eq!(item.title.as_deref(), Some("Never Gonna Give You Up"));
+    }
+}
@@ -0,0 +1,280 @@
+//! Channel and playlist subscription commands
+//!
+//! Subscriptions are kept in their own store file. A background task syncs
+//! the due ones every minute: it lists the newest `SYNC_LISTING_LIMIT`
+//! entries, skips the ones that are already in the download history or the
+//! queue, and queues the rest with the subscription's preset.
+
+use tauri::{AppHandle, Emitter, State};
+use tauri_plugin_store::StoreExt;
+
+use crate::commands::history::load_history;
+use crate::commands::playlist::enumerate_playlist;
+use crate::commands::preferences::get_saved_preferences;
+use crate::download::queue::{QueueItemStatus, SharedDownloadQueue};
+use crate::download::{normalize_url, KnownMedia};
+use crate::models::{
+    DownloadConfig, PlaylistFilter, Subscription, DEFAULT_SYNC_INTERVAL_MINUTES, MIN_SYNC_INTERVAL_MINUTES,
+    SYNC_LISTING_LIMIT,
+};
+
+const STORE_PATH: &str = "subscriptions.json";
+const SUBSCRIPTIONS_KEY: &str = "subscriptions";
+
+/// Event name for finished subscription syncs
+const EVENT_SUBSCRIPTION_SYNCED: &str = "subscription-synced";
+
+/// Seconds between checks for due subscriptions
+const SYNC_CHECK_INTERVAL_SECONDS: u64 = 60;
+
+/// Outcome of syncing one subscription
+#[derive(Debug, Clone, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SubscriptionSyncResult {
+    pub subscription_id: String,
+    pub title: Option<String>,
+    /// Number of new entries queued
+    pub queued: usize,
+    pub error: Option<String>,
+}
+
+/// Reads all subscriptions from the store
+pub fn load_subscriptions(app: &AppHandle) -> Vec<Subscription> {
+    app.store(STORE_PATH)
+        .ok()
+        .and_then(|store| store.get(SUBSCRIPTIONS_KEY))
+        .and_then(|value| serde_json::from_value(value).ok())
+        .unwrap_or_default()
+}
+
+/// Writes all subscriptions to the store
+fn save_subscriptions(app: &AppHandle, subscriptions: &[Subscription]) -> Result<(), String> {
+    let store = app
+        .store(STORE_PATH)
+        .map_err(|e| format!("Failed to open subscriptions store: {}", e))?;
+
+    let value = serde_json::to_value(subscriptions)
+        .map_err(|e| format!("Failed to serialize subscriptions: {}", e))?;
+    store.set(SUBSCRIPTIONS_KEY, value);
+
+    store
+        .save()
+        .map_err(|e| format!("Failed to save subscriptions: {}", e))
+}
+
+/// Applies `update` to one stored subscription
+///
+/// The store is read again so changes made while a sync was running (e.g.
+/// the subscription being removed) aren't overwritten.
+fn update_subscription(app: &AppHandle, id: &str, update: impl FnOnce(&mut Subscription)) -> Result<(), String> {
+    let mut subscriptions = load_subscriptions(app);
+    match subscriptions.iter_mut().find(|s| s.id == id) {
+        Some(subscription) => update(subscription),
+        None => return Ok(()),
+    }
+    save_subscriptions(app, &subscriptions)
+}
+
+/// Subscribes to a channel or playlist
+///
+/// Unless `include_existing` is set, the entries that are there now are
+/// never downloaded; only videos added later are. A backfill covers the
+/// newest `SYNC_LISTING_LIMIT` entries.
+#[tauri::command]
+pub async fn subscription_add(
+    url: String,
+    preset: DownloadConfig,
+    interval_minutes: Option<u64>,
+    include_existing: Option<bool>,
+    app: AppHandle,
+) -> Result<Subscription, String> {
+    let url = url.trim().to_string();
+    let mut subscriptions = load_subscriptions(&app);
+    if subscriptions
+        .iter()
+        .any(|s| normalize_url(&s.url) == normalize_url(&url))
+    {
+        return Err("Already subscribed to this URL".to_string());
+    }
+    preset.playlist_filter.validate().map_err(|e| e.to_string())?;
+
+    // Also checks that the URL is a playlist or channel. Unfiltered, so that
+    // without backfill every existing entry a sync could list is left alone.
+    let limit = Some(SYNC_LISTING_LIMIT);
+    let info = enumerate_playlist(&app, &url, None, limit, &PlaylistFilter::default(), None).await?;
+
+    let now = chrono::Utc::now().timestamp();
+    let include_existing = include_existing.unwrap_or(false);
+    let mut subscription = Subscription {
+        id: format!("sub-{}", chrono::Utc::now().timestamp_millis()),
+        url,
+        title: Some(info.title),
+        preset,
+        interval_minutes: interval_minutes
+            .unwrap_or(DEFAULT_SYNC_INTERVAL_MINUTES)
+            .max(MIN_SYNC_INTERVAL_MINUTES),
+        enabled: true,
+        created_at: now,
+        // Without backfill there is nothing to do until the next interval
+        last_synced_at: (!include_existing).then_some(now),
+        last_error: None,
+        ignored_urls: Vec::new(),
+    };
+    if !include_existing {
+        subscription.ignore_urls(info.entries.into_iter().map(|entry| entry.url));
+    }
+
+    subscriptions.push(subscription.clone());
+    save_subscriptions(&app, &subscriptions)?;
+
+    tracing::info!("Subscribed to {}", subscription.url);
+    Ok(subscription)
+}
+
+/// Removes a subscription (already queued entries stay queued)
+#[tauri::command]
+pub async fn subscription_remove(id: String, app: AppHandle) -> Result<(), String> {
+    let mut subscriptions = load_subscriptions(&app);
+    subscriptions.retain(|s| s.id != id);
+    save_subscriptions(&app, &subscriptions)
+}
+
+/// Lists all subscriptions
+#[tauri::command]
+pub async fn subscription_list(app: AppHandle) -> Result<Vec<Subscription>, String> {
+    Ok(load_subscriptions(&app))
+}
+
+/// Syncs one subscription, or all of them when `id` is None, right away
+#[tauri::command]
+pub async fn subscription_sync_now(
+    id: Option<String>,
+    app: AppHandle,
+    queue: State<'_, SharedDownloadQueue>,
+) -> Result<Vec<SubscriptionSyncResult>, String> {
+    let subscriptions: Vec<Subscription> = load_subscriptions(&app)
+        .into_iter()
+        .filter(|s| id.as_ref().is_none_or(|id| &s.id == id))
+        .collect();
+    if subscriptions.is_empty() && id.is_some() {
+        return Err("Subscription not found".to_string());
+    }
+
+    let mut results = Vec::with_capacity(subscriptions.len());
+    for subscription in &subscriptions {
+        results.push(sync_subscription(&app, queue.inner(), subscription).await);
+    }
+    Ok(results)
+}
+
+/// Lists a subscription and queues the entries that haven't been downloaded
+async fn sync_subscription(
+    app: &AppHandle,
+    queue: &SharedDownloadQueue,
+    subscription: &Subscription,
+) -> SubscriptionSyncResult {
+    let now = chrono::Utc::now().timestamp();
+
+    let filter = &subscription.preset.playlist_filter;
+    let limit = Some(SYNC_LISTING_LIMIT);
+    let info = match enumerate_playlist(app, &subscription.url, None, limit, filter, None).await {
+        Ok(info) => info,
+        Err(e) => {
+            tracing::warn!("Failed to sync subscription {}: {}", subscription.url, e);
+            let error = e.clone();
+            if let Err(e) = update_subscription(app, &subscription.id, |s| {
+                s.last_synced_at = Some(now);
+                s.last_error = Some(error);
+            }) {
+                tracing::error!("{}", e);
+            }
+            let result = SubscriptionSyncResult {
+                subscription_id: subscription.id.clone(),
+                title: subscription.title.clone(),
+                queued: 0,
+                error: Some(e),
+            };
+            let _ = app.emit(EVENT_SUBSCRIPTION_SYNCED, &result);
+            return result;
+        }
+    };
+
+    // Entries an earlier sync queued that were skipped (rejected by the filter
+    // once their full metadata was known, or already archived) are not queued
+    // again. Failed, cancelled and cleared ones are retried.
+    let skipped: Vec<String> = queue
+        .get_all()
+        .await
+        .into_iter()
+        .filter(|item| item.status == QueueItemStatus::Skipped)
+        .map(|item| item.config.url)
+        .filter(|url| {
+            !subscription.ignored_urls.contains(url) && info.entries.iter().any(|entry| entry.url == *url)
+        })
+        .collect();
+
+    let history = load_history(app);
+    let mut known: KnownMedia = history
+        .iter()
+        .filter(|item| item.status == "completed")
+        .map(|item| item.url.as_str())
+        .chain(subscription.ignored_urls.iter().map(String::as_str))
+        .chain(skipped.iter().map(String::as_str))
+        .collect();
+    let preferences = get_saved_preferences(app);
+
+    // Playlists list the newest videos first on most channels; queue the
+    // oldest new video first
+    let mut queued = Vec::new();
+    for entry in info.entries.iter().rev() {
+        if known.contains(&entry.url) || queue.find_duplicate(&entry.url).await.is_some() {
+            continue;
+        }
+
+        let mut config = subscription.config_for(&entry.url);
+        config.output_folder = preferences.resolve_output_folder(&config);
+        queue.add(config).await;
+        known.insert(&entry.url);
+        queued.push(entry.url.clone());
+    }
+
+    if !queued.is_empty() {
+        tracing::info!("Queued {} new entries from {}", queued.len(), subscription.url);
+    }
+
+    let title = info.title;
+    let stored_title = title.clone();
+    let queued_count = queued.len();
+    if let Err(e) = update_subscription(app, &subscription.id, |s| {
+        s.title = Some(stored_title);
+        s.last_synced_at = Some(now);
+        s.last_error = None;
+        s.ignore_urls(skipped);
+    }) {
+        tracing::error!("{}", e);
+    }
+
+    let result = SubscriptionSyncResult {
+        subscription_id: subscription.id.clone(),
+        title: Some(title),
+        queued: queued_count,
+        error: None,
+    };
+    let _ = app.emit(EVENT_SUBSCRIPTION_SYNCED, &result);
+    result
+}
+
+/// Syncs due subscriptions in the background
+pub async fn start_subscription_sync(app: AppHandle, queue: SharedDownloadQueue) {
+    // Let the app finish starting up first
+    tokio::time::sleep(tokio::time::Duration::from_secs(10)).await;
+
+    loop {
+        let now = chrono::Utc::now().timestamp();
+        for subscription in load_subscriptions(&app).iter().filter(|s| s.is_due(now)) {
+            sync_subscription(&app, &queue, subscription).await;
+        }
+
+        tokio::time::sleep(tokio::time::Duration::from_secs(SYNC_CHECK_INTERVAL_SECONDS)).await;
+    }
+}
@@ -1,6 +1,7 @@
 //! Subtitle handling commands
 //!
-//! Implements subtitle listing and download options using yt-dlp.
+//! Implements subtitle listing using yt-dlp. Download options are carried on
+//! `DownloadConfig` as `models::SubtitleOptions`.
 
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
@@ -34,34 +35,6 @@ pub struct SubtitleInfo {
     pub has_subtitles: bool,
 }
 
-/// Subtitle download options
-#[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(rename_all = "camelCase")]
-pub struct SubtitleOptions {
-    /// Whether to download subtitles
-    pub download_subtitles: bool,
-    /// Whether to embed subtitles in video
-    pub embed_subtitles: bool,
-    /// Selected language codes (empty = all)
-    pub languages: Vec<String>,
-    /// Output format: "srt", "vtt", "ass", or "best"
-    pub format: String,
-    /// Whether to include auto-generated captions
-    pub include_auto: bool,
-}
-
-impl Default for SubtitleOptions {
-    fn default() -> Self {
-        Self {
-            download_subtitles: false,
-            embed_subtitles: false,
-            languages: vec![],
-            format: "srt".to_string(),
-            include_auto: false,
-        }
-    }
-}
-
 /// Fetch available subtitles for a URL
 #[tauri::command]
 pub async fn fetch_subtitles(url: String) -> Result<SubtitleInfo, String> {
@@ -218,55 +191,6 @@ fn get_language_name(code: &str) -> String {
         .unwrap_or_else(|| code.to_uppercase())
 }
 
-/// Build yt-dlp arguments for subtitle options
-pub fn build_subtitle_args(options: &SubtitleOptions) -> Vec<String> {
-    let mut args = Vec::new();
-
-    if !options.download_subtitles && !options.embed_subtitles {
-        return args;
-    }
-
-    // Write subtitles to file
-    if options.download_subtitles {
-        args.push("--write-subs".to_string());
-        
-        if options.include_auto {
-            args.push("--write-auto-subs".to_string());
-        }
-    }
-
-    // Embed subtitles in video
-    if options.embed_subtitles {
-        args.push("--embed-subs".to_string());
-    }
-
-    // Language selection
-    if !options.languages.is_empty() {
-        args.push("--sub-langs".to_string());
-        args.push(options.languages.join(","));
-    } else {
-        args.push("--sub-langs".to_string());
-        args.push("all".to_string());
-    }
-
-    // Output format
-    if options.download_subtitles {
-        args.push("--sub-format".to_string());
-        match options.format.as_str() {
-            "srt" => args.push("srt/best".to_string()),
-            "vtt" => args.push("vtt/best".to_string()),
-            "ass" => args.push("ass/best".to_string()),
-            _ => args.push("srt/best".to_string()),
-        }
-
-        // Convert to requested format if needed
-        args.push("--convert-subs".to_string());
-        args.push(options.format.clone());
-    }
-
-    args
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -278,55 +202,4 @@ mod tests {
         assert_eq!(get_language_name("en-US"), "English");
         assert_eq!(get_language_name("unknown"), "UNKNOWN");
     }
-
-    #[test]
-    fn test_build_subtitle_args_disabled() {
-        let options = SubtitleOptions::default();
-        let args = build_subtitle_args(&options);
-        assert!(args.is_empty());
-    }
-
-    #[test]
-    fn test_build_subtitle_args_download() {
-        let options = SubtitleOptions {
-            download_subtitles: true,
-            embed_subtitles: false,
-            languages: vec!["en".to_string(), "tr".to_string()],
-            format: "srt".to_string(),
-            include_auto: false,
-        };
-        let args = build_subtitle_args(&options);
-        assert!(args.contains(&"--write-subs".to_string()));
-        assert!(args.contains(&"--sub-langs".to_string()));
-        assert!(args.contains(&"en,tr".to_string()));
-        assert!(args.contains(&"--convert-subs".to_string()));
-    }
-
-    #[test]
-    fn test_build_subtitle_args_embed() {
-        let options = SubtitleOptions {
-            download_subtitles: false,
-            embed_subtitles: true,
-            languages: vec![],
-            format: "srt".to_string(),
-            include_auto: false,
-        };
-        let args = build_subtitle_args(&options);
-        assert!(args.contains(&"--embed-subs".to_string()));
-        assert!(args.contains(&"all".to_string()));
-    }
-
-    #[test]
-    fn test_build_subtitle_args_with_auto() {
-        let options = SubtitleOptions {
-            download_subtitles: true,
-            embed_subtitles: false,
-            languages: vec![],
-            format: "vtt".to_string(),
-            include_auto: true,
-        };
-        let args = build_subtitle_args(&options);
-        assert!(args.contains(&"--write-auto-subs".to_string()));
-        assert!(args.contains(&"vtt".to_string()));
-    }
 }
@@ -0,0 +1,33 @@
+//! Filename template commands
+//!
+//! Lets the frontend validate a template and preview the resulting path
+//! before a download starts.
+
+use crate::download::template::FilenameTemplate;
+use crate::models::{MediaInfo, OutputFormat};
+use crate::utils::sanitize::SanitizeOptions;
+
+/// Template equivalent of the built-in yt-dlp default ("%(title).100B [%(id)s]")
+const DEFAULT_PREVIEW_TEMPLATE: &str = "{title:100} [{id}]";
+
+/// Renders a filename template against media info
+///
+/// Returns the relative path (with `/` between subfolders) the download would
+/// be saved to, or the template error.
+#[tauri::command]
+pub async fn preview_filename_template(
+    template: String,
+    info: MediaInfo,
+    format: Option<OutputFormat>,
+) -> Result<String, String> {
+    let template = if template.trim().is_empty() {
+        DEFAULT_PREVIEW_TEMPLATE
+    } else {
+        template.as_str()
+    };
+
+    let parsed = FilenameTemplate::parse(template).map_err(|e| e.to_string())?;
+    let ext = format.unwrap_or_default().expected_extension();
+
+    Ok(parsed.render(&info, ext, &SanitizeOptions::default()))
+}
@@ -0,0 +1,131 @@
+//! Download archive file handling
+//!
+//! The archive lives in the app data directory and is handed to yt-dlp with
+//! `--download-archive`, which appends an entry after every finished
+//! download and skips videos that are already listed. The functions here
+//! read and rewrite the same file for listing, importing, exporting and
+//! pruning.
+
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+
+use crate::models::{ArchiveEntry, DownloadError};
+
+/// Parses archive file contents, dropping malformed lines and duplicates
+pub fn parse_archive(contents: &str) -> Vec<ArchiveEntry> {
+    let mut seen = HashSet::new();
+    contents
+        .lines()
+        .filter_map(ArchiveEntry::parse_line)
+        .filter(|entry| seen.insert(entry.clone()))
+        .collect()
+}
+
+/// Formats entries as archive file contents (one line each)
+pub fn format_archive(entries: &[ArchiveEntry]) -> String {
+    entries.iter().map(|entry| entry.to_line() + "\n").collect()
+}
+
+/// Appends the entries that aren't in `existing` yet; returns how many were added
+pub fn merge_entries(existing: &mut Vec<ArchiveEntry>, new: Vec<ArchiveEntry>) -> usize {
+    let mut seen: HashSet<ArchiveEntry> = existing.iter().cloned().collect();
+    let before = existing.len();
+    existing.extend(new.into_iter().filter(|entry| seen.insert(entry.clone())));
+    existing.len() - before
+}
+
+/// Removes the given entries; returns how many were removed
+pub fn remove_entries(existing: &mut Vec<ArchiveEntry>, remove: &[ArchiveEntry]) -> usize {
+    let remove: HashSet<&ArchiveEntry> = remove.iter().collect();
+    let before = existing.len();
+    existing.retain(|entry| !remove.contains(entry));
+    before - existing.len()
+}
+
+/// Reads an archive file; a missing file is an empty archive
+pub fn read_archive(path: &Path) -> Result<Vec<ArchiveEntry>, DownloadError> {
+    match fs::read_to_string(path) {
+        Ok(contents) => Ok(parse_archive(&contents)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Vec::new()),
+        Err(e) => Err(DownloadError::GenericError(format!(
+            "Failed to read download archive {}: {}",
+            path.display(),
+            e
+        ))),
+    }
+}
+
+/// Writes an archive file
+///
+/// The contents go to a temporary file first so a running yt-dlp never
+/// sees a half-written archive.
+pub fn write_archive(path: &Path, entries: &[ArchiveEntry]) -> Result<(), DownloadError> {
+    let write_error = |e: std::io::Error| {
+        DownloadError::GenericError(format!(
+            "Failed to write download archive {}: {}",
+            path.display(),
+            e
+        ))
+    };
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(write_error)?;
+    }
+    let temp = path.with_extension("tmp");
+    fs::write(&temp, format_archive(entries)).map_err(write_error)?;
+    fs::rename(&temp, path).map_err(|e| {
+        let _ = fs::remove_file(&temp);
+        write_error(e)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(extractor: &str, id: &str) -> ArchiveEntry {
+        ArchiveEntry {
+            extractor: extractor.to_string(),
+            id: id.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_parse_archive() {
+        let entries = parse_archive("youtube abc\n\ngarbage\nyoutube abc\nvimeo 123\n");
+        assert_eq!(entries, vec![entry("youtube", "abc"), entry("vimeo", "123")]);
+        assert_eq!(format_archive(&entries), "youtube abc\nvimeo 123\n");
+    }
+
+    #[test]
+    fn test_merge_and_remove_entries() {
+        let mut entries = vec![entry("youtube", "abc")];
+        let added = merge_entries(
+            &mut entries,
+            vec![entry("youtube", "abc"), entry("youtube", "def"), entry("youtube", "def")],
+        );
+        assert_eq!(added, 1);
+        assert_eq!(entries, vec![entry("youtube", "abc"), entry("youtube", "def")]);
+
+        let removed = remove_entries(&mut entries, &[entry("youtube", "abc"), entry("vimeo", "1")]);
+        assert_eq!(removed, 1);
+        assert_eq!(entries, vec![entry("youtube", "def")]);
+    }
+
+    #[test]
+    fn test_read_write_archive() {
+        let dir = std::env::temp_dir().join(format!("mediagrab-archive-{}", std::process::id()));
+        let path = dir.join("archive.txt");
+        assert!(read_archive(&path).unwrap().is_empty());
+
+        let entries = vec![entry("youtube", "abc"), entry("soundcloud", "artist/track")];
+        write_archive(&path, &entries).unwrap();
+        let read = read_archive(&path).unwrap();
+        let temp_left = path.with_extension("tmp").exists();
+
+        let _ = std::fs::remove_dir_all(&dir);
+        assert_eq!(read, entries);
+        assert!(!temp_left);
+    }
+}
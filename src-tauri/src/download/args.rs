@@ -3,52 +3,94 @@
 //! Builds command-line arguments for yt-dlp based on download configuration.
 //! Requirements: 2.3, 2.4, 2.5, 12.1, 12.2, 12.4, 13.1, 6.5
 
-use crate::models::DownloadConfig;
+use std::path::PathBuf;
+
+use crate::download::template::FilenameTemplate;
+use crate::utils::sanitize::{sanitize_fragment, SanitizeOptions};
+use crate::models::{DownloadConfig, OrganizeRule, OutputFormat, SubtitleOptions};
 
 /// Converts a user-friendly filename template to yt-dlp format
 /// 
-/// Supported placeholders:
-/// - {title} -> %(title)s
-/// - {uploader} -> %(uploader)s
-/// - {channel} -> %(channel)s
-/// - {date} -> %(upload_date)s
-/// - {quality} -> %(height)sp
-/// - {resolution} -> %(resolution)s
-/// - {duration} -> %(duration)s
-/// - {id} -> %(id)s
-/// - {playlist_index} -> %(playlist_index)s
-/// - {ext} -> %(ext)s (automatically added if not present)
+/// See `download::template` for the syntax (fallbacks, truncation, date
+/// formats, subfolders). Invalid templates fall back to the default one;
+/// `spawn_ytdlp` rejects them before a download starts.
 pub fn convert_template_to_ytdlp(template: &str) -> String {
-    let mut result = template.to_string();
-    
-    // Replace user-friendly placeholders with yt-dlp format
-    result = result.replace("{title}", "%(title)s");
-    result = result.replace("{uploader}", "%(uploader)s");
-    result = result.replace("{channel}", "%(channel)s");
-    result = result.replace("{date}", "%(upload_date)s");
-    result = result.replace("{quality}", "%(height)sp");
-    result = result.replace("{resolution}", "%(resolution)s");
-    result = result.replace("{duration}", "%(duration)s");
-    result = result.replace("{id}", "%(id)s");
-    result = result.replace("{playlist_index}", "%(playlist_index)s");
-    result = result.replace("{ext}", "%(ext)s");
-    
-    // Ensure extension is present
-    if !result.contains("%(ext)s") {
-        result.push_str(".%(ext)s");
+    match FilenameTemplate::parse(template) {
+        Ok(parsed) => parsed.to_ytdlp(),
+        Err(e) => {
+            tracing::warn!("Ignoring filename template \"{}\": {}", template, e);
+            "%(title)s.%(ext)s".to_string()
+        }
     }
-    
-    result
+}
+
+/// Build yt-dlp arguments for subtitle options
+pub fn build_subtitle_args(options: &SubtitleOptions) -> Vec<String> {
+    let mut args = Vec::new();
+
+    if !options.is_enabled() {
+        return args;
+    }
+
+    // Write subtitles to file
+    if options.download_subtitles {
+        args.push("--write-subs".to_string());
+    }
+
+    // Auto-generated captions (needed for both sidecar files and embedding)
+    if options.include_auto {
+        args.push("--write-auto-subs".to_string());
+    }
+
+    // Embed subtitles in video
+    if options.embed_subtitles {
+        args.push("--embed-subs".to_string());
+    }
+
+    // Language selection
+    if !options.languages.is_empty() {
+        args.push("--sub-langs".to_string());
+        args.push(options.languages.join(","));
+    } else {
+        args.push("--sub-langs".to_string());
+        args.push("all".to_string());
+    }
+
+    // Output format
+    if options.download_subtitles {
+        args.push("--sub-format".to_string());
+        match options.format.as_str() {
+            "srt" => args.push("srt/best".to_string()),
+            "vtt" => args.push("vtt/best".to_string()),
+            "ass" => args.push("ass/best".to_string()),
+            "best" => args.push("best".to_string()),
+            _ => args.push("srt/best".to_string()),
+        }
+
+        // Convert to requested format if needed ("best" keeps the original)
+        if options.format != "best" {
+            args.push("--convert-subs".to_string());
+            args.push(options.format.clone());
+        }
+    }
+
+    args
 }
 
 /// Default filename template
 pub const DEFAULT_FILENAME_TEMPLATE: &str = "{title}";
 
+/// Output template for files written by `--split-chapters` (passed with
+/// the `chapter:` output type)
+pub const CHAPTER_FILENAME_TEMPLATE: &str =
+    "%(title).100B [%(id)s]/%(section_number)03d - %(section_title).100B.%(ext)s";
+
 /// Builds yt-dlp command-line arguments from download configuration
 pub struct ArgumentBuilder {
     config: DownloadConfig,
     ffmpeg_location: Option<String>,
     proxy_url: Option<String>,
+    download_archive: Option<String>,
 }
 
 impl ArgumentBuilder {
@@ -58,6 +100,7 @@ impl ArgumentBuilder {
             config,
             ffmpeg_location: None,
             proxy_url: None,
+            download_archive: None,
         }
     }
 
@@ -72,16 +115,119 @@ impl ArgumentBuilder {
         self.ffmpeg_location = Some(path);
         self
     }
+
+    /// Sets the download archive file
+    pub fn with_download_archive(mut self, path: String) -> Self {
+        self.download_archive = Some(path);
+        self
+    }
     
+    /// Folder name (or yt-dlp template) for an organization rule
+    fn organize_component(&self, rule: OrganizeRule) -> String {
+        match rule {
+            OrganizeRule::Site => "%(extractor_key|Other)s".to_string(),
+            OrganizeRule::Uploader => "%(uploader,channel|Unknown)s".to_string(),
+            // Items queued from a playlist carry its title
+            OrganizeRule::Playlist => match self.config.playlist.as_ref().filter(|p| !p.title.is_empty()) {
+                Some(playlist) => sanitize_fragment(&playlist.title, &SanitizeOptions::default()).replace('%', "%%"),
+                // Empty for single videos; yt-dlp collapses the doubled separator
+                None => "%(playlist_title|)s".to_string(),
+            },
+            OrganizeRule::MediaType if self.is_audio_extraction() => "Audio".to_string(),
+            OrganizeRule::MediaType => "Video".to_string(),
+            OrganizeRule::Year => "%(upload_date>%Y|Unknown)s".to_string(),
+        }
+    }
+
+    /// Prefixes an output template with the organization folders
+    fn organize_template(&self, template: &str) -> String {
+        if self.config.organize_rules.is_empty() {
+            return template.to_string();
+        }
+
+        let mut parts: Vec<String> = self
+            .config
+            .organize_rules
+            .iter()
+            .map(|rule| self.organize_component(*rule))
+            .collect();
+        parts.push(template.to_string());
+        parts.join("/")
+    }
+
+    /// Deepest folder that is known before the download starts
+    ///
+    /// This is the output folder plus the leading organization rules that
+    /// don't depend on metadata (e.g. `MediaType`). Folders below it are
+    /// created by yt-dlp once the metadata is known.
+    pub fn organized_base_folder(&self) -> PathBuf {
+        let mut folder = PathBuf::from(&self.config.output_folder);
+        for rule in &self.config.organize_rules {
+            if !rule.is_static() {
+                break;
+            }
+            folder.push(self.organize_component(*rule));
+        }
+        folder
+    }
+
     /// Gets the filename template string for yt-dlp
     /// 
     /// Default template includes video ID to prevent filename collisions,
     /// especially for Instagram where titles are often identical or empty.
+    /// Clip downloads get a section suffix so several clips of the same video
+    /// don't overwrite each other.
     fn get_filename_template(&self) -> String {
-        match &self.config.filename_template {
-            Some(template) if !template.is_empty() => convert_template_to_ytdlp(template),
+        let template = match &self.config.filename_template {
+            Some(template) if !template.is_empty() => match (&self.config.playlist, FilenameTemplate::parse(template)) {
+                (Some(playlist), Ok(parsed)) => parsed.to_ytdlp_for_playlist(Some(playlist)),
+                _ => convert_template_to_ytdlp(template),
+            },
+            _ if self.has_sections() => {
+                "%(title).100B [%(id)s] (%(section_title,section_start)s).%(ext)s".to_string()
+            }
             _ => "%(title).100B [%(id)s].%(ext)s".to_string(), // Include ID to prevent collisions
+        };
+        self.organize_template(&template)
+    }
+
+    /// Subtitle options to apply, falling back to the legacy `embed_subtitles` flag
+    fn subtitle_options(&self) -> SubtitleOptions {
+        if self.config.subtitles.is_enabled() || !self.config.embed_subtitles {
+            return self.config.subtitles.clone();
+        }
+
+        SubtitleOptions {
+            embed_subtitles: true,
+            ..SubtitleOptions::default()
+        }
+    }
+
+    /// Whether only parts of the video (time ranges or chapters) are downloaded
+    fn has_sections(&self) -> bool {
+        !self.config.sections.is_empty() || !self.config.chapter_patterns.is_empty()
+    }
+
+    /// Builds `--download-sections` values for time ranges and chapter regexes
+    ///
+    /// Time ranges are prefixed with `*`; chapter patterns are passed as-is and
+    /// matched by yt-dlp against chapter titles.
+    fn build_section_args(&self) -> Vec<String> {
+        let mut args = Vec::new();
+
+        for range in &self.config.sections {
+            args.push("--download-sections".to_string());
+            args.push(range.to_ytdlp_section());
         }
+
+        for pattern in &self.config.chapter_patterns {
+            if !pattern.trim().is_empty() {
+                args.push("--download-sections".to_string());
+                args.push(pattern.trim().to_string());
+            }
+        }
+
+        args
     }
 
     /// Builds the format selector string based on format and quality settings
@@ -90,28 +236,60 @@ impl ArgumentBuilder {
     /// - Video formats use quality-specific selectors with fallback
     /// - Audio formats use appropriate extraction flags
     fn build_format_selector(&self) -> String {
-        match self.config.format.as_str() {
+        match self.config.format {
             // Audio formats - all use bestaudio selector
-            "audio-mp3" | "audio-aac" | "audio-opus" | "audio-flac" | "audio-wav" | "audio-best" => {
-                "bestaudio/best".to_string()
-            }
+            OutputFormat::Audio(_) => "bestaudio/best".to_string(),
             // Video formats - use quality-specific selector
-            "video-mp4" | "video-webm" | "video-mkv" | _ => {
-                match self.config.quality.as_str() {
-                    "1080p" => {
-                        "bestvideo[height<=1080]+bestaudio/best[height<=1080]/best".to_string()
-                    }
-                    "720p" => {
-                        "bestvideo[height<=720]+bestaudio/best[height<=720]/best".to_string()
-                    }
-                    "best" | _ => {
-                        "bestvideo+bestaudio/best".to_string()
-                    }
+            OutputFormat::Video(_) => {
+                let mut filter = String::new();
+                if let Some(height) = self.config.quality.max_height() {
+                    filter.push_str(&format!("[height<={}]", height));
+                }
+                if let Some(fps) = self.config.max_fps {
+                    filter.push_str(&format!("[fps<={}]", fps));
+                }
+
+                if filter.is_empty() {
+                    "bestvideo+bestaudio/best".to_string()
+                } else {
+                    format!("bestvideo{f}+bestaudio/best{f}/best", f = filter)
                 }
             }
         }
     }
 
+    /// Builds the yt-dlp format sort string (`-S`) for video formats
+    /// 
+    /// The selector filters out formats above the caps; sorting then decides
+    /// between the remaining ones (resolution, fps, HDR, codec preference).
+    /// Returns None when yt-dlp's default ordering should be used.
+    fn build_format_sort(&self) -> Option<String> {
+        if self.config.format.is_audio() {
+            return None;
+        }
+
+        let mut fields: Vec<String> = Vec::new();
+
+        if let Some(height) = self.config.quality.max_height() {
+            fields.push(format!("res:{}", height));
+        }
+        if let Some(fps) = self.config.max_fps {
+            fields.push(format!("fps:{}", fps));
+        }
+        if !self.config.allow_hdr {
+            fields.push("hdr:sdr".to_string());
+        }
+        if let Some(codec) = self.config.video_codec {
+            fields.push(format!("vcodec:{}", codec.sort_value()));
+        }
+
+        if fields.is_empty() {
+            None
+        } else {
+            Some(fields.join(","))
+        }
+    }
+
     /// Builds the complete argument list for yt-dlp
     pub fn build(&self) -> Vec<String> {
         let mut args: Vec<String> = Vec::new();
@@ -123,47 +301,37 @@ impl ArgumentBuilder {
         args.push("-f".to_string());
         args.push(self.build_format_selector());
 
+        // Format sorting for resolution, fps, HDR and codec preferences
+        if let Some(sort) = self.build_format_sort() {
+            args.push("-S".to_string());
+            args.push(sort);
+        }
+
         // Output template (Requirement 6.5)
         args.push("-o".to_string());
         args.push(self.get_filename_template());
 
+        // Clip sections (time ranges / chapter regexes)
+        args.extend(self.build_section_args());
+
+        // One file per chapter, collected in a folder named after the video
+        if self.config.split_chapters {
+            args.push("--split-chapters".to_string());
+            args.push("-o".to_string());
+            args.push(format!("chapter:{}", self.organize_template(CHAPTER_FILENAME_TEMPLATE)));
+        }
+
         // Audio extraction and format conversion
-        match self.config.format.as_str() {
-            "audio-mp3" => {
-                args.push("-x".to_string());
-                args.push("--audio-format".to_string());
-                args.push("mp3".to_string());
-                args.push("--audio-quality".to_string());
-                args.push("0".to_string()); // Best quality
-            }
-            "audio-aac" => {
-                args.push("-x".to_string());
+        if let OutputFormat::Audio(codec) = self.config.format {
+            args.push("-x".to_string());
+            if let Some(audio_format) = codec.ytdlp_audio_format() {
                 args.push("--audio-format".to_string());
-                args.push("m4a".to_string()); // AAC in M4A container
-                args.push("--audio-quality".to_string());
-                args.push("0".to_string());
+                args.push(audio_format.to_string());
             }
-            "audio-opus" => {
-                args.push("-x".to_string());
-                args.push("--audio-format".to_string());
-                args.push("opus".to_string());
+            if codec.is_lossy() {
                 args.push("--audio-quality".to_string());
-                args.push("0".to_string());
-            }
-            "audio-flac" => {
-                args.push("-x".to_string());
-                args.push("--audio-format".to_string());
-                args.push("flac".to_string());
-            }
-            "audio-wav" => {
-                args.push("-x".to_string());
-                args.push("--audio-format".to_string());
-                args.push("wav".to_string());
-            }
-            "audio-best" => {
-                args.push("-x".to_string()); // Extract audio, keep original format
+                args.push("0".to_string()); // Best quality
             }
-            _ => {} // Video formats don't need audio extraction
         }
 
         // Embed thumbnail (Requirement 12.1)
@@ -172,13 +340,26 @@ impl ArgumentBuilder {
         // Embed metadata (Requirement 12.2)
         args.push("--add-metadata".to_string());
 
-        // Embed subtitles if enabled (Requirement 12.4)
-        if self.config.embed_subtitles {
-            args.push("--embed-subs".to_string());
-            args.push("--sub-langs".to_string());
-            args.push("all".to_string());
+        // SponsorBlock: cut segments or mark them as chapters
+        if let Some(remove) = self.config.sponsorblock.remove_arg() {
+            args.push("--sponsorblock-remove".to_string());
+            args.push(remove);
+        }
+        if let Some(mark) = self.config.sponsorblock.mark_arg() {
+            args.push("--sponsorblock-mark".to_string());
+            args.push(mark);
         }
 
+        // Subtitles: sidecar files and/or embedding (Requirement 12.4)
+        args.extend(build_subtitle_args(&self.subtitle_options()));
+
+        // Livestreams: start point, waiting for scheduled streams, duration limit
+        args.extend(self.config.live.to_args());
+
+        // Playlist entries outside the filter are skipped, now that the full
+        // metadata (e.g. the upload date) is known
+        args.extend(self.config.playlist_filter.to_args());
+
         // Cookies: prefer custom file over browser cookies (Requirement 13.1)
         // Custom cookies.txt file takes priority
         if let Some(ref cookies_file) = self.config.cookies_file_path {
@@ -200,6 +381,12 @@ impl ArgumentBuilder {
             args.push(ffmpeg_path.clone());
         }
 
+        // Record finished downloads and skip videos that are already recorded
+        if let Some(ref archive) = self.download_archive {
+            args.push("--download-archive".to_string());
+            args.push(archive.clone());
+        }
+
         // Proxy if specified
         if let Some(ref proxy) = self.proxy_url {
             if !proxy.is_empty() {
@@ -209,20 +396,9 @@ impl ArgumentBuilder {
         }
 
         // Merge output format for video formats
-        match self.config.format.as_str() {
-            "video-mp4" => {
-                args.push("--merge-output-format".to_string());
-                args.push("mp4".to_string());
-            }
-            "video-webm" => {
-                args.push("--merge-output-format".to_string());
-                args.push("webm".to_string());
-            }
-            "video-mkv" => {
-                args.push("--merge-output-format".to_string());
-                args.push("mkv".to_string());
-            }
-            _ => {} // Audio formats don't need merge output format
+        if let OutputFormat::Video(container) = self.config.format {
+            args.push("--merge-output-format".to_string());
+            args.push(container.extension().to_string());
         }
 
         // Use default yt-dlp progress output format instead of custom template
@@ -277,12 +453,14 @@ impl ArgumentBuilder {
         self.build_format_selector()
     }
 
+    /// Returns the format sort string, if any (useful for testing)
+    pub fn get_format_sort(&self) -> Option<String> {
+        self.build_format_sort()
+    }
+
     /// Checks if audio extraction is enabled based on format
     pub fn is_audio_extraction(&self) -> bool {
-        matches!(
-            self.config.format.as_str(),
-            "audio-mp3" | "audio-best" | "audio-flac" | "audio-wav" | "audio-aac" | "audio-opus"
-        )
+        self.config.format.is_audio()
     }
 }
 
@@ -0,0 +1,363 @@
+//! Batch URL import
+//!
+//! Pulls every http(s) link out of pasted text or a .txt/.csv file. A text
+//! line can override the format, quality and output folder of its links
+//! with `|`-separated options:
+//!
+//! ```text
+//! https://youtu.be/dQw4w9WgXcQ | format=mp3 | folder=D:\Music
+//! ```
+//!
+//! CSV files have a header row with a `url` column and optional `format`,
+//! `quality` and `folder` columns; without a header, those four columns are
+//! read in that order.
+
+use serde::{Deserialize, Serialize};
+
+use super::{is_ignored_param, normalize_url};
+use crate::models::{DownloadConfig, OutputFormat, Quality};
+
+/// Characters that end a link pasted into prose
+const TRAILING_PUNCTUATION: &[char] = &['.', ',', ';', ':', '!', '?', '"', '\'', '>', ']', '}', '*'];
+
+/// Why a batch option couldn't be used
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+enum OptionError {
+    /// Not an option at all (e.g. an extra CSV column)
+    #[error("unknown option \"{0}\"")]
+    Unknown(String),
+    /// A known option with a bad value
+    #[error("{0}")]
+    InvalidValue(String),
+}
+
+/// Settings a batch line sets for its links
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchOverrides {
+    #[serde(default)]
+    pub format: Option<OutputFormat>,
+    #[serde(default)]
+    pub quality: Option<Quality>,
+    #[serde(default)]
+    pub output_folder: Option<String>,
+}
+
+impl BatchOverrides {
+    /// `config` with the URL and these overrides applied
+    pub fn apply(&self, config: &DownloadConfig, url: &str) -> DownloadConfig {
+        let mut config = DownloadConfig {
+            url: url.to_string(),
+            ..config.clone()
+        };
+        if let Some(format) = self.format {
+            config.format = format;
+        }
+        if let Some(quality) = self.quality {
+            config.quality = quality;
+        }
+        if let Some(folder) = &self.output_folder {
+            config.output_folder = folder.clone();
+        }
+        config
+    }
+
+    /// Sets one `key=value` option
+    fn set(&mut self, key: &str, value: &str) -> Result<(), OptionError> {
+        let value = value.trim();
+        if value.is_empty() {
+            return Ok(());
+        }
+        match key.trim().to_lowercase().as_str() {
+            "format" | "f" => self.format = Some(parse_format(value).map_err(OptionError::InvalidValue)?),
+            "quality" | "q" => {
+                self.quality = Some(
+                    value
+                        .parse()
+                        .map_err(|_| OptionError::InvalidValue(format!("unknown quality \"{}\"", value)))?,
+                )
+            }
+            "folder" | "output" | "outputfolder" | "output_folder" | "dir" => {
+                self.output_folder = Some(value.to_string())
+            }
+            other => return Err(OptionError::Unknown(other.to_string())),
+        }
+        Ok(())
+    }
+}
+
+/// A link to queue, with the line it came from (1-based)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchEntry {
+    pub line: usize,
+    pub url: String,
+    #[serde(default)]
+    pub overrides: BatchOverrides,
+}
+
+/// A line that couldn't be used, and why
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchIssue {
+    pub line: usize,
+    pub text: String,
+    pub error: String,
+}
+
+/// Links found in a batch, for previewing before they're queued
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BatchParse {
+    pub entries: Vec<BatchEntry>,
+    pub issues: Vec<BatchIssue>,
+}
+
+impl BatchParse {
+    /// Adds a link unless an earlier line already has it
+    fn push(&mut self, line: usize, url: String, overrides: BatchOverrides) {
+        let key = normalize_url(&url);
+        if let Some(first) = self.entries.iter().find(|entry| normalize_url(&entry.url) == key) {
+            let error = format!("same link as line {}", first.line);
+            self.issue(line, &url, error);
+            return;
+        }
+        self.entries.push(BatchEntry { line, url, overrides });
+    }
+
+    fn issue(&mut self, line: usize, text: &str, error: String) {
+        self.issues.push(BatchIssue {
+            line,
+            text: text.to_string(),
+            error,
+        });
+    }
+}
+
+/// Parses a batch of pasted text, or the contents of a .csv file
+pub fn parse_batch(text: &str, csv: bool) -> BatchParse {
+    if csv {
+        parse_csv(text)
+    } else {
+        parse_text(text)
+    }
+}
+
+fn parse_text(text: &str) -> BatchParse {
+    let mut batch = BatchParse::default();
+    for (i, line) in text.lines().enumerate() {
+        let line_number = i + 1;
+        let urls = extract_urls(line);
+        if urls.is_empty() {
+            continue;
+        }
+
+        let mut overrides = BatchOverrides::default();
+        let mut error = None;
+        for option in line.split('|').skip(1) {
+            if let Some((key, value)) = option.split_once('=') {
+                if let Err(e) = overrides.set(key, value) {
+                    error.get_or_insert(e.to_string());
+                }
+            }
+        }
+        if let Some(error) = error {
+            batch.issue(line_number, line.trim(), error);
+            continue;
+        }
+
+        for url in urls {
+            batch.push(line_number, url, overrides.clone());
+        }
+    }
+    batch
+}
+
+fn parse_csv(text: &str) -> BatchParse {
+    let mut batch = BatchParse::default();
+    // Spreadsheets in many locales save with semicolons
+    let delimiter = match text.lines().next() {
+        Some(first) if first.matches(';').count() > first.matches(',').count() => ';',
+        _ => ',',
+    };
+
+    let mut columns = ["url", "format", "quality", "folder"].map(String::from).to_vec();
+    for (i, line) in text.lines().enumerate() {
+        let line_number = i + 1;
+        let fields = split_csv_line(line, delimiter);
+        if fields.iter().all(|field| field.trim().is_empty()) {
+            continue;
+        }
+
+        // A first row naming a url column is the header
+        if i == 0 && fields.iter().any(|field| field.trim().eq_ignore_ascii_case("url")) {
+            columns = fields.iter().map(|field| field.trim().to_lowercase()).collect();
+            continue;
+        }
+
+        let mut urls = Vec::new();
+        let mut overrides = BatchOverrides::default();
+        let mut error = None;
+        for (column, field) in columns.iter().zip(&fields) {
+            if column == "url" {
+                urls = extract_urls(field);
+            } else {
+                match overrides.set(column, field) {
+                    // Extra columns (titles, notes) are fine; bad values aren't
+                    Ok(()) | Err(OptionError::Unknown(_)) => {}
+                    Err(e) => {
+                        error.get_or_insert(e.to_string());
+                    }
+                }
+            }
+        }
+
+        match (urls.is_empty(), error) {
+            (true, _) => batch.issue(line_number, line.trim(), "no link found".to_string()),
+            (false, Some(error)) => batch.issue(line_number, line.trim(), error),
+            (false, None) => {
+                for url in urls {
+                    batch.push(line_number, url, overrides.clone());
+                }
+            }
+        }
+    }
+    batch
+}
+
+/// Splits one CSV line, honouring double quotes
+fn split_csv_line(line: &str, delimiter: char) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = line.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' => quoted = !quoted,
+            c if c == delimiter && !quoted => fields.push(std::mem::take(&mut field)),
+            c => field.push(c),
+        }
+    }
+    fields.push(field);
+    fields
+}
+
+/// Every http(s) link in a piece of text, cleaned up for downloading
+pub fn extract_urls(text: &str) -> Vec<String> {
+    text.split(|c: char| c.is_whitespace() || c == '|' || c == '<' || c == '"')
+        .filter_map(|word| {
+            let start = word.find("https://").or_else(|| word.find("http://"))?;
+            clean_url(&word[start..])
+        })
+        .collect()
+}
+
+/// Trims punctuation a link picked up from the surrounding text and drops
+/// tracking parameters and the fragment
+fn clean_url(raw: &str) -> Option<String> {
+    let mut url = raw.trim_end_matches(TRAILING_PUNCTUATION);
+    // Keep "(...)" that belongs to the link, drop a closing one that doesn't
+    while url.ends_with(')') && url.matches(')').count() > url.matches('(').count() {
+        url = url[..url.len() - 1].trim_end_matches(TRAILING_PUNCTUATION);
+    }
+
+    let url = url.split('#').next().unwrap_or(url);
+    let (base, query) = url.split_once('?').unwrap_or((url, ""));
+    let host = base.split("://").nth(1)?.split('/').next()?;
+    if !host.contains('.') || host.starts_with('.') || host.ends_with('.') {
+        return None;
+    }
+
+    let params: Vec<&str> = query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .filter(|pair| !is_ignored_param(pair.split('=').next().unwrap_or(pair)))
+        .collect();
+
+    Some(if params.is_empty() {
+        base.to_string()
+    } else {
+        format!("{}?{}", base, params.join("&"))
+    })
+}
+
+/// Parses a format, also taking just the extension (`mp3`, `mkv`)
+pub fn parse_format(value: &str) -> Result<OutputFormat, String> {
+    let value = value.trim().to_lowercase();
+    value
+        .parse()
+        .or_else(|_| format!("audio-{}", value).parse())
+        .or_else(|_| format!("video-{}", value).parse())
+        .map_err(|_| format!("unknown format \"{}\"", value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{AudioCodec, VideoContainer};
+
+    #[test]
+    fn test_extract_urls_from_prose() {
+        let text = "Watch this (https://www.youtube.com/watch?v=dQw4w9WgXcQ&utm_source=x), \
+                    and https://en.wikipedia.org/wiki/Foo_(bar). Also <https://vimeo.com/123#t=10>";
+        assert_eq!(
+            extract_urls(text),
+            vec![
+                "https://www.youtube.com/watch?v=dQw4w9WgXcQ",
+                "https://en.wikipedia.org/wiki/Foo_(bar)",
+                "https://vimeo.com/123",
+            ]
+        );
+        assert_eq!(
+            extract_urls("https://youtu.be/dQw4w9WgXcQ?si=abc&pp=xyz&list=PL1"),
+            vec!["https://youtu.be/dQw4w9WgXcQ?list=PL1"]
+        );
+        assert!(extract_urls("no links, just http://localhost and text").is_empty());
+    }
+
+    #[test]
+    fn test_parse_text_with_overrides() {
+        let text = "https://youtu.be/aaaaaaaaaaa | format=mp3 | folder=D:\\Music\n\
+                    \n\
+                    random note\n\
+                    https://youtu.be/bbbbbbbbbbb | quality=720p\n\
+                    https://youtu.be/ccccccccccc | format=flac-hd\n\
+                    again: https://youtu.be/aaaaaaaaaaa";
+        let batch = parse_batch(text, false);
+
+        assert_eq!(batch.entries.len(), 2);
+        assert_eq!(batch.entries[0].line, 1);
+        assert_eq!(batch.entries[0].overrides.format, Some(OutputFormat::Audio(AudioCodec::Mp3)));
+        assert_eq!(batch.entries[0].overrides.output_folder.as_deref(), Some("D:\\Music"));
+        assert_eq!(batch.entries[1].overrides.quality, Some("720p".parse().unwrap()));
+
+        assert_eq!(batch.issues.len(), 2);
+        assert_eq!(batch.issues[0].line, 5);
+        assert!(batch.issues[1].error.contains("line 1"));
+    }
+
+    #[test]
+    fn test_parse_csv() {
+        let text = "title;URL;Format;Folder\n\
+                    \"Song; live\";https://youtu.be/aaaaaaaaaaa;mkv;\"C:\\Videos\"\n\
+                    Missing;;;\n\
+                    Bad;https://youtu.be/bbbbbbbbbbb;mp5;\n";
+        let batch = parse_batch(text, true);
+
+        assert_eq!(batch.entries.len(), 1);
+        let overrides = &batch.entries[0].overrides;
+        assert_eq!(overrides.format, Some(OutputFormat::Video(VideoContainer::Mkv)));
+        assert_eq!(overrides.output_folder.as_deref(), Some("C:\\Videos"));
+        assert_eq!(batch.issues.len(), 2);
+        assert_eq!(batch.issues[0].line, 3);
+        assert!(batch.issues[1].error.contains("unknown format"));
+
+        // No header: url, format, quality, folder
+        let batch = parse_batch("https://vimeo.com/1,audio-opus,,\n", true);
+        assert_eq!(batch.entries[0].overrides.format, Some(OutputFormat::Audio(AudioCodec::Opus)));
+    }
+}
@@ -0,0 +1,291 @@
+//! Duplicate detection for queued downloads
+//!
+//! Two URLs point at the same media if they are equal after normalization
+//! (scheme, `www.`/`m.` host prefixes, fragments, trailing slashes and
+//! tracking parameters ignored) or if they carry the same video id on a
+//! known site, so `youtu.be/ID` matches `youtube.com/watch?v=ID&t=30`.
+
+use std::collections::HashSet;
+
+use serde::Serialize;
+
+use crate::download::queue::QueueItemId;
+
+/// Query parameters that never change which media a URL points at
+const IGNORED_PARAMS: &[&str] = &[
+    "si", "feature", "pp", "ab_channel", "t", "start", "fbclid", "gclid", "igshid", "ref", "ref_src",
+];
+
+/// Whether a query parameter is tracking or position noise rather than part
+/// of what the URL points at
+pub fn is_ignored_param(key: &str) -> bool {
+    key.starts_with("utm_") || IGNORED_PARAMS.contains(&key)
+}
+
+/// How two URLs were found to match
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DuplicateMatch {
+    /// Same URL after normalization
+    Url,
+    /// Different URLs for the same video id
+    VideoId,
+}
+
+/// Where the existing download was found
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum DuplicateSource {
+    /// A pending or active queue item
+    Queue,
+    /// A completed download in history
+    History,
+}
+
+/// An existing download of the media that was about to be queued
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Duplicate {
+    pub source: DuplicateSource,
+    pub matched_by: DuplicateMatch,
+    /// URL of the existing entry
+    pub url: String,
+    pub title: Option<String>,
+    /// Set when `source` is `Queue`
+    pub queue_item_id: Option<QueueItemId>,
+    /// Set when `source` is `History`
+    pub history_id: Option<String>,
+    /// Downloaded file, only if it's still on disk
+    pub file_path: Option<String>,
+    /// Unix timestamp of the earlier download (history only)
+    pub downloaded_at: Option<i64>,
+}
+
+/// Identity of a URL for duplicate checks
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MediaKey {
+    /// Normalized URL
+    pub url: String,
+    /// Site-qualified video id (e.g. "youtube:dQw4w9WgXcQ"), for known sites
+    pub video_id: Option<String>,
+}
+
+impl MediaKey {
+    pub fn from_url(url: &str) -> Self {
+        Self {
+            url: normalize_url(url),
+            video_id: extract_video_id(url),
+        }
+    }
+
+    /// Checks whether `other_url` points at the same media
+    pub fn match_url(&self, other_url: &str) -> Option<DuplicateMatch> {
+        let other = MediaKey::from_url(other_url);
+        if self.url == other.url {
+            Some(DuplicateMatch::Url)
+        } else if self.video_id.is_some() && self.video_id == other.video_id {
+            Some(DuplicateMatch::VideoId)
+        } else {
+            None
+        }
+    }
+}
+
+/// A set of media for checking many URLs at once
+#[derive(Debug, Default)]
+pub struct KnownMedia {
+    urls: HashSet<String>,
+    video_ids: HashSet<String>,
+}
+
+impl KnownMedia {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert(&mut self, url: &str) {
+        let key = MediaKey::from_url(url);
+        self.urls.insert(key.url);
+        if let Some(video_id) = key.video_id {
+            self.video_ids.insert(video_id);
+        }
+    }
+
+    /// Whether `url` matches a known URL or video id
+    pub fn contains(&self, url: &str) -> bool {
+        let key = MediaKey::from_url(url);
+        self.urls.contains(&key.url) || key.video_id.is_some_and(|id| self.video_ids.contains(&id))
+    }
+}
+
+impl<'a> FromIterator<&'a str> for KnownMedia {
+    fn from_iter<I: IntoIterator<Item = &'a str>>(urls: I) -> Self {
+        let mut known = Self::new();
+        for url in urls {
+            known.insert(url);
+        }
+        known
+    }
+}
+
+/// Splits a URL into lowercase host, path and query (scheme and fragment dropped)
+fn split_url(url: &str) -> (String, &str, &str) {
+    let url = url.trim();
+    let url = url.split('#').next().unwrap_or("");
+    let rest = url.split_once("://").map(|(_, rest)| rest).unwrap_or(url);
+
+    let (authority, path_and_query) = match rest.find(['/', '?']) {
+        Some(pos) => rest.split_at(pos),
+        None => (rest, ""),
+    };
+    let (path, query) = path_and_query.split_once('?').unwrap_or((path_and_query, ""));
+
+    // Drop credentials and port
+    let host = authority.rsplit('@').next().unwrap_or(authority);
+    let host = host.split(':').next().unwrap_or(host).to_lowercase();
+    let host = host
+        .strip_prefix("www.")
+        .or_else(|| host.strip_prefix("m."))
+        .unwrap_or(&host)
+        .to_string();
+
+    (host, path, query)
+}
+
+/// Query parameter value by name
+fn query_param<'a>(query: &'a str, name: &str) -> Option<&'a str> {
+    query
+        .split('&')
+        .filter_map(|pair| pair.split_once('='))
+        .find(|(key, _)| *key == name)
+        .map(|(_, value)| value)
+}
+
+/// Normalizes a URL for comparison
+///
+/// Lowercases the host, drops the scheme, `www.`/`m.`, the fragment, a
+/// trailing slash and tracking parameters, and sorts the remaining query.
+pub fn normalize_url(url: &str) -> String {
+    let (host, path, query) = split_url(url);
+    let path = path.trim_end_matches('/');
+
+    let mut params: Vec<&str> = query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .filter(|pair| {
+            let key = pair.split('=').next().unwrap_or(pair);
+            !is_ignored_param(key)
+        })
+        .collect();
+    params.sort_unstable();
+
+    if params.is_empty() {
+        format!("{}{}", host, path)
+    } else {
+        format!("{}{}?{}", host, path, params.join("&"))
+    }
+}
+
+/// Whether a string looks like a YouTube video id
+fn is_youtube_id(id: &str) -> bool {
+    id.len() == 11 && id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+/// Extracts a site-qualified video id from URLs of well-known sites
+///
+/// The site prefix matches yt-dlp's lowercase extractor key, as used in the
+/// download archive.
+pub fn extract_video_id(url: &str) -> Option<String> {
+    let (host, path, query) = split_url(url);
+    let mut segments = path.split('/').filter(|s| !s.is_empty());
+
+    match host.as_str() {
+        "youtu.be" => segments
+            .next()
+            .filter(|id| is_youtube_id(id))
+            .map(|id| format!("youtube:{}", id)),
+        "youtube.com" | "music.youtube.com" | "youtube-nocookie.com" => {
+            let id = match segments.next()? {
+                "watch" => query_param(query, "v")?,
+                "shorts" | "embed" | "live" | "v" => segments.next()?,
+                _ => return None,
+            };
+            is_youtube_id(id).then(|| format!("youtube:{}", id))
+        }
+        "vimeo.com" | "player.vimeo.com" => segments
+            .find(|s| s.chars().all(|c| c.is_ascii_digit()))
+            .map(|id| format!("vimeo:{}", id)),
+        "dailymotion.com" => match segments.next()? {
+            // /video/x8abc12_some-title
+            "video" => segments
+                .next()
+                .and_then(|s| s.split('_').next())
+                .filter(|id| !id.is_empty())
+                .map(|id| format!("dailymotion:{}", id)),
+            _ => None,
+        },
+        "dai.ly" => segments.next().map(|id| format!("dailymotion:{}", id)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_url() {
+        assert_eq!(
+            normalize_url("HTTPS://WWW.YouTube.com/watch?v=dQw4w9WgXcQ&feature=share&utm_source=x#t=10"),
+            "youtube.com/watch?v=dQw4w9WgXcQ"
+        );
+        assert_eq!(normalize_url("http://example.com/video/"), "example.com/video");
+        assert_eq!(
+            normalize_url("https://example.com/v?b=2&a=1"),
+            normalize_url("https://example.com/v?a=1&b=2")
+        );
+        assert_eq!(normalize_url("https://m.example.com:443/clip"), "example.com/clip");
+    }
+
+    #[test]
+    fn test_extract_video_id() {
+        let id = Some("youtube:dQw4w9WgXcQ".to_string());
+        assert_eq!(extract_video_id("https://youtu.be/dQw4w9WgXcQ?si=abc"), id);
+        assert_eq!(extract_video_id("https://www.youtube.com/watch?v=dQw4w9WgXcQ&list=PL1"), id);
+        assert_eq!(extract_video_id("https://youtube.com/shorts/dQw4w9WgXcQ"), id);
+        assert_eq!(extract_video_id("https://music.youtube.com/watch?v=dQw4w9WgXcQ"), id);
+        assert_eq!(extract_video_id("https://www.youtube.com/playlist?list=PL123"), None);
+        assert_eq!(extract_video_id("https://vimeo.com/76979871"), Some("vimeo:76979871".to_string()));
+        assert_eq!(
+            extract_video_id("https://www.dailymotion.com/video/x8abc12_some-title"),
+            Some("dailymotion:x8abc12".to_string())
+        );
+        assert_eq!(extract_video_id("https://example.com/watch?v=dQw4w9WgXcQ"), None);
+    }
+
+    #[test]
+    fn test_match_url() {
+        let key = MediaKey::from_url("https://youtu.be/dQw4w9WgXcQ");
+        assert_eq!(key.match_url("https://youtu.be/dQw4w9WgXcQ/"), Some(DuplicateMatch::Url));
+        assert_eq!(
+            key.match_url("https://www.youtube.com/watch?v=dQw4w9WgXcQ&t=42"),
+            Some(DuplicateMatch::VideoId)
+        );
+        assert_eq!(key.match_url("https://youtu.be/aaaaaaaaaaa"), None);
+
+        // Unknown sites only match by URL
+        let key = MediaKey::from_url("https://example.com/a");
+        assert_eq!(key.match_url("https://example.com/b"), None);
+    }
+
+    #[test]
+    fn test_known_media() {
+        let known: KnownMedia = ["https://youtu.be/dQw4w9WgXcQ", "https://example.com/a/"]
+            .into_iter()
+            .collect();
+        assert!(known.contains("https://www.youtube.com/watch?v=dQw4w9WgXcQ"));
+        assert!(known.contains("http://example.com/a"));
+        assert!(!known.contains("https://youtu.be/aaaaaaaaaaa"));
+        assert!(!known.contains("https://example.com/b"));
+    }
+}
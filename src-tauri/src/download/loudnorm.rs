@@ -0,0 +1,370 @@
+//! Two-pass EBU R128 loudness normalization with ffmpeg
+//!
+//! Pass one runs `loudnorm` with `print_format=json` against a null output
+//! and reads the measurement from stderr. Pass two feeds the measured values
+//! back (linear mode) and re-encodes the audio in its original codec. The
+//! normalized file replaces the download under the same name.
+
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+
+use crate::models::{
+    is_valid_target_lufs, DownloadError, LoudnessMeasurement, TARGET_LRA, TARGET_TRUE_PEAK,
+};
+
+/// Sample rate of normalized lossy files; loudnorm upsamples to 192 kHz
+/// internally, which lossy encoders don't take
+const LOSSY_SAMPLE_RATE: u32 = 48000;
+
+/// Audio stream of the downloaded file, as listed by ffmpeg
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SourceAudio {
+    /// Decoder name, e.g. "flac" or "pcm_s24le"
+    pub codec: String,
+    pub sample_rate: Option<u32>,
+    /// ffmpeg sample format, e.g. "s16" or "s32"
+    pub sample_fmt: Option<String>,
+}
+
+/// Reads the first audio stream from ffmpeg's input listing
+///
+/// e.g. `Stream #0:0: Audio: flac, 96000 Hz, stereo, s32 (24 bit)`
+pub fn parse_source_audio(stderr: &str) -> Option<SourceAudio> {
+    let line = stderr.lines().find(|l| l.trim_start().starts_with("Stream #") && l.contains("Audio: "))?;
+    let mut parts = line[line.find("Audio: ")? + "Audio: ".len()..].split(", ");
+    let codec = parts.next()?.split_whitespace().next()?.to_string();
+
+    let mut source = SourceAudio { codec, ..SourceAudio::default() };
+    for part in parts {
+        let first = part.split_whitespace().next().unwrap_or("");
+        if let Some(rate) = part.strip_suffix(" Hz") {
+            source.sample_rate = rate.trim().parse().ok();
+        } else if matches!(first.trim_end_matches('p'), "u8" | "s16" | "s32" | "s64" | "flt" | "dbl") {
+            source.sample_fmt = Some(first.to_string());
+        }
+    }
+    Some(source)
+}
+
+/// Encoder options for the file's audio codec, chosen by extension
+///
+/// Lossless files keep the source's sample rate and bit depth; lossy ones
+/// are written at 48 kHz.
+fn audio_codec_args(extension: &str, source: &SourceAudio) -> Option<Vec<String>> {
+    let lossy: &[&str] = match extension {
+        "mp3" => &["-c:a", "libmp3lame", "-q:a", "0"],
+        "m4a" | "aac" => &["-c:a", "aac", "-b:a", "256k"],
+        "opus" | "webm" => &["-c:a", "libopus", "-b:a", "192k"],
+        "ogg" => &["-c:a", "libvorbis", "-q:a", "6"],
+        _ => &[],
+    };
+    // Without a listed rate the lossless path falls back to 48 kHz too
+    let sample_rate = if lossy.is_empty() {
+        source.sample_rate.unwrap_or(LOSSY_SAMPLE_RATE)
+    } else {
+        LOSSY_SAMPLE_RATE
+    };
+    let high_depth = source
+        .sample_fmt
+        .as_deref()
+        .is_some_and(|fmt| !fmt.starts_with("s16") && !fmt.starts_with("u8"));
+
+    let mut args: Vec<String> = match extension {
+        "flac" => vec![
+            "-c:a".into(),
+            "flac".into(),
+            "-sample_fmt".into(),
+            if high_depth { "s32" } else { "s16" }.into(),
+        ],
+        // The pcm codec name carries the bit depth
+        "wav" if source.codec.starts_with("pcm_") => vec!["-c:a".into(), source.codec.clone()],
+        "wav" => vec!["-c:a".into(), "pcm_s16le".into()],
+        _ if lossy.is_empty() => return None,
+        _ => lossy.iter().map(|s| s.to_string()).collect(),
+    };
+    args.extend(["-ar".to_string(), sample_rate.to_string()]);
+    Some(args)
+}
+
+/// `loudnorm` filter options shared by both passes
+fn loudnorm_filter(target_lufs: f64) -> String {
+    format!("loudnorm=I={}:TP={}:LRA={}", target_lufs, TARGET_TRUE_PEAK, TARGET_LRA)
+}
+
+/// Arguments for the measuring pass
+pub fn build_measure_args(input: &str, target_lufs: f64) -> Vec<String> {
+    vec![
+        "-hide_banner".to_string(),
+        "-nostdin".to_string(),
+        "-i".to_string(),
+        input.to_string(),
+        "-map".to_string(),
+        "0:a:0".to_string(),
+        "-af".to_string(),
+        format!("{}:print_format=json", loudnorm_filter(target_lufs)),
+        "-f".to_string(),
+        "null".to_string(),
+        "-".to_string(),
+    ]
+}
+
+/// Arguments for the normalizing pass
+///
+/// Returns None if the file's audio codec can't be re-encoded.
+pub fn build_normalize_args(
+    input: &str,
+    output: &str,
+    measurement: &LoudnessMeasurement,
+    source: &SourceAudio,
+) -> Option<Vec<String>> {
+    let extension = Path::new(input)
+        .extension()
+        .map(|e| e.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+    let codec_args = audio_codec_args(&extension, source)?;
+
+    let filter = format!(
+        "{}:measured_I={}:measured_TP={}:measured_LRA={}:measured_thresh={}:offset={}:linear=true:print_format=json",
+        loudnorm_filter(measurement.target_i),
+        measurement.input_i,
+        measurement.input_tp,
+        measurement.input_lra,
+        measurement.input_thresh,
+        measurement.target_offset,
+    );
+
+    let mut args: Vec<String> = [
+        "-hide_banner", "-nostdin", "-y", "-i", input,
+        // Keep cover art and tags
+        "-map", "0", "-map_metadata", "0", "-c:v", "copy",
+        "-af", &filter,
+    ]
+    .iter()
+    .map(|s| s.to_string())
+    .collect();
+    args.extend(codec_args);
+    args.push(output.to_string());
+    Some(args)
+}
+
+/// Reads the JSON block loudnorm prints at the end of stderr
+///
+/// ffmpeg prints numbers as strings (`"input_i" : "-27.61"`); silent input
+/// gives "-inf", which is treated as unmeasurable.
+pub fn parse_loudnorm_output(stderr: &str, target_lufs: f64) -> Option<LoudnessMeasurement> {
+    let start = stderr.rfind('{')?;
+    let end = start + stderr[start..].find('}')?;
+    let json: serde_json::Value = serde_json::from_str(&stderr[start..=end]).ok()?;
+
+    let field = |name: &str| -> Option<f64> {
+        let value = json.get(name)?;
+        let number = match value.as_str() {
+            Some(s) => s.trim().parse::<f64>().ok()?,
+            None => value.as_f64()?,
+        };
+        number.is_finite().then_some(number)
+    };
+
+    Some(LoudnessMeasurement {
+        input_i: field("input_i")?,
+        input_tp: field("input_tp")?,
+        input_lra: field("input_lra")?,
+        input_thresh: field("input_thresh")?,
+        target_offset: field("target_offset")?,
+        target_i: target_lufs,
+        output_i: field("output_i"),
+    })
+}
+
+/// Temporary path for the normalized file, next to the download
+fn normalized_temp_path(input: &str) -> PathBuf {
+    let input = Path::new(input);
+    let stem = input
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_else(|| "output".to_string());
+    let extension = input
+        .extension()
+        .map(|e| e.to_string_lossy().to_string())
+        .unwrap_or_default();
+    input.with_file_name(format!("{}.loudnorm.{}", stem, extension))
+}
+
+/// Runs ffmpeg to completion and returns its stderr
+async fn run_ffmpeg(ffmpeg_path: &str, args: &[String]) -> Result<String, DownloadError> {
+    let output = crate::utils::create_hidden_async_command(ffmpeg_path)
+        .args(args)
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .output()
+        .await
+        .map_err(|e| {
+            if e.kind() == std::io::ErrorKind::NotFound {
+                DownloadError::ExecutableNotFound(ffmpeg_path.to_string())
+            } else {
+                DownloadError::ProcessSpawnError(e.to_string())
+            }
+        })?;
+
+    let stderr = String::from_utf8_lossy(&output.stderr).to_string();
+    if !output.status.success() {
+        let last_line = stderr.lines().rev().find(|l| !l.trim().is_empty()).unwrap_or("");
+        return Err(DownloadError::LoudnessNormalizationFailed(format!(
+            "ffmpeg exited with {}: {}",
+            output.status,
+            last_line.trim()
+        )));
+    }
+    Ok(stderr)
+}
+
+/// Normalizes an audio file in place to `target_lufs`
+///
+/// Returns the first-pass measurement (with the achieved loudness in
+/// `output_i` when ffmpeg reports it).
+pub async fn run_loudnorm(
+    ffmpeg_path: &str,
+    input: &str,
+    target_lufs: f64,
+) -> Result<LoudnessMeasurement, DownloadError> {
+    if !is_valid_target_lufs(target_lufs) {
+        return Err(DownloadError::LoudnessNormalizationFailed(format!(
+            "target {} LUFS is outside -70..-5",
+            target_lufs
+        )));
+    }
+
+    tracing::info!("Measuring loudness of {}", input);
+    let stderr = run_ffmpeg(ffmpeg_path, &build_measure_args(input, target_lufs)).await?;
+    let measurement = parse_loudnorm_output(&stderr, target_lufs).ok_or_else(|| {
+        DownloadError::LoudnessNormalizationFailed("could not measure loudness".to_string())
+    })?;
+    tracing::info!(
+        "Measured {} LUFS, {} dBTP, {} LU; normalizing to {} LUFS",
+        measurement.input_i,
+        measurement.input_tp,
+        measurement.input_lra,
+        target_lufs
+    );
+
+    let temp = normalized_temp_path(input);
+    let temp_str = temp.to_string_lossy().to_string();
+    let source = parse_source_audio(&stderr).unwrap_or_default();
+    let args = build_normalize_args(input, &temp_str, &measurement, &source).ok_or_else(|| {
+        DownloadError::LoudnessNormalizationFailed(format!("unsupported audio file: {}", input))
+    })?;
+
+    let stderr = match run_ffmpeg(ffmpeg_path, &args).await {
+        Ok(stderr) => stderr,
+        Err(e) => {
+            let _ = std::fs::remove_file(&temp);
+            return Err(e);
+        }
+    };
+
+    std::fs::rename(&temp, input).map_err(|e| {
+        let _ = std::fs::remove_file(&temp);
+        DownloadError::LoudnessNormalizationFailed(format!("failed to replace {}: {}", input, e))
+    })?;
+
+    Ok(LoudnessMeasurement {
+        output_i: parse_loudnorm_output(&stderr, target_lufs).and_then(|m| m.output_i),
+        ..measurement
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MEASURE_STDERR: &str = r#"Input #0, mp3, from 'talk.mp3':
+  Duration: 00:42:10.12, start: 0.025057, bitrate: 128 kb/s
+  Stream #0:0: Audio: mp3 (mp3float), 44100 Hz, stereo, fltp, 128 kb/s
+[Parsed_loudnorm_0 @ 0000021c2f3b2c40]
+{
+	"input_i" : "-27.61",
+	"input_tp" : "-4.47",
+	"input_lra" : "18.06",
+	"input_thresh" : "-39.20",
+	"output_i" : "-16.58",
+	"output_tp" : "-1.50",
+	"output_lra" : "14.78",
+	"output_thresh" : "-27.71",
+	"normalization_type" : "dynamic",
+	"target_offset" : "0.58"
+}
+"#;
+
+    fn measurement() -> LoudnessMeasurement {
+        parse_loudnorm_output(MEASURE_STDERR, -16.0).unwrap()
+    }
+
+    #[test]
+    fn test_parse_loudnorm_output() {
+        let m = measurement();
+        assert_eq!(m.input_i, -27.61);
+        assert_eq!(m.input_tp, -4.47);
+        assert_eq!(m.input_lra, 18.06);
+        assert_eq!(m.input_thresh, -39.20);
+        assert_eq!(m.target_offset, 0.58);
+        assert_eq!(m.target_i, -16.0);
+        assert_eq!(m.output_i, Some(-16.58));
+    }
+
+    #[test]
+    fn test_parse_loudnorm_output_silence() {
+        let stderr = MEASURE_STDERR.replace("\"-27.61\"", "\"-inf\"");
+        assert!(parse_loudnorm_output(&stderr, -16.0).is_none());
+        assert!(parse_loudnorm_output("no json here", -16.0).is_none());
+    }
+
+    #[test]
+    fn test_build_measure_args() {
+        let args = build_measure_args("talk.mp3", -23.0);
+        assert!(args.contains(&"loudnorm=I=-23:TP=-1.5:LRA=11:print_format=json".to_string()));
+        assert_eq!(&args[args.len() - 3..], ["-f", "null", "-"]);
+    }
+
+    #[test]
+    fn test_build_normalize_args() {
+        let source = parse_source_audio(MEASURE_STDERR).unwrap();
+        let args = build_normalize_args("talk.mp3", "talk.loudnorm.mp3", &measurement(), &source).unwrap();
+        let filter = &args[args.iter().position(|a| a == "-af").unwrap() + 1];
+        assert!(filter.starts_with("loudnorm=I=-16:TP=-1.5:LRA=11:measured_I=-27.61:"));
+        assert!(filter.contains("offset=0.58:linear=true"));
+        assert!(args.windows(2).any(|w| w == ["-c:a", "libmp3lame"]));
+        assert!(args.windows(2).any(|w| w == ["-ar", "48000"]));
+        assert_eq!(args.last().unwrap(), "talk.loudnorm.mp3");
+
+        assert!(build_normalize_args("clip.mkv", "clip.loudnorm.mkv", &measurement(), &source).is_none());
+    }
+
+    #[test]
+    fn test_build_normalize_args_keeps_lossless_format() {
+        let source = parse_source_audio("  Stream #0:0: Audio: flac, 96000 Hz, stereo, s32 (24 bit)").unwrap();
+        assert_eq!(source.codec, "flac");
+        assert_eq!(source.sample_rate, Some(96000));
+        assert_eq!(source.sample_fmt.as_deref(), Some("s32"));
+        let args = build_normalize_args("song.flac", "song.loudnorm.flac", &measurement(), &source).unwrap();
+        assert!(args.windows(2).any(|w| w == ["-sample_fmt", "s32"]));
+        assert!(args.windows(2).any(|w| w == ["-ar", "96000"]));
+
+        let source = parse_source_audio(
+            "    Stream #0:0: Audio: pcm_s24le ([1][0][0][0] / 0x0001), 88200 Hz, 2 channels, s32 (24 bit), 4233 kb/s",
+        )
+        .unwrap();
+        let args = build_normalize_args("song.wav", "song.loudnorm.wav", &measurement(), &source).unwrap();
+        assert!(args.windows(2).any(|w| w == ["-c:a", "pcm_s24le"]));
+        assert!(args.windows(2).any(|w| w == ["-ar", "88200"]));
+    }
+
+    #[test]
+    fn test_normalized_temp_path() {
+        let input = Path::new("music").join("Song [abc].opus");
+        assert_eq!(
+            normalized_temp_path(&input.to_string_lossy()),
+            Path::new("music").join("Song [abc].loudnorm.opus")
+        );
+    }
+}
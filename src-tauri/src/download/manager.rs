@@ -146,6 +146,17 @@ impl DownloadManager {
 
     /// Marks the download as completed successfully
     pub async fn complete(&self, file_path: String) -> Result<DownloadResult, DownloadError> {
+        self.complete_with_files(file_path, Vec::new(), Vec::new()).await
+    }
+
+    /// Marks the download as completed, reporting extra files (split chapters
+    /// and sidecar subtitles)
+    pub async fn complete_with_files(
+        &self,
+        file_path: String,
+        chapter_files: Vec<String>,
+        subtitle_files: Vec<String>,
+    ) -> Result<DownloadResult, DownloadError> {
         // Transition to Completed state
         self.transition_to(DownloadState::Completed)
             .await
@@ -165,9 +176,17 @@ impl DownloadManager {
             *process = None;
         }
         
+        let file_paths = std::iter::once(file_path.clone())
+            .filter(|path| !path.is_empty())
+            .chain(chapter_files)
+            .collect();
+        
         Ok(DownloadResult {
             success: true,
             file_path: Some(file_path),
+            file_paths,
+            subtitle_files,
+            loudness: None,
             error: None,
         })
     }
@@ -193,10 +212,30 @@ impl DownloadManager {
         DownloadResult {
             success: false,
             file_path: None,
+            file_paths: Vec::new(),
+            subtitle_files: Vec::new(),
+            loudness: None,
             error: Some(error.to_string()),
         }
     }
 
+    /// Marks the download as skipped because yt-dlp had nothing to download
+    ///
+    /// The reason is kept as the last error so the status shows why.
+    pub async fn skip(&self, reason: DownloadError) {
+        let _ = self.transition_to(DownloadState::Skipped).await;
+        
+        {
+            let mut last_error = self.last_error.write().await;
+            *last_error = Some(reason.to_string());
+        }
+        
+        {
+            let mut process = self.process.lock().await;
+            *process = None;
+        }
+    }
+
     /// Requests cancellation of the current download
     /// 
     /// **Validates: Requirements 1.4**
@@ -324,6 +363,22 @@ impl DownloadManager {
             .map_err(|_| DownloadError::GenericError("Invalid state transition".to_string()))?;
         Ok(())
     }
+
+    /// Transitions to waiting state (yt-dlp is waiting for a scheduled livestream)
+    pub async fn wait_for_live(&self) -> Result<(), DownloadError> {
+        self.transition_to(DownloadState::WaitingForLive)
+            .await
+            .map_err(|_| DownloadError::GenericError("Invalid state transition".to_string()))?;
+        Ok(())
+    }
+
+    /// Transitions to recording state (first progress event of a livestream)
+    pub async fn start_recording(&self) -> Result<(), DownloadError> {
+        self.transition_to(DownloadState::Recording)
+            .await
+            .map_err(|_| DownloadError::GenericError("Invalid state transition".to_string()))?;
+        Ok(())
+    }
 }
 
 impl Default for DownloadManager {
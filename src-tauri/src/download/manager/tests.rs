@@ -4,6 +4,9 @@
 //! **Validates: Requirements 1.5**
 
 use super::*;
+use crate::models::{
+    AudioCodec, LiveOptions, OutputFormat, PlaylistFilter, Quality, SponsorBlockOptions, SubtitleOptions, VideoContainer,
+};
 use proptest::prelude::*;
 
 /// Generate arbitrary download configurations
@@ -11,14 +14,14 @@ fn arb_download_config() -> impl Strategy<Value = DownloadConfig> {
     (
         "[a-zA-Z0-9]{5,20}".prop_map(|s| format!("https://youtube.com/watch?v={}", s)),
         prop_oneof![
-            Just("video-mp4".to_string()),
-            Just("audio-mp3".to_string()),
-            Just("audio-best".to_string()),
+            Just(OutputFormat::Video(VideoContainer::Mp4)),
+            Just(OutputFormat::Audio(AudioCodec::Mp3)),
+            Just(OutputFormat::Audio(AudioCodec::Best)),
         ],
         prop_oneof![
-            Just("best".to_string()),
-            Just("1080p".to_string()),
-            Just("720p".to_string()),
+            Just(Quality::Best),
+            Just(Quality::P1080),
+            Just(Quality::P720),
         ],
         Just("C:\\Downloads".to_string()),
         any::<bool>(),
@@ -40,6 +43,20 @@ fn arb_download_config() -> impl Strategy<Value = DownloadConfig> {
                     filename_template: None,
                     proxy_url: None,
                     cookies_file_path: None,
+                    max_fps: None,
+                    allow_hdr: true,
+                    video_codec: None,
+                    sections: Vec::new(),
+                    chapter_patterns: Vec::new(),
+                    sponsorblock: SponsorBlockOptions::default(),
+                    split_chapters: false,
+                    subtitles: SubtitleOptions::default(),
+                    organize_rules: Vec::new(),
+                    transcode_preset: None,
+                    loudness_target_lufs: None,
+                    live: LiveOptions::default(),
+                    playlist: None,
+                    playlist_filter: PlaylistFilter::default(),
                 }
             },
         )
@@ -48,14 +65,28 @@ fn arb_download_config() -> impl Strategy<Value = DownloadConfig> {
 fn create_test_config() -> DownloadConfig {
     DownloadConfig {
         url: "https://youtube.com/watch?v=test".to_string(),
-        format: "video-mp4".to_string(),
-        quality: "best".to_string(),
+        format: OutputFormat::Video(VideoContainer::Mp4),
+        quality: Quality::Best,
         output_folder: "C:\\Downloads".to_string(),
         embed_subtitles: false,
         cookies_from_browser: None,
         filename_template: None,
         proxy_url: None,
         cookies_file_path: None,
+        max_fps: None,
+        allow_hdr: true,
+        video_codec: None,
+        sections: Vec::new(),
+        chapter_patterns: Vec::new(),
+        sponsorblock: SponsorBlockOptions::default(),
+        split_chapters: false,
+        subtitles: SubtitleOptions::default(),
+        organize_rules: Vec::new(),
+        transcode_preset: None,
+        loudness_target_lufs: None,
+        live: LiveOptions::default(),
+        playlist: None,
+        playlist_filter: PlaylistFilter::default(),
     }
 }
 
@@ -227,7 +258,7 @@ mod unit_tests {
         let config1 = create_test_config();
         let mut config2 = create_test_config();
         config2.url = "https://youtube.com/watch?v=test2".to_string();
-        config2.format = "audio-mp3".to_string();
+        config2.format = OutputFormat::Audio(AudioCodec::Mp3);
 
         manager.start_download(config1).await.unwrap();
         let result = manager.start_download(config2).await;
@@ -259,6 +290,56 @@ mod unit_tests {
         assert_eq!(manager.get_state().await, DownloadState::Completed);
     }
 
+    #[tokio::test]
+    async fn test_live_recording_flow() {
+        let manager = DownloadManager::new();
+        let mut config = create_test_config();
+        config.live = LiveOptions {
+            enabled: true,
+            wait_for_stream: true,
+            ..LiveOptions::default()
+        };
+
+        manager.start_download(config).await.unwrap();
+        manager.start_downloading().await.unwrap();
+
+        manager.wait_for_live().await.unwrap();
+        assert_eq!(manager.get_state().await, DownloadState::WaitingForLive);
+        assert!(manager.is_active().await);
+
+        manager.start_recording().await.unwrap();
+        assert_eq!(manager.get_state().await, DownloadState::Recording);
+        assert!(manager.start_recording().await.is_err());
+
+        let result = manager.complete("C:\\Downloads\\stream.ts".to_string()).await.unwrap();
+        assert!(result.success);
+        assert_eq!(manager.get_state().await, DownloadState::Completed);
+    }
+
+    #[tokio::test]
+    async fn test_complete_with_chapter_files() {
+        let manager = DownloadManager::new();
+        let mut config = create_test_config();
+        config.split_chapters = true;
+
+        manager.start_download(config).await.unwrap();
+        manager.start_downloading().await.unwrap();
+
+        let chapters = vec![
+            "C:\\Downloads\\video\\001 - Intro.mp4".to_string(),
+            "C:\\Downloads\\video\\002 - Main.mp4".to_string(),
+        ];
+        let result = manager
+            .complete_with_files("C:\\Downloads\\video.mp4".to_string(), chapters.clone(), Vec::new())
+            .await
+            .unwrap();
+
+        assert_eq!(result.file_path, Some("C:\\Downloads\\video.mp4".to_string()));
+        assert_eq!(result.file_paths.len(), 3);
+        assert_eq!(result.file_paths[0], "C:\\Downloads\\video.mp4");
+        assert_eq!(&result.file_paths[1..], chapters.as_slice());
+    }
+
     #[tokio::test]
     async fn test_cancel_download() {
         let manager = DownloadManager::new();
@@ -285,6 +366,22 @@ mod unit_tests {
         assert_eq!(manager.get_state().await, DownloadState::Failed);
     }
 
+    #[tokio::test]
+    async fn test_skip_download() {
+        let manager = DownloadManager::new();
+        let config = create_test_config();
+
+        manager.start_download(config).await.unwrap();
+        manager.start_downloading().await.unwrap();
+        
+        manager.skip(DownloadError::AlreadyArchived("Video".to_string())).await;
+        assert_eq!(manager.get_state().await, DownloadState::Skipped);
+        assert!(manager.get_last_error().await.unwrap().contains("Video"));
+        
+        manager.reset().await.unwrap();
+        assert_eq!(manager.get_state().await, DownloadState::Idle);
+    }
+
     #[tokio::test]
     async fn test_reset_after_completion() {
         let manager = DownloadManager::new();
@@ -322,6 +419,7 @@ mod unit_tests {
             speed: "1.0MiB/s".to_string(),
             eta_seconds: Some(60),
             status: "downloading".to_string(),
+            elapsed_seconds: None,
         };
         
         manager.update_progress(progress.clone()).await;
@@ -3,17 +3,31 @@
 //! This module handles yt-dlp process execution, progress parsing,
 //! and download job management.
 
+pub mod archive;
 pub mod args;
+pub mod batch;
+pub mod duplicate;
+pub mod loudnorm;
 pub mod manager;
 pub mod parser;
+pub mod playlist;
 pub mod process;
 pub mod queue;
+pub mod template;
+pub mod transcode;
 
 #[cfg(test)]
 mod tests;
 
+pub use archive::*;
 pub use args::*;
+pub use batch::*;
+pub use duplicate::*;
+pub use loudnorm::*;
 pub use manager::*;
 pub use parser::*;
+pub use playlist::*;
 pub use process::*;
 pub use queue::*;
+pub use template::*;
+pub use transcode::*;
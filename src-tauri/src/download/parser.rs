@@ -9,7 +9,7 @@
 //!
 //! **Validates: Requirements 4.2, 4.5, 6.3**
 
-use crate::models::{DownloadError, ProgressEvent};
+use crate::models::{parse_timestamp, DownloadError, ProgressEvent};
 
 /// Result of parsing a progress line
 #[derive(Debug, Clone, PartialEq)]
@@ -18,6 +18,12 @@ pub enum ParsedLine {
     Progress(ProgressEvent),
     /// Merging state detected
     Merging,
+    /// Video skipped because it's in the download archive (carries the title)
+    AlreadyArchived(String),
+    /// Video skipped by the playlist filter (carries yt-dlp's reason)
+    FilteredOut(String),
+    /// Waiting for a scheduled livestream to start (--wait-for-video)
+    WaitingForLive,
     /// Line could not be parsed (not an error, just not progress data)
     Unknown,
 }
@@ -37,12 +43,60 @@ pub fn parse_progress_line(line: &str) -> ParsedLine {
         return ParsedLine::Merging;
     }
     
+    // SponsorBlock segment removal re-encodes after the download reaches 100%,
+    // so report it as post-processing. "[SponsorBlock]" lines (fetching
+    // segments) run before the download starts and are left as Unknown.
+    if line.starts_with("[ModifyChapters]") {
+        return ParsedLine::Merging;
+    }
+    
+    // Chapter splitting also runs after the download; the per-chapter
+    // "Destination:" lines are left as Unknown so the file paths get picked up.
+    if line.starts_with("[SplitChapters] Splitting") {
+        return ParsedLine::Merging;
+    }
+    
+    // With --download-archive, videos that were downloaded before are skipped:
+    // "[download] Some Title has already been recorded in the archive"
+    if let Some(title) = line
+        .strip_prefix("[download]")
+        .and_then(|rest| rest.strip_suffix("has already been recorded in the archive"))
+    {
+        return ParsedLine::AlreadyArchived(title.trim().to_string());
+    }
+    
+    // Entries rejected by --match-filters/--dateafter/--datebefore:
+    // "[download] Some Title does not pass filter (duration>=?300), skipping .."
+    // "[download] 2024-12-31 upload date is not in range 20250101 to 99991231"
+    if let Some(reason) = line.strip_prefix("[download]").filter(|rest| {
+        rest.contains(" does not pass filter ") || rest.contains(" upload date is not in range ")
+    }) {
+        return ParsedLine::FilteredOut(reason.trim().to_string());
+    }
+    
+    // --wait-for-video: "[wait] Waiting for 01:23:45 - Press Ctrl+C to try now"
+    if line.starts_with("[wait]") {
+        return ParsedLine::WaitingForLive;
+    }
+    
     // Try to parse default yt-dlp download format first
     // Format: [download]  45.2% of  52.3MiB at  2.5MiB/s ETA 00:12
     if line.starts_with("[download]") && line.contains('%') {
         return parse_default_download_line(line);
     }
     
+    // Livestreams have no total size, so there's no percentage:
+    // [download]   12.34MiB at    1.23MiB/s (00:00:41) (frag 12)
+    if line.starts_with("[download]") && line.contains(" at ") && line.ends_with(')') {
+        return parse_live_download_line(line);
+    }
+    
+    // ffmpeg stats while recording with --downloader ffmpeg:
+    // frame= 1234 fps= 30 q=-1.0 size=   10240kB time=00:00:41.20 bitrate=2035.1kbits/s speed=1.00x
+    if (line.starts_with("frame=") || line.starts_with("size=")) && line.contains("time=") {
+        return parse_ffmpeg_stats_line(line);
+    }
+    
     // Try pipe-delimited format (legacy/custom template)
     if line.contains('|') && !line.starts_with('[') {
         return parse_pipe_delimited_line(line);
@@ -131,9 +185,88 @@ fn parse_default_download_line(line: &str) -> ParsedLine {
         speed,
         eta_seconds,
         status: "downloading".to_string(),
+        elapsed_seconds: None,
     })
 }
 
+/// Parse live yt-dlp download format (no percentage or ETA)
+/// Format: [download]   12.34MiB at    1.23MiB/s (00:00:41) (frag 12)
+fn parse_live_download_line(line: &str) -> ParsedLine {
+    let content = line.trim_start_matches("[download]").trim();
+    
+    let Some((size_str, rest)) = content.split_once(" at ") else {
+        return ParsedLine::Unknown;
+    };
+    let Some(downloaded_bytes) = parse_bytes_raw_or_formatted_optional(size_str) else {
+        return ParsedLine::Unknown;
+    };
+    
+    let speed = clean_speed_string(rest.split_whitespace().next().unwrap_or(""));
+    let elapsed_seconds = rest
+        .split_once('(')
+        .and_then(|(_, after)| after.split_once(')'))
+        .and_then(|(elapsed, _)| parse_eta(elapsed));
+    if elapsed_seconds.is_none() {
+        return ParsedLine::Unknown;
+    }
+    
+    ParsedLine::Progress(live_progress(downloaded_bytes, speed, elapsed_seconds))
+}
+
+/// Parse an ffmpeg stats line
+/// Format: frame= 1234 fps= 30 q=-1.0 size=   10240kB time=00:00:41.20 bitrate=2035.1kbits/s speed=1.00x
+fn parse_ffmpeg_stats_line(line: &str) -> ParsedLine {
+    // Values may be padded ("size=   10240kB"), so join each key with its value first
+    let tokens: Vec<&str> = line.split_whitespace().collect();
+    let mut fields = Vec::new();
+    let mut i = 0;
+    while i < tokens.len() {
+        match tokens[i].split_once('=') {
+            Some((key, "")) if i + 1 < tokens.len() => {
+                fields.push((key, tokens[i + 1]));
+                i += 2;
+            }
+            Some((key, value)) => {
+                fields.push((key, value));
+                i += 1;
+            }
+            None => i += 1,
+        }
+    }
+    let field = |name: &str| fields.iter().find(|(key, _)| *key == name).map(|(_, value)| *value);
+    
+    // time=00:00:41.20 - drop the fractional seconds
+    let elapsed_seconds = field("time").and_then(|t| parse_eta(t.split('.').next().unwrap_or(t)));
+    if elapsed_seconds.is_none() {
+        return ParsedLine::Unknown;
+    }
+    
+    // ffmpeg's "kB" is 1024 bytes
+    let downloaded_bytes = field("size")
+        .or_else(|| field("Lsize"))
+        .and_then(parse_bytes_raw_or_formatted_optional)
+        .unwrap_or(0);
+    let speed = field("bitrate")
+        .filter(|b| !b.eq_ignore_ascii_case("n/a"))
+        .unwrap_or("--")
+        .to_string();
+    
+    ParsedLine::Progress(live_progress(downloaded_bytes, speed, elapsed_seconds))
+}
+
+/// Progress event for a livestream recording
+fn live_progress(downloaded_bytes: u64, speed: String, elapsed_seconds: Option<u64>) -> ProgressEvent {
+    ProgressEvent {
+        percentage: 0.0,
+        downloaded_bytes,
+        total_bytes: None,
+        speed,
+        eta_seconds: None,
+        status: "recording".to_string(),
+        elapsed_seconds,
+    }
+}
+
 /// Parse pipe-delimited progress format
 /// Format: 45.2%|12345678|52345678|2.5MiB/s|00:12
 fn parse_pipe_delimited_line(data: &str) -> ParsedLine {
@@ -180,6 +313,7 @@ fn parse_pipe_delimited_line(data: &str) -> ParsedLine {
         speed,
         eta_seconds,
         status: "downloading".to_string(),
+        elapsed_seconds: None,
     })
 }
 
@@ -281,28 +415,11 @@ fn parse_eta(s: &str) -> Option<u64> {
     let s = s.trim();
     
     // Handle N/A or Unknown
-    if s.eq_ignore_ascii_case("n/a") || s.eq_ignore_ascii_case("unknown") || s.is_empty() {
+    if s.eq_ignore_ascii_case("n/a") || s.eq_ignore_ascii_case("unknown") {
         return None;
     }
     
-    // Parse time format: HH:MM:SS or MM:SS or SS
-    let parts: Vec<&str> = s.split(':').collect();
-    
-    match parts.len() {
-        1 => parts[0].parse().ok(),
-        2 => {
-            let mins: u64 = parts[0].parse().ok()?;
-            let secs: u64 = parts[1].parse().ok()?;
-            Some(mins * 60 + secs)
-        }
-        3 => {
-            let hours: u64 = parts[0].parse().ok()?;
-            let mins: u64 = parts[1].parse().ok()?;
-            let secs: u64 = parts[2].parse().ok()?;
-            Some(hours * 3600 + mins * 60 + secs)
-        }
-        _ => None,
-    }
+    parse_timestamp(s)
 }
 
 /// Error category for parsed yt-dlp errors
@@ -818,6 +935,9 @@ mod tests {
         
         // N/A
         assert_eq!(parse_eta("N/A"), None);
+
+        // Garbage from a stats line doesn't overflow
+        assert_eq!(parse_eta(&format!("{}:00:00", u64::MAX / 1000)), None);
     }
 
     #[test]
@@ -836,6 +956,96 @@ mod tests {
         assert!(matches!(parse_progress_line("Merging video and audio"), ParsedLine::Merging));
     }
 
+    #[test]
+    fn test_split_chapters_lines() {
+        assert!(matches!(
+            parse_progress_line("[SplitChapters] Splitting video by chapters; 5 chapters found"),
+            ParsedLine::Merging
+        ));
+        assert!(matches!(
+            parse_progress_line("[SplitChapters] Chapter 001; Destination: /tmp/001 - Intro.mp4"),
+            ParsedLine::Unknown
+        ));
+    }
+
+    #[test]
+    fn test_sponsorblock_lines() {
+        assert!(matches!(
+            parse_progress_line("[ModifyChapters] Removing chapters from /tmp/video.mp4"),
+            ParsedLine::Merging
+        ));
+        assert!(matches!(
+            parse_progress_line("[ModifyChapters] SponsorBlock information is unavailable"),
+            ParsedLine::Merging
+        ));
+        assert!(matches!(
+            parse_progress_line("[SponsorBlock] Fetching SponsorBlock segments"),
+            ParsedLine::Unknown
+        ));
+        assert!(matches!(
+            parse_progress_line("[SponsorBlock] Found 3 segments in the SponsorBlock database"),
+            ParsedLine::Unknown
+        ));
+    }
+
+    #[test]
+    fn test_already_archived_line() {
+        match parse_progress_line("[download] Rick Astley - Never Gonna Give You Up has already been recorded in the archive") {
+            ParsedLine::AlreadyArchived(title) => assert_eq!(title, "Rick Astley - Never Gonna Give You Up"),
+            other => panic!("unexpected {:?}", other),
+        }
+        assert!(matches!(
+            parse_progress_line("[download] /tmp/video.mp4 has already been downloaded"),
+            ParsedLine::Unknown
+        ));
+    }
+
+    #[test]
+    fn test_filtered_out_lines() {
+        assert!(matches!(
+            parse_progress_line("[download] Short clip #shorts does not pass filter (title!~='(?i)(\\x23shorts)'), skipping .."),
+            ParsedLine::FilteredOut(_)
+        ));
+        match parse_progress_line("[download] 2024-12-31 upload date is not in range 2025-01-01 to 9999-12-31") {
+            ParsedLine::FilteredOut(reason) => assert!(reason.starts_with("2024-12-31 upload date")),
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_live_progress_lines() {
+        let result = parse_progress_line("[download]   12.00MiB at    1.50MiB/s (00:01:41) (frag 12)");
+        match result {
+            ParsedLine::Progress(event) => {
+                assert_eq!(event.status, "recording");
+                assert_eq!(event.downloaded_bytes, 12 * 1024 * 1024);
+                assert_eq!(event.total_bytes, None);
+                assert_eq!(event.speed, "1.50MiB/s");
+                assert_eq!(event.eta_seconds, None);
+                assert_eq!(event.elapsed_seconds, Some(101));
+            }
+            other => panic!("Expected Progress, got {:?}", other),
+        }
+        
+        let result = parse_progress_line(
+            "frame= 1234 fps= 30 q=-1.0 size=   10240kB time=00:00:41.20 bitrate=2035.1kbits/s speed=1.00x",
+        );
+        match result {
+            ParsedLine::Progress(event) => {
+                assert_eq!(event.status, "recording");
+                assert_eq!(event.downloaded_bytes, 10240 * 1024);
+                assert_eq!(event.speed, "2035.1kbits/s");
+                assert_eq!(event.elapsed_seconds, Some(41));
+            }
+            other => panic!("Expected Progress, got {:?}", other),
+        }
+        
+        assert_eq!(
+            parse_progress_line("[wait] Waiting for 01:23:45 - Press Ctrl+C to try now"),
+            ParsedLine::WaitingForLive
+        );
+    }
+    
     #[test]
     fn test_unknown_lines() {
         assert!(matches!(parse_progress_line(""), ParsedLine::Unknown));
@@ -0,0 +1,393 @@
+//! Streaming playlist enumeration
+//!
+//! `yt-dlp --flat-playlist -j` prints one JSON object per entry as soon as
+//! the entry is listed, with the playlist's own fields (`playlist_title`,
+//! `playlist_count`, ...) repeated on every line. Parsing line by line lets
+//! huge channels fill in incrementally and be cancelled part-way, instead of
+//! waiting for one `-J` document holding every entry.
+
+use serde_json::Value;
+
+use crate::models::{DownloadError, PlaylistEntry, PlaylistFilter, PlaylistInfo};
+
+/// Titles yt-dlp uses for entries that can't be downloaded
+const UNAVAILABLE_TITLES: &[&str] = &["[Deleted video]", "[Private video]", "[Unavailable video]"];
+
+/// yt-dlp arguments for listing a playlist, optionally one page of it
+///
+/// `start` is 1-based; `limit` caps the number of entries listed (before
+/// the filter drops any).
+///
+/// The playlist filter is applied by the collector rather than passed to
+/// yt-dlp: flat entries carry the same fields either way, and a listing
+/// where yt-dlp drops every entry wouldn't even give the playlist title.
+pub fn playlist_args(url: &str, start: Option<u32>, limit: Option<u32>) -> Vec<String> {
+    let mut args = vec![
+        "--flat-playlist".to_string(),
+        "-j".to_string(),
+        "--no-download".to_string(),
+        "--no-warnings".to_string(),
+    ];
+
+    if start.is_some() || limit.is_some() {
+        let start = start.unwrap_or(1).max(1);
+        let end = limit.map(|limit| (start + limit.max(1) - 1).to_string()).unwrap_or_default();
+        args.push("--playlist-items".to_string());
+        args.push(format!("{}:{}", start, end));
+    }
+
+    args.push(url.to_string());
+    args
+}
+
+/// Builds the watch URL for a flat entry
+///
+/// Flat entries usually carry a full `url` (or `webpage_url`), but some
+/// extractors only give an id, sometimes in the `url` field itself. Those
+/// are turned into URLs for the extractor named in `ie_key`; entries from
+/// other extractors yield None rather than a guessed URL.
+pub fn entry_url(entry: &Value) -> Option<String> {
+    let is_http = |s: &&str| s.starts_with("http://") || s.starts_with("https://");
+
+    if let Some(url) = ["webpage_url", "url", "original_url"]
+        .iter()
+        .find_map(|key| entry[*key].as_str().filter(is_http))
+    {
+        return Some(url.to_string());
+    }
+
+    let id = entry["id"]
+        .as_str()
+        .or_else(|| entry["url"].as_str())
+        .filter(|id| !id.is_empty())?;
+    let extractor = entry["ie_key"].as_str().or_else(|| entry["extractor_key"].as_str())?;
+    url_for_id(extractor, id)
+}
+
+/// Watch URL for a video id on sites whose URLs only need the id
+fn url_for_id(extractor: &str, id: &str) -> Option<String> {
+    let url = match extractor.to_ascii_lowercase().as_str() {
+        "youtube" => format!("https://www.youtube.com/watch?v={}", id),
+        "vimeo" => format!("https://vimeo.com/{}", id),
+        "dailymotion" => format!("https://www.dailymotion.com/video/{}", id),
+        "twitchvod" => format!("https://www.twitch.tv/videos/{}", id.trim_start_matches('v')),
+        "bilibili" => format!("https://www.bilibili.com/video/{}", id),
+        "niconico" => format!("https://www.nicovideo.jp/watch/{}", id),
+        "rumble" => format!("https://rumble.com/embed/{}/", id),
+        _ => return None,
+    };
+    Some(url)
+}
+
+/// Assembles a `PlaylistInfo` from `-j` lines as they arrive
+#[derive(Debug)]
+pub struct PlaylistCollector {
+    /// URL the listing was started for (used when yt-dlp doesn't report one)
+    url: String,
+    info: Option<PlaylistInfo>,
+    /// Lines that weren't playlist entries (single videos, junk)
+    non_entries: usize,
+    /// Entries dropped because they're unavailable or have no usable URL
+    skipped: usize,
+    /// Entries dropped by the filter
+    filtered: usize,
+    /// Position of the first listed entry, for entries without an index
+    start: u32,
+    filter: PlaylistFilter,
+}
+
+impl PlaylistCollector {
+    pub fn new(url: &str, start: Option<u32>) -> Self {
+        Self {
+            url: url.to_string(),
+            info: None,
+            non_entries: 0,
+            skipped: 0,
+            filtered: 0,
+            start: start.unwrap_or(1).max(1),
+            filter: PlaylistFilter::default(),
+        }
+    }
+
+    /// Drops entries that don't pass `filter`
+    pub fn with_filter(mut self, filter: PlaylistFilter) -> Self {
+        self.filter = filter;
+        self
+    }
+
+    /// Entries collected so far
+    pub fn loaded(&self) -> usize {
+        self.info.as_ref().map_or(0, |info| info.entries.len())
+    }
+
+    /// Playlist size reported by the site, if known
+    pub fn total(&self) -> Option<u32> {
+        self.info.as_ref().and_then(|info| info.total_count)
+    }
+
+    /// Unavailable entries dropped so far
+    pub fn skipped(&self) -> usize {
+        self.skipped
+    }
+
+    /// Entries dropped by the filter so far
+    pub fn filtered(&self) -> usize {
+        self.filtered
+    }
+
+    /// Parses one output line; returns whether it added an entry
+    pub fn push_line(&mut self, line: &str) -> bool {
+        let line = line.trim();
+        if !line.starts_with('{') {
+            return false;
+        }
+        let entry: Value = match serde_json::from_str(line) {
+            Ok(value) => value,
+            Err(e) => {
+                tracing::debug!("Skipping unparsable playlist line: {}", e);
+                self.non_entries += 1;
+                return false;
+            }
+        };
+
+        // Only playlist entries carry the playlist fields
+        if entry["playlist_id"].is_null() && entry["playlist_index"].is_null() {
+            self.non_entries += 1;
+            return false;
+        }
+
+        let info = self.info.get_or_insert_with(|| playlist_from_entry(&entry, &self.url));
+        let index = entry["playlist_index"]
+            .as_u64()
+            .map(|index| index as u32)
+            .unwrap_or(self.start + (info.entries.len() + self.skipped + self.filtered) as u32);
+
+        let title = entry["title"].as_str().unwrap_or("Unknown");
+        if UNAVAILABLE_TITLES.contains(&title) {
+            self.skipped += 1;
+            return false;
+        }
+        let (Some(id), Some(url)) = (entry["id"].as_str(), entry_url(&entry)) else {
+            tracing::warn!("Skipping playlist entry {} without a usable URL", index);
+            self.skipped += 1;
+            return false;
+        };
+
+        let listed = PlaylistEntry {
+            id: id.to_string(),
+            title: title.to_string(),
+            url,
+            thumbnail: entry_thumbnail(&entry),
+            duration: entry["duration"].as_f64(),
+            playlist_index: index,
+            uploader: entry["uploader"]
+                .as_str()
+                .or_else(|| entry["channel"].as_str())
+                .map(|s| s.to_string()),
+            upload_date: entry_upload_date(&entry),
+        };
+        if !self.filter.matches(&listed) {
+            self.filtered += 1;
+            return false;
+        }
+
+        info.entries.push(listed);
+        info.video_count = info.entries.len() as u32;
+        true
+    }
+
+    /// Finishes the listing
+    ///
+    /// Fails if no line looked like a playlist entry, e.g. for a single video.
+    pub fn finish(self) -> Result<PlaylistInfo, DownloadError> {
+        match self.info {
+            Some(info) => Ok(info),
+            None if self.non_entries > 0 => Err(DownloadError::GenericError("URL is not a playlist".to_string())),
+            None => Err(DownloadError::GenericError("Playlist is empty".to_string())),
+        }
+    }
+}
+
+/// Playlist fields repeated on every entry line
+fn playlist_from_entry(entry: &Value, url: &str) -> PlaylistInfo {
+    PlaylistInfo {
+        id: entry["playlist_id"].as_str().unwrap_or("").to_string(),
+        title: entry["playlist_title"]
+            .as_str()
+            .or_else(|| entry["playlist"].as_str())
+            .unwrap_or("Unknown Playlist")
+            .to_string(),
+        url: entry["playlist_webpage_url"].as_str().unwrap_or(url).to_string(),
+        thumbnail: None,
+        uploader: entry["playlist_uploader"]
+            .as_str()
+            .or_else(|| entry["playlist_channel"].as_str())
+            .map(|s| s.to_string()),
+        video_count: 0,
+        total_count: entry["playlist_count"]
+            .as_u64()
+            .or_else(|| entry["n_entries"].as_u64())
+            .map(|count| count as u32),
+        entries: Vec::new(),
+    }
+}
+
+/// Upload date (YYYYMMDD) of a flat entry, from `upload_date` or `timestamp`
+fn entry_upload_date(entry: &Value) -> Option<String> {
+    entry["upload_date"]
+        .as_str()
+        .filter(|date| date.len() == 8)
+        .map(|date| date.to_string())
+        .or_else(|| {
+            entry["timestamp"]
+                .as_i64()
+                .or_else(|| entry["release_timestamp"].as_i64())
+                .and_then(|timestamp| chrono::DateTime::from_timestamp(timestamp, 0))
+                .map(|date| date.format("%Y%m%d").to_string())
+        })
+}
+
+/// Thumbnail of a flat entry (`thumbnail`, or the last of `thumbnails`)
+fn entry_thumbnail(entry: &Value) -> Option<String> {
+    entry["thumbnail"]
+        .as_str()
+        .or_else(|| {
+            entry["thumbnails"]
+                .as_array()
+                .and_then(|thumbnails| thumbnails.last())
+                .and_then(|thumbnail| thumbnail["url"].as_str())
+        })
+        .map(|s| s.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn line(value: Value) -> String {
+        value.to_string()
+    }
+
+    fn entry_line(id: &str, title: &str, index: u64) -> String {
+        line(json!({
+            "_type": "url",
+            "ie_key": "Youtube",
+            "id": id,
+            "title": title,
+            "url": format!("https://www.youtube.com/watch?v={}", id),
+            "duration": 120.0,
+            "playlist_id": "PLtest123",
+            "playlist_title": "Test Playlist",
+            "playlist_uploader": "Test Channel",
+            "playlist_webpage_url": "https://www.youtube.com/playlist?list=PLtest123",
+            "playlist_count": 4,
+            "playlist_index": index
+        }))
+    }
+
+    #[test]
+    fn test_collect_playlist_lines() {
+        let mut collector = PlaylistCollector::new("https://youtube.com/playlist?list=PLtest123", None);
+        assert!(collector.push_line(&entry_line("video1", "First Video", 1)));
+        assert!(!collector.push_line("[youtube:tab] Downloading page 2"));
+        assert!(collector.push_line(&entry_line("video2", "Second Video", 2)));
+        assert!(!collector.push_line(&entry_line("video3", "[Deleted video]", 3)));
+        assert!(!collector.push_line(&entry_line("video4", "[Private video]", 4)));
+        assert_eq!(collector.loaded(), 2);
+        assert_eq!(collector.skipped(), 2);
+        assert_eq!(collector.total(), Some(4));
+
+        let info = collector.finish().unwrap();
+        assert_eq!(info.id, "PLtest123");
+        assert_eq!(info.title, "Test Playlist");
+        assert_eq!(info.url, "https://www.youtube.com/playlist?list=PLtest123");
+        assert_eq!(info.uploader.as_deref(), Some("Test Channel"));
+        assert_eq!(info.video_count, 2);
+        assert_eq!(info.entries[1].title, "Second Video");
+        assert_eq!(info.entries[1].playlist_index, 2);
+    }
+
+    #[test]
+    fn test_single_video_is_not_a_playlist() {
+        let mut collector = PlaylistCollector::new("https://youtu.be/abc123", None);
+        collector.push_line(&line(json!({ "_type": "video", "id": "abc123", "title": "Single Video" })));
+        assert!(collector.finish().is_err());
+    }
+
+    #[test]
+    fn test_page_indices_continue_from_start() {
+        let mut collector = PlaylistCollector::new("https://example.com/list", Some(51));
+        for id in ["a", "b"] {
+            collector.push_line(&line(json!({
+                "ie_key": "Vimeo",
+                "id": id,
+                "title": id,
+                "playlist_id": "list"
+            })));
+        }
+        let info = collector.finish().unwrap();
+        assert_eq!(info.entries[0].playlist_index, 51);
+        assert_eq!(info.entries[1].playlist_index, 52);
+        assert_eq!(info.entries[1].url, "https://vimeo.com/b");
+    }
+
+    #[test]
+    fn test_collector_applies_filter() {
+        let filter = PlaylistFilter {
+            date_after: Some("2025-01-01".to_string()),
+            title_excludes: vec!["#shorts".to_string()],
+            ..Default::default()
+        };
+        let mut collector = PlaylistCollector::new("https://example.com/list", None).with_filter(filter);
+        let entry = |id: &str, title: &str, timestamp: Option<i64>| {
+            line(json!({
+                "ie_key": "Youtube",
+                "id": id,
+                "title": title,
+                "timestamp": timestamp,
+                "playlist_id": "list"
+            }))
+        };
+        assert!(!collector.push_line(&entry("a", "Old video", Some(1_700_000_000))));
+        assert!(!collector.push_line(&entry("b", "Clip #Shorts", Some(1_740_000_000))));
+        assert!(collector.push_line(&entry("c", "New video", Some(1_740_000_000))));
+        // No date in the listing: left for yt-dlp to check when downloading
+        assert!(collector.push_line(&entry("d", "Undated video", None)));
+        assert_eq!(collector.filtered(), 2);
+
+        let info = collector.finish().unwrap();
+        assert_eq!(info.entries[0].upload_date.as_deref(), Some("20250219"));
+        assert_eq!(info.entries[1].playlist_index, 4);
+    }
+
+    #[test]
+    fn test_entry_url() {
+        // Full URLs are used as they are
+        let entry = json!({ "ie_key": "Soundcloud", "id": "1", "url": "https://soundcloud.com/a/b" });
+        assert_eq!(entry_url(&entry).as_deref(), Some("https://soundcloud.com/a/b"));
+
+        // Bare ids are rebuilt for the entry's extractor
+        let entry = json!({ "ie_key": "Youtube", "id": "dQw4w9WgXcQ", "url": "dQw4w9WgXcQ" });
+        assert_eq!(entry_url(&entry).as_deref(), Some("https://www.youtube.com/watch?v=dQw4w9WgXcQ"));
+        let entry = json!({ "ie_key": "TwitchVod", "id": "v123456" });
+        assert_eq!(entry_url(&entry).as_deref(), Some("https://www.twitch.tv/videos/123456"));
+
+        // No YouTube guess for other sites
+        assert_eq!(entry_url(&json!({ "ie_key": "Generic", "id": "x" })), None);
+        assert_eq!(entry_url(&json!({ "id": "x" })), None);
+    }
+
+    #[test]
+    fn test_playlist_args() {
+        let args = playlist_args("https://example.com/list", None, None);
+        assert!(!args.contains(&"--playlist-items".to_string()));
+        assert_eq!(args.last().unwrap(), "https://example.com/list");
+
+        let args = playlist_args("https://example.com/list", Some(101), Some(50));
+        assert!(args.windows(2).any(|w| w == ["--playlist-items", "101:150"]));
+
+        let args = playlist_args("https://example.com/list", Some(20), None);
+        assert!(args.windows(2).any(|w| w == ["--playlist-items", "20:"]));
+    }
+}
@@ -13,6 +13,7 @@ use tokio::sync::mpsc;
 
 use crate::download::args::ArgumentBuilder;
 use crate::download::parser::{parse_error_line, parse_progress_line, ParsedLine};
+use crate::download::template::FilenameTemplate;
 use crate::models::{DownloadConfig, DownloadError, ProgressEvent};
 use crate::utils::logging::{log_ytdlp_command, log_ytdlp_stdout, log_ytdlp_stderr};
 
@@ -23,10 +24,18 @@ pub enum ProcessOutput {
     Progress(ProgressEvent),
     /// Merging state detected
     Merging,
+    /// yt-dlp skipped the video because it's in the download archive
+    AlreadyArchived(String),
+    /// yt-dlp skipped the video because it doesn't pass the playlist filter
+    FilteredOut(String),
+    /// yt-dlp is waiting for a scheduled livestream to start
+    WaitingForLive,
     /// Error detected in stderr
     Error(DownloadError),
     /// Final file path detected from --print after_move:filepath
     FilePath(String),
+    /// File written by --split-chapters
+    ChapterFile(String),
     /// Process completed successfully
     Completed(String), // file path
     /// Process exited with error code
@@ -41,6 +50,8 @@ pub struct SpawnConfig {
     pub ytdlp_path: String,
     /// Path to ffmpeg directory (optional)
     pub ffmpeg_location: Option<String>,
+    /// Download archive file (optional); archived videos are skipped
+    pub download_archive: Option<String>,
 }
 
 impl Default for SpawnConfig {
@@ -48,6 +59,7 @@ impl Default for SpawnConfig {
         Self {
             ytdlp_path: "yt-dlp".to_string(),
             ffmpeg_location: None,
+            download_archive: None,
         }
     }
 }
@@ -59,6 +71,13 @@ pub async fn spawn_ytdlp(
     config: &DownloadConfig,
     spawn_config: &SpawnConfig,
 ) -> Result<Child, DownloadError> {
+    // Reject invalid filename templates instead of silently using the default
+    if let Some(ref template) = config.filename_template {
+        if !template.is_empty() {
+            FilenameTemplate::parse(template)?;
+        }
+    }
+    
     // Build arguments
     let mut builder = ArgumentBuilder::new(config.clone());
     
@@ -66,6 +85,10 @@ pub async fn spawn_ytdlp(
         builder = builder.with_ffmpeg_location(ffmpeg_path.clone());
     }
     
+    if let Some(ref archive) = spawn_config.download_archive {
+        builder = builder.with_download_archive(archive.clone());
+    }
+    
     // Add proxy if configured
     if let Some(ref proxy) = config.proxy_url {
         builder = builder.with_proxy(Some(proxy.clone()));
@@ -190,9 +213,25 @@ pub async fn stream_process_output(
                                             tracing::info!("Merging state detected");
                                             let _ = tx_stdout.send(ProcessOutput::Merging).await;
                                         }
+                                        ParsedLine::AlreadyArchived(title) => {
+                                            tracing::info!("Skipped by download archive: {}", title);
+                                            let _ = tx_stdout.send(ProcessOutput::AlreadyArchived(title)).await;
+                                        }
+                                        ParsedLine::FilteredOut(reason) => {
+                                            tracing::info!("Skipped by playlist filter: {}", reason);
+                                            let _ = tx_stdout.send(ProcessOutput::FilteredOut(reason)).await;
+                                        }
+                                        ParsedLine::WaitingForLive => {
+                                            tracing::debug!("Waiting for livestream: {}", line);
+                                            let _ = tx_stdout.send(ProcessOutput::WaitingForLive).await;
+                                        }
                                         ParsedLine::Unknown => {
+                                            // Check if this line is a split chapter file
+                                            if let Some(path) = extract_chapter_path(line) {
+                                                tracing::info!("Detected chapter file: {}", path);
+                                                let _ = tx_stdout.send(ProcessOutput::ChapterFile(path)).await;
                                             // Check if this line is the final filepath
-                                            if is_valid_filepath(line) {
+                                            } else if is_valid_filepath(line) {
                                                 tracing::info!("Detected file path: {}", line);
                                                 let _ = tx_stdout.send(ProcessOutput::FilePath(line.to_string())).await;
                                             }
@@ -216,6 +255,9 @@ pub async fn stream_process_output(
     // Note: Also parse progress from stderr as some yt-dlp versions output progress there
     let stderr_task = tokio::spawn(async move {
         while let Ok(Some(line)) = stderr_reader.next_line().await {
+            // ffmpeg (live recording) rewrites its stats line with \r; keep the latest
+            let line = line.rsplit('\r').find(|part| !part.trim().is_empty()).unwrap_or("").to_string();
+            
             // Log stderr output (always logged as it often contains important info)
             log_ytdlp_stderr(&line);
             
@@ -232,6 +274,20 @@ pub async fn stream_process_output(
                     let _ = tx_stderr.send(ProcessOutput::Merging).await;
                     continue;
                 }
+                ParsedLine::AlreadyArchived(title) => {
+                    tracing::info!("Skipped by download archive (from stderr): {}", title);
+                    let _ = tx_stderr.send(ProcessOutput::AlreadyArchived(title)).await;
+                    continue;
+                }
+                ParsedLine::FilteredOut(reason) => {
+                    tracing::info!("Skipped by playlist filter (from stderr): {}", reason);
+                    let _ = tx_stderr.send(ProcessOutput::FilteredOut(reason)).await;
+                    continue;
+                }
+                ParsedLine::WaitingForLive => {
+                    let _ = tx_stderr.send(ProcessOutput::WaitingForLive).await;
+                    continue;
+                }
                 ParsedLine::Unknown => {
                     // Not progress, try to parse as error
                 }
@@ -289,8 +345,9 @@ fn is_valid_filepath(line: &str) -> bool {
         return false;
     }
     
-    // Should not contain yt-dlp output markers
-    if line.contains('[') || line.contains(']') || line.contains("download:") {
+    // Should not be a yt-dlp output line; brackets inside the path are fine
+    // (the default template adds "[id]", organization rules add subfolders)
+    if line.starts_with('[') || line.contains("download:") {
         return false;
     }
     
@@ -325,6 +382,57 @@ fn extract_output_path(line: &str) -> Option<String> {
     None
 }
 
+/// Subtitle extensions yt-dlp may write as sidecar files
+const SUBTITLE_EXTENSIONS: &[&str] = &["srt", "vtt", "ass", "ssa", "lrc", "ttml", "srv3", "json3"];
+
+/// Finds sidecar subtitle files written next to a downloaded file
+///
+/// yt-dlp names them `<file stem>.<lang>.<ext>` in the same folder.
+pub fn find_subtitle_files(file_path: &str) -> Vec<String> {
+    let path = Path::new(file_path);
+    let (Some(dir), Some(stem)) = (path.parent(), path.file_stem().and_then(|s| s.to_str())) else {
+        return Vec::new();
+    };
+    let prefix = format!("{}.", stem);
+
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+
+    let mut files: Vec<String> = entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|candidate| {
+            let name_matches = candidate
+                .file_name()
+                .and_then(|n| n.to_str())
+                .is_some_and(|name| name.starts_with(&prefix));
+            let ext_matches = candidate
+                .extension()
+                .and_then(|e| e.to_str())
+                .is_some_and(|ext| SUBTITLE_EXTENSIONS.contains(&ext.to_lowercase().as_str()));
+            name_matches && ext_matches
+        })
+        .map(|candidate| candidate.to_string_lossy().to_string())
+        .collect();
+
+    files.sort();
+    files
+}
+
+/// Extracts a chapter file path from --split-chapters output
+///
+/// Pattern: [SplitChapters] Chapter 001; Destination: /path/to/001 - Intro.mp4
+fn extract_chapter_path(line: &str) -> Option<String> {
+    if !line.starts_with("[SplitChapters] Chapter") {
+        return None;
+    }
+
+    line.split_once("Destination:")
+        .map(|(_, path)| path.trim().to_string())
+        .filter(|path| !path.is_empty())
+}
+
 /// Kills a child process
 ///
 /// **Validates: Requirements 1.4**
@@ -365,4 +473,45 @@ mod tests {
         assert_eq!(config.ytdlp_path, "yt-dlp");
         assert!(config.ffmpeg_location.is_none());
     }
+
+    #[test]
+    fn test_extract_chapter_path() {
+        let line = "[SplitChapters] Chapter 003; Destination: C:\\Downloads\\Album\\003 - Track.m4a";
+        assert_eq!(
+            extract_chapter_path(line),
+            Some("C:\\Downloads\\Album\\003 - Track.m4a".to_string())
+        );
+        assert_eq!(
+            extract_chapter_path("[SplitChapters] Splitting video by chapters; 5 chapters found"),
+            None
+        );
+        assert_eq!(extract_chapter_path("[download] Destination: C:\\video.mp4"), None);
+    }
+
+    #[test]
+    fn test_is_valid_filepath_allows_brackets() {
+        let path = std::env::temp_dir().join("Youtube").join("Some Video [abc123].mp4");
+        assert!(is_valid_filepath(&path.to_string_lossy()));
+        assert!(!is_valid_filepath("[download] 100% of 10.00MiB"));
+        assert!(!is_valid_filepath("Some Video [abc123].mp4"));
+    }
+
+    #[test]
+    fn test_find_subtitle_files() {
+        let dir = std::env::temp_dir().join(format!("mediagrab-subs-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        for name in ["clip.mp4", "clip.en.srt", "clip.tr.srt", "clip.info.json", "other.en.srt"] {
+            std::fs::write(dir.join(name), b"").unwrap();
+        }
+
+        let video = dir.join("clip.mp4");
+        let found = find_subtitle_files(&video.to_string_lossy());
+        let names: Vec<String> = found
+            .iter()
+            .map(|p| Path::new(p).file_name().unwrap().to_string_lossy().to_string())
+            .collect();
+
+        let _ = std::fs::remove_dir_all(&dir);
+        assert_eq!(names, vec!["clip.en.srt", "clip.tr.srt"]);
+    }
 }
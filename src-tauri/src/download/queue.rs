@@ -7,6 +7,7 @@ use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 use tokio::sync::{mpsc, RwLock, Semaphore};
 
+use crate::download::duplicate::{Duplicate, DuplicateSource, MediaKey};
 use crate::models::{DownloadConfig, DownloadError, ProgressEvent};
 
 /// Unique identifier for queue items
@@ -18,18 +19,54 @@ pub type QueueItemId = u64;
 pub enum QueueItemStatus {
     /// Waiting in queue
     Pending,
+    /// Waiting for its scheduled start time
+    Scheduled,
     /// Currently downloading
     Downloading,
+    /// Waiting for a scheduled livestream to start
+    #[serde(rename = "waitingForLive")]
+    WaitingForLive,
+    /// Recording a livestream
+    Recording,
     /// Merging video/audio
     Merging,
+    /// Re-encoding with a transcode preset
+    Transcoding,
     /// Completed successfully
     Completed,
+    /// Nothing downloaded (already in the download archive or outside the
+    /// playlist filter)
+    Skipped,
     /// Failed with error
     Failed,
     /// Cancelled by user
     Cancelled,
 }
 
+/// When a queue item may run
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueueSchedule {
+    /// Unix timestamp (seconds) before which the download isn't started
+    #[serde(default)]
+    pub not_before: Option<i64>,
+    /// Unix timestamp (seconds) by which the download must have finished
+    #[serde(default)]
+    pub deadline: Option<i64>,
+}
+
+impl QueueSchedule {
+    /// Whether the start time has come
+    pub fn is_due(&self, now: i64) -> bool {
+        self.not_before.is_none_or(|not_before| not_before <= now)
+    }
+
+    /// Whether the deadline has passed
+    pub fn is_expired(&self, now: i64) -> bool {
+        self.deadline.is_some_and(|deadline| deadline <= now)
+    }
+}
+
 /// A single item in the download queue
 #[derive(Debug, Clone, serde::Serialize)]
 #[serde(rename_all = "camelCase")]
@@ -46,29 +83,52 @@ pub struct QueueItem {
     pub speed: String,
     /// ETA in seconds
     pub eta_seconds: Option<u64>,
+    /// Time recorded so far (livestreams)
+    pub elapsed_seconds: Option<u64>,
     /// Error message if failed
     pub error: Option<String>,
+    /// Why nothing was downloaded, if skipped
+    pub skip_reason: Option<String>,
     /// Output file path if completed
     pub file_path: Option<String>,
+    /// All output files if completed (main file first, then chapter files)
+    pub file_paths: Vec<String>,
+    /// Sidecar subtitle files if completed
+    pub subtitle_files: Vec<String>,
     /// Media title (fetched from URL)
     pub title: Option<String>,
     /// Thumbnail URL
     pub thumbnail: Option<String>,
+    /// Start time and deadline, if scheduled
+    pub schedule: Option<QueueSchedule>,
+    /// Shared by items queued together from one playlist
+    pub group_id: Option<String>,
+    /// Queued despite being a duplicate; downloaded even if it's in the
+    /// download archive
+    #[serde(skip)]
+    pub force: bool,
 }
 
 impl QueueItem {
     pub fn new(id: QueueItemId, config: DownloadConfig) -> Self {
         Self {
             id,
-            config,
             status: QueueItemStatus::Pending,
             progress: 0.0,
             speed: String::new(),
             eta_seconds: None,
+            elapsed_seconds: None,
             error: None,
+            skip_reason: None,
             file_path: None,
+            file_paths: Vec::new(),
+            subtitle_files: Vec::new(),
             title: None,
             thumbnail: None,
+            schedule: None,
+            group_id: config.playlist.as_ref().map(|playlist| playlist.group_id.clone()),
+            force: false,
+            config,
         }
     }
 }
@@ -79,6 +139,8 @@ impl QueueItem {
 pub enum QueueEvent {
     /// Item added to queue
     ItemAdded { item: QueueItem },
+    /// Several items added at once (batch import)
+    ItemsAdded { items: Vec<QueueItem> },
     /// Item status changed
     ItemUpdated { item: QueueItem },
     /// Item removed from queue
@@ -122,22 +184,94 @@ impl DownloadQueue {
 
     /// Adds a new item to the queue
     pub async fn add(&self, config: DownloadConfig) -> QueueItem {
+        self.add_scheduled(config, None, false, 0).await
+    }
+
+    /// Adds a new item that isn't started before `schedule.not_before`
+    ///
+    /// The item is `Scheduled` until then; `now` is the current Unix time.
+    /// A `force`d item bypasses the download archive.
+    pub async fn add_scheduled(
+        &self,
+        config: DownloadConfig,
+        schedule: Option<QueueSchedule>,
+        force: bool,
+        now: i64,
+    ) -> QueueItem {
         let id = self.next_id.fetch_add(1, Ordering::SeqCst);
-        let item = QueueItem::new(id, config);
+        let mut item = QueueItem::new(id, config);
+        item.force = force;
+        if let Some(schedule) = schedule {
+            if !schedule.is_due(now) {
+                item.status = QueueItemStatus::Scheduled;
+            }
+            item.schedule = Some(schedule);
+        }
+
+        self.insert(vec![item.clone()]).await;
+        let _ = self.event_tx.send(QueueEvent::ItemAdded { item: item.clone() });
+
+        item
+    }
+
+    /// Adds several items in order, with a single `ItemsAdded` event
+    pub async fn add_batch(&self, configs: Vec<DownloadConfig>) -> Vec<QueueItem> {
+        let new_items: Vec<QueueItem> = configs
+            .into_iter()
+            .map(|config| QueueItem::new(self.next_id.fetch_add(1, Ordering::SeqCst), config))
+            .collect();
+        if new_items.is_empty() {
+            return new_items;
+        }
+
+        self.insert(new_items.clone()).await;
+        let _ = self.event_tx.send(QueueEvent::ItemsAdded { items: new_items.clone() });
+
+        new_items
+    }
+
+    /// Appends new items to the queue and the pending list
+    async fn insert(&self, new_items: Vec<QueueItem>) {
+        let ids: Vec<QueueItemId> = new_items.iter().map(|item| item.id).collect();
 
         {
             let mut items = self.items.write().await;
-            items.push(item.clone());
+            items.extend(new_items);
         }
 
         {
             let mut pending = self.pending.write().await;
-            pending.push_back(id);
+            pending.extend(ids);
         }
+    }
 
-        let _ = self.event_tx.send(QueueEvent::ItemAdded { item: item.clone() });
-
-        item
+    /// Finds a pending or active item for the same media as `url`
+    pub async fn find_duplicate(&self, url: &str) -> Option<Duplicate> {
+        let key = MediaKey::from_url(url);
+        let items = self.items.read().await;
+        items
+            .iter()
+            .filter(|item| {
+                !matches!(
+                    item.status,
+                    QueueItemStatus::Completed
+                        | QueueItemStatus::Skipped
+                        | QueueItemStatus::Failed
+                        | QueueItemStatus::Cancelled
+                )
+            })
+            .find_map(|item| {
+                key.match_url(&item.config.url).map(|matched_by| Duplicate {
+                    source: DuplicateSource::Queue,
+                    matched_by,
+                    url: item.config.url.clone(),
+                    title: item.title.clone(),
+                    queue_item_id: Some(item.id),
+                    history_id: None,
+                    file_path: None,
+                    downloaded_at: None,
+                })
+            })
     }
 
     /// Gets all queue items
@@ -167,8 +301,12 @@ impl DownloadQueue {
             item.progress = event.percentage;
             item.speed = event.speed.clone();
             item.eta_seconds = event.eta_seconds;
-            if event.status == "merging" {
-                item.status = QueueItemStatus::Merging;
+            item.elapsed_seconds = event.elapsed_seconds;
+            match event.status.as_str() {
+                "recording" => item.status = QueueItemStatus::Recording,
+                "merging" => item.status = QueueItemStatus::Merging,
+                "transcoding" => item.status = QueueItemStatus::Transcoding,
+                _ => {}
             }
             let _ = self.event_tx.send(QueueEvent::ItemUpdated { item: item.clone() });
         }
@@ -176,6 +314,18 @@ impl DownloadQueue {
 
     /// Marks an item as completed
     pub async fn complete(&self, id: QueueItemId, file_path: String) {
+        self.complete_with_files(id, file_path, Vec::new(), Vec::new()).await;
+    }
+
+    /// Marks an item as completed, reporting extra files (split chapters and
+    /// sidecar subtitles)
+    pub async fn complete_with_files(
+        &self,
+        id: QueueItemId,
+        file_path: String,
+        chapter_files: Vec<String>,
+        subtitle_files: Vec<String>,
+    ) {
         {
             let mut active = self.active.write().await;
             active.retain(|&i| i != id);
@@ -185,7 +335,12 @@ impl DownloadQueue {
         if let Some(item) = items.iter_mut().find(|i| i.id == id) {
             item.status = QueueItemStatus::Completed;
             item.progress = 100.0;
+            item.file_paths = std::iter::once(file_path.clone())
+                .filter(|path| !path.is_empty())
+                .chain(chapter_files)
+                .collect();
             item.file_path = Some(file_path);
+            item.subtitle_files = subtitle_files;
             item.speed = String::new();
             item.eta_seconds = None;
             let _ = self.event_tx.send(QueueEvent::ItemUpdated { item: item.clone() });
@@ -209,6 +364,23 @@ impl DownloadQueue {
         }
     }
 
+    /// Marks an item as skipped: yt-dlp had nothing to download
+    pub async fn skip(&self, id: QueueItemId, reason: DownloadError) {
+        {
+            let mut active = self.active.write().await;
+            active.retain(|&i| i != id);
+        }
+
+        let mut items = self.items.write().await;
+        if let Some(item) = items.iter_mut().find(|i| i.id == id) {
+            item.status = QueueItemStatus::Skipped;
+            item.skip_reason = Some(reason.to_string());
+            item.speed = String::new();
+            item.eta_seconds = None;
+            let _ = self.event_tx.send(QueueEvent::ItemUpdated { item: item.clone() });
+        }
+    }
+
     /// Cancels a specific item
     pub async fn cancel(&self, id: QueueItemId) -> Result<(), DownloadError> {
         // Remove from pending if it's there
@@ -220,7 +392,7 @@ impl DownloadQueue {
         // Update status
         let mut items = self.items.write().await;
         if let Some(item) = items.iter_mut().find(|i| i.id == id) {
-            if item.status == QueueItemStatus::Pending {
+            if matches!(item.status, QueueItemStatus::Pending | QueueItemStatus::Scheduled) {
                 item.status = QueueItemStatus::Cancelled;
                 let _ = self.event_tx.send(QueueEvent::ItemUpdated { item: item.clone() });
                 return Ok(());
@@ -241,7 +413,10 @@ impl DownloadQueue {
             // Can only remove terminal items
             if matches!(
                 item.status,
-                QueueItemStatus::Completed | QueueItemStatus::Failed | QueueItemStatus::Cancelled
+                QueueItemStatus::Completed
+                    | QueueItemStatus::Skipped
+                    | QueueItemStatus::Failed
+                    | QueueItemStatus::Cancelled
             ) {
                 items.remove(pos);
                 let _ = self.event_tx.send(QueueEvent::ItemRemoved { id });
@@ -257,17 +432,66 @@ impl DownloadQueue {
         items.retain(|i| {
             !matches!(
                 i.status,
-                QueueItemStatus::Completed | QueueItemStatus::Failed | QueueItemStatus::Cancelled
+                QueueItemStatus::Completed
+                    | QueueItemStatus::Skipped
+                    | QueueItemStatus::Failed
+                    | QueueItemStatus::Cancelled
             )
         });
         let _ = self.event_tx.send(QueueEvent::QueueCleared);
     }
 
-    /// Gets the next pending item and marks it as active
+    /// Starts scheduled items whose time has come and fails the ones whose
+    /// deadline passed before they could start
+    ///
+    /// Returns the failed items.
+    pub async fn release_due(&self, now: i64) -> Vec<QueueItem> {
+        let mut expired = Vec::new();
+        {
+            let mut items = self.items.write().await;
+            for item in items.iter_mut() {
+                let Some(schedule) = item.schedule else { continue };
+                let waiting = matches!(item.status, QueueItemStatus::Pending | QueueItemStatus::Scheduled);
+                if waiting && schedule.is_expired(now) {
+                    item.status = QueueItemStatus::Failed;
+                    item.error = Some(
+                        DownloadError::DeadlineMissed("the download didn't start in time".to_string()).to_string(),
+                    );
+                    expired.push(item.clone());
+                } else if item.status == QueueItemStatus::Scheduled && schedule.is_due(now) {
+                    item.status = QueueItemStatus::Pending;
+                } else {
+                    continue;
+                }
+                let _ = self.event_tx.send(QueueEvent::ItemUpdated { item: item.clone() });
+            }
+        }
+
+        if !expired.is_empty() {
+            let mut pending = self.pending.write().await;
+            pending.retain(|id| !expired.iter().any(|item| item.id == *id));
+        }
+
+        expired
+    }
+
+    /// IDs of items that can start now (scheduled ones are skipped)
+    async fn ready_ids(&self) -> Vec<QueueItemId> {
+        let items = self.items.read().await;
+        items
+            .iter()
+            .filter(|item| item.status == QueueItemStatus::Pending)
+            .map(|item| item.id)
+            .collect()
+    }
+
+    /// Gets the next pending item that can start now and marks it as active
     pub async fn pop_next(&self) -> Option<QueueItem> {
+        let ready = self.ready_ids().await;
         let id = {
             let mut pending = self.pending.write().await;
-            pending.pop_front()?
+            let pos = pending.iter().position(|id| ready.contains(id))?;
+            pending.remove(pos)?
         };
 
         {
@@ -300,9 +524,10 @@ impl DownloadQueue {
         self.pending.read().await.len()
     }
 
-    /// Checks if there are pending items
+    /// Checks if there are pending items that can start now
     pub async fn has_pending(&self) -> bool {
-        !self.pending.read().await.is_empty()
+        let ready = self.ready_ids().await;
+        self.pending.read().await.iter().any(|id| ready.contains(id))
     }
 
     /// Sets media info for an item
@@ -383,7 +608,8 @@ impl DownloadQueue {
         let mut pending = self.pending.write().await;
         
         for item in items.iter() {
-            if item.status == QueueItemStatus::Pending && !pending.contains(&item.id) {
+            let waiting = matches!(item.status, QueueItemStatus::Pending | QueueItemStatus::Scheduled);
+            if waiting && !pending.contains(&item.id) {
                 pending.push_back(item.id);
             }
         }
@@ -400,3 +626,118 @@ pub fn create_download_queue(
 ) -> SharedDownloadQueue {
     Arc::new(DownloadQueue::new(max_concurrent, event_tx))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_queue() -> DownloadQueue {
+        let (tx, _rx) = mpsc::unbounded_channel();
+        DownloadQueue::new(3, tx)
+    }
+
+    fn test_config(url: &str) -> DownloadConfig {
+        serde_json::from_value(serde_json::json!({
+            "url": url,
+            "format": "video-mp4",
+            "quality": "best",
+            "outputFolder": "C:\\Downloads",
+            "embedSubtitles": false,
+            "cookiesFromBrowser": null
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_queue_schedule() {
+        let schedule = QueueSchedule {
+            not_before: Some(100),
+            deadline: Some(200),
+        };
+        assert!(!schedule.is_due(99));
+        assert!(schedule.is_due(100));
+        assert!(!schedule.is_expired(199));
+        assert!(schedule.is_expired(200));
+        assert!(QueueSchedule::default().is_due(0));
+        assert!(!QueueSchedule::default().is_expired(i64::MAX));
+    }
+
+    #[tokio::test]
+    async fn test_scheduled_items_wait_for_their_time() {
+        let queue = test_queue();
+        let schedule = QueueSchedule {
+            not_before: Some(1_000),
+            deadline: None,
+        };
+        let scheduled = queue
+            .add_scheduled(test_config("https://example.com/later"), Some(schedule), false, 500)
+            .await;
+        assert_eq!(scheduled.status, QueueItemStatus::Scheduled);
+
+        // Only unscheduled items can start
+        let now = queue.add(test_config("https://example.com/now")).await;
+        assert_eq!(queue.pop_next().await.map(|item| item.id), Some(now.id));
+        assert!(!queue.has_pending().await);
+        assert!(queue.pop_next().await.is_none());
+
+        assert!(queue.release_due(999).await.is_empty());
+        assert!(!queue.has_pending().await);
+
+        queue.release_due(1_000).await;
+        assert_eq!(queue.get(scheduled.id).await.unwrap().status, QueueItemStatus::Pending);
+        assert_eq!(queue.pop_next().await.map(|item| item.id), Some(scheduled.id));
+    }
+
+    #[tokio::test]
+    async fn test_missed_deadline_fails_item() {
+        let queue = test_queue();
+        let schedule = QueueSchedule {
+            not_before: Some(100),
+            deadline: Some(200),
+        };
+        let item = queue
+            .add_scheduled(test_config("https://example.com/a"), Some(schedule), false, 0)
+            .await;
+
+        let expired = queue.release_due(250).await;
+        assert_eq!(expired.len(), 1);
+        assert_eq!(expired[0].id, item.id);
+        assert_eq!(queue.get(item.id).await.unwrap().status, QueueItemStatus::Failed);
+        assert!(!queue.has_pending().await);
+    }
+
+    #[tokio::test]
+    async fn test_skipped_item_keeps_reason_and_can_be_cleared() {
+        let queue = test_queue();
+        let item = queue.add(test_config("https://example.com/a")).await;
+        queue.pop_next().await;
+
+        queue.skip(item.id, DownloadError::AlreadyArchived("A".to_string())).await;
+        let skipped = queue.get(item.id).await.unwrap();
+        assert_eq!(skipped.status, QueueItemStatus::Skipped);
+        assert!(skipped.skip_reason.unwrap().contains("A"));
+        assert!(skipped.error.is_none());
+        assert_eq!(queue.active_count().await, 0);
+        assert!(queue.find_duplicate("https://example.com/a").await.is_none());
+
+        queue.clear_completed().await;
+        assert!(queue.get(item.id).await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_add_batch_sends_one_event() {
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let queue = DownloadQueue::new(3, tx);
+        let items = queue
+            .add_batch(vec![test_config("https://example.com/a"), test_config("https://example.com/b")])
+            .await;
+
+        match rx.try_recv() {
+            Ok(QueueEvent::ItemsAdded { items: added }) => assert_eq!(added.len(), 2),
+            other => panic!("unexpected {:?}", other),
+        }
+        assert!(rx.try_recv().is_err());
+        assert_eq!(queue.pop_next().await.map(|item| item.id), Some(items[0].id));
+        assert_eq!(queue.pop_next().await.map(|item| item.id), Some(items[1].id));
+    }
+}
@@ -0,0 +1,613 @@
+//! Filename template engine
+//!
+//! Parses user filename templates like
+//! `{uploader|channel|Unknown}/{date:%Y-%m}/{title:80} [{id}]` and either
+//! converts them to a yt-dlp output template or renders them locally against
+//! a `MediaInfo` for previews.
+//!
+//! Syntax:
+//! - `{field}` - placeholder (see `TemplateField`)
+//! - `{a|b|Default}` - first non-empty field, optional literal default last
+//! - `{title:80}` - truncate to 80 characters
+//! - `{date:%Y-%m}` - strftime formatting for the upload date
+//! - `/` or `\` - subdirectory separator
+
+use std::fmt;
+
+use chrono::format::{Fixed, Item, Numeric, StrftimeItems};
+use chrono::NaiveDate;
+
+use crate::models::{DownloadError, MediaInfo, PlaylistContext};
+use crate::utils::sanitize::{sanitize_filename_with, sanitize_fragment, SanitizeOptions};
+
+/// Value yt-dlp substitutes for missing fields without a default
+const MISSING_VALUE: &str = "NA";
+
+/// Placeholders supported in filename templates
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TemplateField {
+    Title,
+    Uploader,
+    Channel,
+    Date,
+    Quality,
+    Resolution,
+    Duration,
+    Id,
+    PlaylistIndex,
+    PlaylistTitle,
+    Ext,
+}
+
+impl TemplateField {
+    /// Every supported placeholder
+    pub const ALL: [TemplateField; 11] = [
+        TemplateField::Title,
+        TemplateField::Uploader,
+        TemplateField::Channel,
+        TemplateField::Date,
+        TemplateField::Quality,
+        TemplateField::Resolution,
+        TemplateField::Duration,
+        TemplateField::Id,
+        TemplateField::PlaylistIndex,
+        TemplateField::PlaylistTitle,
+        TemplateField::Ext,
+    ];
+
+    /// Placeholder name used in templates
+    pub fn name(&self) -> &'static str {
+        match self {
+            TemplateField::Title => "title",
+            TemplateField::Uploader => "uploader",
+            TemplateField::Channel => "channel",
+            TemplateField::Date => "date",
+            TemplateField::Quality => "quality",
+            TemplateField::Resolution => "resolution",
+            TemplateField::Duration => "duration",
+            TemplateField::Id => "id",
+            TemplateField::PlaylistIndex => "playlist_index",
+            TemplateField::PlaylistTitle => "playlist_title",
+            TemplateField::Ext => "ext",
+        }
+    }
+
+    /// Corresponding yt-dlp output template field
+    fn ytdlp_name(&self) -> &'static str {
+        match self {
+            TemplateField::Date => "upload_date",
+            TemplateField::Quality => "height",
+            other => other.name(),
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        TemplateField::ALL.into_iter().find(|field| field.name() == name)
+    }
+
+    /// Value known up front for items queued from a playlist
+    ///
+    /// Those items are downloaded with `--no-playlist`, so yt-dlp itself
+    /// doesn't know their playlist fields.
+    fn playlist_value(&self, playlist: &PlaylistContext) -> Option<String> {
+        match self {
+            TemplateField::PlaylistIndex => Some(playlist.padded_index()),
+            TemplateField::PlaylistTitle => Some(playlist.title.clone()).filter(|title| !title.is_empty()),
+            _ => None,
+        }
+    }
+}
+
+/// Formatting applied to a placeholder
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Modifier {
+    /// Keep at most this many characters
+    Truncate(usize),
+    /// strftime format for dates
+    DateFormat(String),
+}
+
+/// A `{...}` placeholder
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Placeholder {
+    /// Fields tried in order
+    fields: Vec<TemplateField>,
+    /// Literal used when every field is empty
+    default: Option<String>,
+    modifier: Option<Modifier>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Segment {
+    Literal(String),
+    Placeholder(Placeholder),
+}
+
+/// A parsed filename template
+///
+/// Each component is one path segment (subdirectories are separate components).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FilenameTemplate {
+    components: Vec<Vec<Segment>>,
+}
+
+impl FilenameTemplate {
+    /// Parses and validates a template
+    pub fn parse(template: &str) -> Result<Self, DownloadError> {
+        let mut components: Vec<Vec<Segment>> = Vec::new();
+        let mut segments: Vec<Segment> = Vec::new();
+        let mut literal = String::new();
+        let mut chars = template.chars();
+
+        while let Some(c) = chars.next() {
+            match c {
+                '{' => {
+                    let mut body = String::new();
+                    let mut closed = false;
+                    for c in chars.by_ref() {
+                        match c {
+                            '}' => {
+                                closed = true;
+                                break;
+                            }
+                            '{' => return Err(invalid("nested '{'")),
+                            _ => body.push(c),
+                        }
+                    }
+                    if !closed {
+                        return Err(invalid(&format!("unclosed placeholder \"{{{}\"", body)));
+                    }
+
+                    flush_literal(&mut literal, &mut segments);
+                    segments.push(Segment::Placeholder(parse_placeholder(&body)?));
+                }
+                '}' => return Err(invalid("unmatched '}'")),
+                '/' | '\\' => {
+                    flush_literal(&mut literal, &mut segments);
+                    components.push(std::mem::take(&mut segments));
+                }
+                _ => literal.push(c),
+            }
+        }
+        flush_literal(&mut literal, &mut segments);
+        components.push(segments);
+
+        // Drop empty, "." and ".." components so templates can't escape the output folder
+        components.retain(|segments| match segments.as_slice() {
+            [] => false,
+            [Segment::Literal(text)] => !text.trim_matches(|c: char| c.is_whitespace() || c == '.').is_empty(),
+            _ => true,
+        });
+
+        if components.is_empty() {
+            return Err(invalid("template is empty"));
+        }
+
+        // yt-dlp needs the extension; add it when the template doesn't place it
+        let has_ext = components
+            .iter()
+            .flatten()
+            .any(|segment| matches!(segment, Segment::Placeholder(p) if p.fields.contains(&TemplateField::Ext)));
+        if !has_ext {
+            if let Some(last) = components.last_mut() {
+                last.push(Segment::Literal(".".to_string()));
+                last.push(Segment::Placeholder(Placeholder {
+                    fields: vec![TemplateField::Ext],
+                    default: None,
+                    modifier: None,
+                }));
+            }
+        }
+
+        Ok(Self { components })
+    }
+
+    /// Converts to a yt-dlp output template (`-o`)
+    pub fn to_ytdlp(&self) -> String {
+        self.to_ytdlp_for_playlist(None)
+    }
+
+    /// Converts to a yt-dlp output template, filling in the playlist fields
+    /// of an item queued from a playlist as literals
+    pub fn to_ytdlp_for_playlist(&self, playlist: Option<&PlaylistContext>) -> String {
+        self.components
+            .iter()
+            .map(|segments| {
+                segments
+                    .iter()
+                    .map(|segment| segment_to_ytdlp(segment, playlist))
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("/")
+    }
+
+    /// Renders the template locally, sanitizing each path component
+    ///
+    /// Missing values become "NA" like they do in yt-dlp.
+    pub fn render(&self, info: &MediaInfo, ext: &str, options: &SanitizeOptions) -> String {
+        self.components
+            .iter()
+            .map(|segments| {
+                let raw: String = segments
+                    .iter()
+                    .map(|segment| match segment {
+                        Segment::Literal(text) => text.clone(),
+                        Segment::Placeholder(placeholder) => render_placeholder(placeholder, info, ext),
+                    })
+                    .collect();
+                sanitize_filename_with(&raw, options)
+            })
+            .collect::<Vec<_>>()
+            .join("/")
+    }
+}
+
+impl fmt::Display for FilenameTemplate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.to_ytdlp())
+    }
+}
+
+fn invalid(reason: &str) -> DownloadError {
+    DownloadError::InvalidTemplate(reason.to_string())
+}
+
+fn flush_literal(literal: &mut String, segments: &mut Vec<Segment>) {
+    if !literal.is_empty() {
+        segments.push(Segment::Literal(std::mem::take(literal)));
+    }
+}
+
+/// Parses the inside of `{...}`: `field|field|Default:modifier`
+fn parse_placeholder(body: &str) -> Result<Placeholder, DownloadError> {
+    let (expr, modifier) = match body.split_once(':') {
+        Some((expr, modifier)) => (expr, Some(modifier.trim())),
+        None => (body, None),
+    };
+
+    let parts: Vec<&str> = expr.split('|').map(str::trim).collect();
+    let mut fields = Vec::new();
+    let mut default = None;
+
+    for (index, part) in parts.iter().enumerate() {
+        match TemplateField::from_name(part) {
+            Some(field) => fields.push(field),
+            None if part.is_empty() => return Err(invalid("empty placeholder")),
+            // Only the last alternative may be a literal default
+            None if index > 0 && index == parts.len() - 1 => {
+                if part.contains(['%', '(', ')', '&', ',']) {
+                    return Err(invalid(&format!("default \"{}\" contains reserved characters", part)));
+                }
+                default = Some(part.to_string());
+            }
+            None => return Err(invalid(&format!("unknown placeholder \"{{{}}}\"", part))),
+        }
+    }
+
+    let modifier = match modifier {
+        None => None,
+        Some(m) if m.contains('%') => {
+            if fields.iter().any(|field| *field != TemplateField::Date) {
+                return Err(invalid("date formats can only be used with {date}"));
+            }
+            if !StrftimeItems::new(m).all(|item| is_date_item(&item)) {
+                return Err(invalid(&format!("invalid date format \"{}\"", m)));
+            }
+            Some(Modifier::DateFormat(m.to_string()))
+        }
+        Some(m) => match m.parse::<usize>() {
+            Ok(0) | Err(_) => return Err(invalid(&format!("invalid modifier \"{}\"", m))),
+            Ok(length) => Some(Modifier::Truncate(length)),
+        },
+    };
+
+    Ok(Placeholder { fields, default, modifier })
+}
+
+/// Whether a strftime item can be rendered from a date alone
+///
+/// Upload dates carry no time of day, and chrono panics when asked to format
+/// one, so time fields are rejected along with invalid specifiers.
+fn is_date_item(item: &Item) -> bool {
+    match item {
+        Item::Literal(_) | Item::OwnedLiteral(_) | Item::Space(_) | Item::OwnedSpace(_) => true,
+        Item::Numeric(numeric, _) => matches!(
+            numeric,
+            Numeric::Year
+                | Numeric::YearDiv100
+                | Numeric::YearMod100
+                | Numeric::IsoYear
+                | Numeric::IsoYearDiv100
+                | Numeric::IsoYearMod100
+                | Numeric::Month
+                | Numeric::Day
+                | Numeric::WeekFromSun
+                | Numeric::WeekFromMon
+                | Numeric::IsoWeek
+                | Numeric::NumDaysFromSun
+                | Numeric::WeekdayFromMon
+                | Numeric::Ordinal
+        ),
+        Item::Fixed(fixed) => matches!(
+            fixed,
+            Fixed::ShortMonthName | Fixed::LongMonthName | Fixed::ShortWeekdayName | Fixed::LongWeekdayName
+        ),
+        _ => false,
+    }
+}
+
+/// yt-dlp syntax: `%(field>strf,alternate&replacement|default).Ns`
+fn segment_to_ytdlp(segment: &Segment, playlist: Option<&PlaylistContext>) -> String {
+    match segment {
+        Segment::Literal(text) => literal_to_ytdlp(text),
+        Segment::Placeholder(placeholder) => {
+            if let Some(value) = playlist.and_then(|playlist| known_playlist_value(placeholder, playlist)) {
+                return literal_to_ytdlp(&value);
+            }
+
+            let mut expr = String::new();
+            for (index, field) in placeholder.fields.iter().enumerate() {
+                if index > 0 {
+                    expr.push(',');
+                }
+                expr.push_str(field.ytdlp_name());
+                if let Some(Modifier::DateFormat(format)) = &placeholder.modifier {
+                    expr.push('>');
+                    expr.push_str(format);
+                }
+            }
+            // {quality} renders as "1080p"
+            if placeholder.fields.first() == Some(&TemplateField::Quality) {
+                expr.push_str("&{}p");
+            }
+            if let Some(default) = &placeholder.default {
+                expr.push('|');
+                expr.push_str(default);
+            }
+
+            match &placeholder.modifier {
+                Some(Modifier::Truncate(length)) => format!("%({}).{}s", expr, length),
+                _ => format!("%({})s", expr),
+            }
+        }
+    }
+}
+
+fn literal_to_ytdlp(text: &str) -> String {
+    sanitize_fragment(text, &SanitizeOptions::default()).replace('%', "%%")
+}
+
+/// Resolves a placeholder that starts with a playlist field
+///
+/// Placeholders that start with a metadata field are left to yt-dlp, which
+/// knows those values better.
+fn known_playlist_value(placeholder: &Placeholder, playlist: &PlaylistContext) -> Option<String> {
+    let value = placeholder
+        .fields
+        .first()?
+        .playlist_value(playlist)?;
+    Some(match &placeholder.modifier {
+        Some(Modifier::Truncate(length)) => value.chars().take(*length).collect(),
+        _ => value,
+    })
+}
+
+fn render_placeholder(placeholder: &Placeholder, info: &MediaInfo, ext: &str) -> String {
+    let value = placeholder
+        .fields
+        .iter()
+        .find_map(|field| field_value(*field, info, ext, placeholder.modifier.as_ref()))
+        .map(|value| {
+            if placeholder.fields.first() == Some(&TemplateField::Quality) {
+                format!("{}p", value)
+            } else {
+                value
+            }
+        });
+
+    let value = value
+        .or_else(|| placeholder.default.clone())
+        .unwrap_or_else(|| MISSING_VALUE.to_string());
+
+    match &placeholder.modifier {
+        Some(Modifier::Truncate(length)) => value.chars().take(*length).collect(),
+        _ => value,
+    }
+}
+
+/// Looks up a field; None when the value is missing or empty
+fn field_value(field: TemplateField, info: &MediaInfo, ext: &str, modifier: Option<&Modifier>) -> Option<String> {
+    let value = match field {
+        TemplateField::Title => Some(info.title.clone()),
+        TemplateField::Uploader => info.uploader.clone(),
+        TemplateField::Channel => info.channel.clone(),
+        TemplateField::Date => {
+            let date = info.upload_date.clone()?;
+            match modifier {
+                Some(Modifier::DateFormat(format)) => {
+                    let parsed = NaiveDate::parse_from_str(&date, "%Y%m%d").ok()?;
+                    Some(parsed.format(format).to_string())
+                }
+                _ => Some(date),
+            }
+        }
+        TemplateField::Quality => info.height.map(|height| height.to_string()),
+        TemplateField::Resolution => match (info.width, info.height) {
+            (Some(width), Some(height)) => Some(format!("{}x{}", width, height)),
+            _ => None,
+        },
+        TemplateField::Duration => info.duration.map(|seconds| (seconds as u64).to_string()),
+        TemplateField::Id => info.id.clone(),
+        TemplateField::PlaylistIndex | TemplateField::PlaylistTitle => None,
+        TemplateField::Ext => Some(ext.to_string()),
+    };
+
+    value.filter(|value| !value.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    fn sample_info() -> MediaInfo {
+        MediaInfo {
+            title: "Never Gonna Give You Up".to_string(),
+            thumbnail: None,
+            duration: Some(213.0),
+            uploader: Some("Rick Astley".to_string()),
+            filesize_approx: None,
+            id: Some("dQw4w9WgXcQ".to_string()),
+            channel: None,
+            upload_date: Some("20091025".to_string()),
+            width: Some(1920),
+            height: Some(1080),
+            live_status: None,
+        }
+    }
+
+    fn preview(template: &str) -> String {
+        FilenameTemplate::parse(template)
+            .unwrap()
+            .render(&sample_info(), "mp4", &SanitizeOptions::default())
+    }
+
+    #[test]
+    fn test_simple_placeholders() {
+        let template = FilenameTemplate::parse("{title} [{id}]").unwrap();
+        assert_eq!(template.to_ytdlp(), "%(title)s [%(id)s].%(ext)s");
+        assert_eq!(preview("{title} [{id}]"), "Never Gonna Give You Up [dQw4w9WgXcQ].mp4");
+    }
+
+    #[test]
+    fn test_unknown_placeholder_rejected() {
+        assert!(matches!(
+            FilenameTemplate::parse("{titel}"),
+            Err(DownloadError::InvalidTemplate(_))
+        ));
+        assert!(FilenameTemplate::parse("{title").is_err());
+        assert!(FilenameTemplate::parse("title}").is_err());
+        assert!(FilenameTemplate::parse("{}").is_err());
+        assert!(FilenameTemplate::parse("{title:abc}").is_err());
+        assert!(FilenameTemplate::parse("{title:%Y}").is_err());
+        assert!(FilenameTemplate::parse("../..").is_err());
+    }
+
+    #[test]
+    fn test_fallbacks() {
+        let template = FilenameTemplate::parse("{channel|uploader|Unknown}").unwrap();
+        assert_eq!(template.to_ytdlp(), "%(channel,uploader|Unknown)s.%(ext)s");
+        assert_eq!(preview("{channel|uploader|Unknown}"), "Rick Astley.mp4");
+        assert_eq!(preview("{channel|Unknown}"), "Unknown.mp4");
+        assert_eq!(preview("{channel}"), "NA.mp4");
+    }
+
+    #[test]
+    fn test_truncation() {
+        let template = FilenameTemplate::parse("{title:5}").unwrap();
+        assert_eq!(template.to_ytdlp(), "%(title).5s.%(ext)s");
+        assert_eq!(preview("{title:5}"), "Never.mp4");
+    }
+
+    #[test]
+    fn test_date_format() {
+        let template = FilenameTemplate::parse("{date:%Y-%m}/{title}").unwrap();
+        assert_eq!(template.to_ytdlp(), "%(upload_date>%Y-%m)s/%(title)s.%(ext)s");
+        assert_eq!(preview("{date:%Y-%m}/{title}"), "2009-10/Never Gonna Give You Up.mp4");
+        assert_eq!(preview("{date}"), "20091025.mp4");
+    }
+
+    #[test]
+    fn test_date_format_rejects_time_and_invalid_specifiers() {
+        for template in ["{date:%H}", "{date:%Y-%m %H:%M}", "{date:%Y-%!}", "{date:%}"] {
+            assert!(
+                matches!(FilenameTemplate::parse(template), Err(DownloadError::InvalidTemplate(_))),
+                "{} should be rejected",
+                template
+            );
+        }
+        assert_eq!(preview("{date:%a %d %B %Y}"), "Sun 25 October 2009.mp4");
+    }
+
+    #[test]
+    fn test_quality_and_resolution() {
+        let template = FilenameTemplate::parse("{title} {quality}").unwrap();
+        assert_eq!(template.to_ytdlp(), "%(title)s %(height&{}p)s.%(ext)s");
+        assert_eq!(preview("{quality} {resolution}"), "1080p 1920x1080.mp4");
+    }
+
+    #[test]
+    fn test_subdirectories_and_literals() {
+        let template = FilenameTemplate::parse("../Music: {uploader}//100% {title}.{ext}").unwrap();
+        assert_eq!(template.to_ytdlp(), "Music_ %(uploader)s/100%% %(title)s.%(ext)s");
+        assert_eq!(
+            preview("{uploader}\\{title}"),
+            "Rick Astley/Never Gonna Give You Up.mp4"
+        );
+    }
+
+    #[test]
+    fn test_playlist_fields_filled_in() {
+        let template = FilenameTemplate::parse("{playlist_title}/{playlist_index} - {title|playlist_title}").unwrap();
+        assert_eq!(
+            template.to_ytdlp(),
+            "%(playlist_title)s/%(playlist_index)s - %(title,playlist_title)s.%(ext)s"
+        );
+
+        let playlist = PlaylistContext {
+            group_id: "g".to_string(),
+            index: 3,
+            count: 12,
+            title: "Best of: 100%".to_string(),
+        };
+        assert_eq!(
+            template.to_ytdlp_for_playlist(Some(&playlist)),
+            "Best of_ 100%%/03 - %(title,playlist_title)s.%(ext)s"
+        );
+    }
+
+    #[test]
+    fn test_preview_sanitizes_values() {
+        let mut info = sample_info();
+        info.title = "What? / Why: *now*".to_string();
+        let template = FilenameTemplate::parse("{title}").unwrap();
+        assert_eq!(
+            template.render(&info, "mp3", &SanitizeOptions::default()),
+            "What_ _ Why_ _now_.mp3"
+        );
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(100))]
+
+        /// Property: every known placeholder parses and maps to a yt-dlp field
+        #[test]
+        fn prop_known_fields_parse(index in 0usize..TemplateField::ALL.len(), prefix in "[a-zA-Z ]{0,10}") {
+            let field = TemplateField::ALL[index];
+            let template = format!("{}{{{}}}", prefix, field.name());
+            let parsed = FilenameTemplate::parse(&template).unwrap();
+            let ytdlp = parsed.to_ytdlp();
+            let expected = format!("%({}", field.ytdlp_name());
+            prop_assert!(ytdlp.contains(&expected));
+            prop_assert!(ytdlp.contains("%(ext)s"));
+        }
+
+        /// Property: unknown placeholders are always rejected
+        #[test]
+        fn prop_unknown_fields_rejected(name in "[a-z_]{1,12}") {
+            prop_assume!(TemplateField::from_name(&name).is_none());
+            let template = format!("{{{}}}", name);
+            prop_assert!(FilenameTemplate::parse(&template).is_err());
+        }
+
+        /// Property: rendered previews never escape the output folder
+        #[test]
+        fn prop_render_stays_relative(template in "[a-z./\\\\ ]{0,20}\\{title\\}") {
+            let parsed = FilenameTemplate::parse(&template).unwrap();
+            let rendered = parsed.render(&sample_info(), "mp4", &SanitizeOptions::default());
+            prop_assert!(!rendered.starts_with('/'));
+            prop_assert!(rendered.split('/').all(|component| component != ".." && component != "." && !component.is_empty()));
+        }
+    }
+}
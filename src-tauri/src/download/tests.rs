@@ -3,25 +3,53 @@
 //! **Feature: MediaGrab, Property 4: yt-dlp Argument Builder Correctness**
 //! **Validates: Requirements 2.3, 2.4**
 
-use super::args::ArgumentBuilder;
-use crate::models::DownloadConfig;
+use std::path::PathBuf;
+
+use super::args::{build_subtitle_args, ArgumentBuilder};
+use crate::models::{
+    AudioCodec, DownloadConfig, LiveOptions, LiveStart, OrganizeRule, OutputFormat, PlaylistContext, PlaylistFilter,
+    Quality, SponsorBlockCategory, SponsorBlockOptions, SubtitleOptions, VideoCodec, VideoContainer,
+};
 use proptest::prelude::*;
 
 /// Generate arbitrary format values
-fn arb_format() -> impl Strategy<Value = String> {
+fn arb_format() -> impl Strategy<Value = OutputFormat> {
     prop_oneof![
-        Just("video-mp4".to_string()),
-        Just("audio-mp3".to_string()),
-        Just("audio-best".to_string()),
+        Just(OutputFormat::Video(VideoContainer::Mp4)),
+        Just(OutputFormat::Audio(AudioCodec::Mp3)),
+        Just(OutputFormat::Audio(AudioCodec::Best)),
     ]
 }
 
 /// Generate arbitrary quality values
-fn arb_quality() -> impl Strategy<Value = String> {
+fn arb_quality() -> impl Strategy<Value = Quality> {
+    prop_oneof![
+        Just(Quality::Best),
+        Just(Quality::P2160),
+        Just(Quality::P1440),
+        Just(Quality::P1080),
+        Just(Quality::P720),
+        Just(Quality::P480),
+        Just(Quality::P360),
+    ]
+}
+
+/// Generate arbitrary fps caps
+fn arb_max_fps() -> impl Strategy<Value = Option<u32>> {
+    prop_oneof![
+        Just(None),
+        Just(Some(30)),
+        Just(Some(60)),
+    ]
+}
+
+/// Generate arbitrary preferred video codecs
+fn arb_video_codec() -> impl Strategy<Value = Option<VideoCodec>> {
     prop_oneof![
-        Just("best".to_string()),
-        Just("1080p".to_string()),
-        Just("720p".to_string()),
+        Just(None),
+        Just(Some(VideoCodec::Av1)),
+        Just(Some(VideoCodec::Vp9)),
+        Just(Some(VideoCodec::H264)),
     ]
 }
 
@@ -56,9 +84,23 @@ fn arb_download_config() -> impl Strategy<Value = DownloadConfig> {
         any::<bool>(),
         arb_browser(),
         arb_filename_template(),
+        arb_max_fps(),
+        any::<bool>(),
+        arb_video_codec(),
     )
         .prop_map(
-            |(url, format, quality, output_folder, embed_subtitles, cookies_from_browser, filename_template)| {
+            |(
+                url,
+                format,
+                quality,
+                output_folder,
+                embed_subtitles,
+                cookies_from_browser,
+                filename_template,
+                max_fps,
+                allow_hdr,
+                video_codec,
+            )| {
                 DownloadConfig {
                     url,
                     format,
@@ -69,6 +111,20 @@ fn arb_download_config() -> impl Strategy<Value = DownloadConfig> {
                     filename_template,
                     proxy_url: None,
                     cookies_file_path: None,
+                    max_fps,
+                    allow_hdr,
+                    video_codec,
+                    sections: Vec::new(),
+                    chapter_patterns: Vec::new(),
+                    sponsorblock: SponsorBlockOptions::default(),
+                    split_chapters: false,
+                    subtitles: SubtitleOptions::default(),
+                    organize_rules: Vec::new(),
+                    transcode_preset: None,
+                    loudness_target_lufs: None,
+                    live: LiveOptions::default(),
+                    playlist: None,
+                    playlist_filter: PlaylistFilter::default(),
                 }
             },
         )
@@ -100,8 +156,8 @@ proptest! {
         let format_selector = &args[f_index.unwrap() + 1];
 
         // Verify format selector based on format and quality
-        match config.format.as_str() {
-            "audio-mp3" | "audio-best" => {
+        match config.format {
+            OutputFormat::Audio(_) => {
                 // Audio formats should use bestaudio selector
                 prop_assert!(
                     format_selector.contains("bestaudio"),
@@ -109,9 +165,9 @@ proptest! {
                     format_selector
                 );
             }
-            "video-mp4" => {
-                match config.quality.as_str() {
-                    "1080p" => {
+            OutputFormat::Video(_) => {
+                match config.quality {
+                    Quality::P1080 => {
                         // Should have height<=1080 constraint with fallback
                         prop_assert!(
                             format_selector.contains("height<=1080"),
@@ -125,7 +181,7 @@ proptest! {
                             format_selector
                         );
                     }
-                    "720p" => {
+                    Quality::P720 => {
                         // Should have height<=720 constraint with fallback
                         prop_assert!(
                             format_selector.contains("height<=720"),
@@ -139,7 +195,7 @@ proptest! {
                             format_selector
                         );
                     }
-                    "best" | _ => {
+                    Quality::Best => {
                         // Best quality should use bestvideo+bestaudio
                         prop_assert!(
                             format_selector.contains("bestvideo") && format_selector.contains("bestaudio"),
@@ -147,16 +203,63 @@ proptest! {
                             format_selector
                         );
                     }
+                    other => {
+                        // Remaining ladder steps follow the same height<=N pattern
+                        let height = other.max_height().unwrap();
+                        prop_assert!(
+                            format_selector.contains(&format!("height<={}", height)),
+                            "{} quality should have height<={} constraint, got: {}",
+                            other,
+                            height,
+                            format_selector
+                        );
+                        prop_assert!(
+                            format_selector.contains("/"),
+                            "Format selector should have fallback logic, got: {}",
+                            format_selector
+                        );
+                    }
+                }
+
+                // fps cap is enforced in the selector
+                if let Some(fps) = config.max_fps {
+                    prop_assert!(
+                        format_selector.contains(&format!("fps<={}", fps)),
+                        "fps cap should be in selector, got: {}",
+                        format_selector
+                    );
                 }
             }
-            _ => {
-                // Unknown format defaults to video behavior
-                prop_assert!(
-                    format_selector.contains("bestvideo") || format_selector.contains("best"),
-                    "Unknown format should default to video behavior, got: {}",
-                    format_selector
-                );
+        }
+    }
+
+    /// Property: Format sorting reflects resolution, fps, HDR and codec preferences
+    ///
+    /// **Validates: Requirements 2.3, 2.4**
+    #[test]
+    fn prop_format_sort_reflects_preferences(
+        config in arb_download_config()
+    ) {
+        let builder = ArgumentBuilder::new(config.clone());
+        let args = builder.build();
+        let sort = args
+            .iter()
+            .position(|a| a == "-S")
+            .map(|i| args[i + 1].clone());
+
+        if config.format.is_audio() {
+            prop_assert!(sort.is_none(), "Audio formats should not use format sorting");
+        } else {
+            let sort = sort.unwrap_or_default();
+            if let Some(height) = config.quality.max_height() {
+                let expected = format!("res:{}", height);
+                prop_assert!(sort.contains(&expected), "Sort should contain {}, got: {}", expected, sort);
+            }
+            if let Some(codec) = config.video_codec {
+                let expected = format!("vcodec:{}", codec.sort_value());
+                prop_assert!(sort.contains(&expected), "Sort should contain {}, got: {}", expected, sort);
             }
+            prop_assert_eq!(sort.contains("hdr:sdr"), !config.allow_hdr);
         }
     }
 
@@ -173,8 +276,8 @@ proptest! {
         let has_extract_audio = args.contains(&"-x".to_string());
         let has_audio_format_mp3 = args.windows(2).any(|w| w == ["--audio-format", "mp3"]);
 
-        match config.format.as_str() {
-            "audio-mp3" => {
+        match config.format {
+            OutputFormat::Audio(AudioCodec::Mp3) => {
                 // MP3 format should have -x and --audio-format mp3
                 prop_assert!(
                     has_extract_audio,
@@ -185,7 +288,7 @@ proptest! {
                     "audio-mp3 format should have --audio-format mp3"
                 );
             }
-            "audio-best" => {
+            OutputFormat::Audio(AudioCodec::Best) => {
                 // Best audio should have -x but NOT --audio-format (keep original)
                 prop_assert!(
                     has_extract_audio,
@@ -196,7 +299,13 @@ proptest! {
                     "audio-best format should NOT have --audio-format mp3"
                 );
             }
-            "video-mp4" | _ => {
+            OutputFormat::Audio(_) => {
+                prop_assert!(
+                    has_extract_audio,
+                    "audio formats should have -x flag"
+                );
+            }
+            OutputFormat::Video(_) => {
                 // Video formats should NOT have audio extraction
                 prop_assert!(
                     !has_extract_audio,
@@ -320,7 +429,7 @@ proptest! {
 
         let has_merge_format = args.windows(2).any(|w| w == ["--merge-output-format", "mp4"]);
 
-        if config.format == "video-mp4" {
+        if config.format == OutputFormat::Video(VideoContainer::Mp4) {
             prop_assert!(
                 has_merge_format,
                 "video-mp4 format should have --merge-output-format mp4"
@@ -336,14 +445,28 @@ mod unit_tests {
     fn create_test_config() -> DownloadConfig {
         DownloadConfig {
             url: "https://youtube.com/watch?v=test123".to_string(),
-            format: "video-mp4".to_string(),
-            quality: "best".to_string(),
+            format: OutputFormat::Video(VideoContainer::Mp4),
+            quality: Quality::Best,
             output_folder: "C:\\Downloads".to_string(),
             embed_subtitles: false,
             cookies_from_browser: None,
             filename_template: None,
             proxy_url: None,
             cookies_file_path: None,
+            max_fps: None,
+            allow_hdr: true,
+            video_codec: None,
+            sections: Vec::new(),
+            chapter_patterns: Vec::new(),
+            sponsorblock: SponsorBlockOptions::default(),
+            split_chapters: false,
+            subtitles: SubtitleOptions::default(),
+            organize_rules: Vec::new(),
+            transcode_preset: None,
+            loudness_target_lufs: None,
+            live: LiveOptions::default(),
+            playlist: None,
+            playlist_filter: PlaylistFilter::default(),
         }
     }
 
@@ -364,7 +487,7 @@ mod unit_tests {
     #[test]
     fn test_audio_mp3_extraction() {
         let mut config = create_test_config();
-        config.format = "audio-mp3".to_string();
+        config.format = OutputFormat::Audio(AudioCodec::Mp3);
 
         let builder = ArgumentBuilder::new(config);
         let args = builder.build();
@@ -376,7 +499,7 @@ mod unit_tests {
     #[test]
     fn test_audio_best_no_reencode() {
         let mut config = create_test_config();
-        config.format = "audio-best".to_string();
+        config.format = OutputFormat::Audio(AudioCodec::Best);
 
         let builder = ArgumentBuilder::new(config);
         let args = builder.build();
@@ -389,7 +512,7 @@ mod unit_tests {
     #[test]
     fn test_quality_1080p_format_selector() {
         let mut config = create_test_config();
-        config.quality = "1080p".to_string();
+        config.quality = Quality::P1080;
 
         let builder = ArgumentBuilder::new(config);
         let format_selector = builder.get_format_selector();
@@ -402,7 +525,7 @@ mod unit_tests {
     #[test]
     fn test_quality_720p_format_selector() {
         let mut config = create_test_config();
-        config.quality = "720p".to_string();
+        config.quality = Quality::P720;
 
         let builder = ArgumentBuilder::new(config);
         let format_selector = builder.get_format_selector();
@@ -410,6 +533,48 @@ mod unit_tests {
         assert!(format_selector.contains("height<=720"));
     }
 
+    #[test]
+    fn test_quality_2160p_format_selector() {
+        let mut config = create_test_config();
+        config.quality = Quality::P2160;
+
+        let builder = ArgumentBuilder::new(config);
+        let format_selector = builder.get_format_selector();
+
+        assert!(format_selector.contains("height<=2160"));
+        assert_eq!(builder.get_format_sort(), Some("res:2160".to_string()));
+    }
+
+    #[test]
+    fn test_fps_cap_and_codec_preference() {
+        let mut config = create_test_config();
+        config.quality = Quality::P1080;
+        config.max_fps = Some(30);
+        config.allow_hdr = false;
+        config.video_codec = Some(VideoCodec::H264);
+
+        let builder = ArgumentBuilder::new(config);
+
+        assert_eq!(
+            builder.get_format_selector(),
+            "bestvideo[height<=1080][fps<=30]+bestaudio/best[height<=1080][fps<=30]/best"
+        );
+        assert_eq!(
+            builder.get_format_sort(),
+            Some("res:1080,fps:30,hdr:sdr,vcodec:h264".to_string())
+        );
+    }
+
+    #[test]
+    fn test_best_quality_has_no_format_sort() {
+        let config = create_test_config();
+
+        let builder = ArgumentBuilder::new(config);
+        let args = builder.build();
+
+        assert!(!args.contains(&"-S".to_string()));
+    }
+
     #[test]
     fn test_cookies_from_browser() {
         let mut config = create_test_config();
@@ -444,6 +609,37 @@ mod unit_tests {
         assert!(args.windows(2).any(|w| w == ["--ffmpeg-location", "C:\\ffmpeg\\bin"]));
     }
 
+    #[test]
+    fn test_download_archive() {
+        let args = ArgumentBuilder::new(create_test_config()).build();
+        assert!(!args.contains(&"--download-archive".to_string()));
+
+        let args = ArgumentBuilder::new(create_test_config())
+            .with_download_archive("C:\\Users\\me\\download-archive.txt".to_string())
+            .build();
+        assert!(args
+            .windows(2)
+            .any(|w| w == ["--download-archive", "C:\\Users\\me\\download-archive.txt"]));
+    }
+
+    #[test]
+    fn test_live_recording_args() {
+        let args = ArgumentBuilder::new(create_test_config()).build();
+        assert!(!args.iter().any(|a| a.contains("live-from-start")));
+
+        let mut config = create_test_config();
+        config.live = LiveOptions {
+            enabled: true,
+            start: LiveStart::FromStart,
+            max_duration_seconds: Some(600),
+            ..LiveOptions::default()
+        };
+        let args = ArgumentBuilder::new(config).build();
+        assert!(args.contains(&"--live-from-start".to_string()));
+        assert!(args.windows(2).any(|w| w == ["--downloader-args", "ffmpeg:-t 600"]));
+        assert!(!args.contains(&"--wait-for-video".to_string()));
+    }
+
     #[test]
     fn test_custom_filename_template() {
         let mut config = create_test_config();
@@ -469,4 +665,292 @@ mod unit_tests {
         // Verify --newline flag is present for progress parsing
         assert!(args.contains(&"--newline".to_string()));
     }
+
+    #[test]
+    fn test_time_range_sections() {
+        let mut config = create_test_config();
+        config.sections = vec![
+            "00:01:00-00:02:30".parse().unwrap(),
+            "1:00:00-".parse().unwrap(),
+        ];
+
+        let builder = ArgumentBuilder::new(config);
+        let args = builder.build();
+
+        assert!(args.windows(2).any(|w| w == ["--download-sections", "*60-150"]));
+        assert!(args.windows(2).any(|w| w == ["--download-sections", "*3600-inf"]));
+
+        // Default template gets a section suffix so clips don't overwrite each other
+        let o_index = args.iter().position(|a| a == "-o").unwrap();
+        assert!(args[o_index + 1].contains("section_start"));
+    }
+
+    #[test]
+    fn test_chapter_pattern_sections() {
+        let mut config = create_test_config();
+        config.chapter_patterns = vec!["(?i)intro".to_string(), "  ".to_string()];
+
+        let builder = ArgumentBuilder::new(config);
+        let args = builder.build();
+
+        assert!(args.windows(2).any(|w| w == ["--download-sections", "(?i)intro"]));
+        assert_eq!(args.iter().filter(|a| *a == "--download-sections").count(), 1);
+    }
+
+    #[test]
+    fn test_no_sections_by_default() {
+        let config = create_test_config();
+
+        let builder = ArgumentBuilder::new(config);
+        let args = builder.build();
+
+        assert!(!args.contains(&"--download-sections".to_string()));
+        let o_index = args.iter().position(|a| a == "-o").unwrap();
+        assert_eq!(args[o_index + 1], "%(title).100B [%(id)s].%(ext)s");
+    }
+
+    #[test]
+    fn test_sponsorblock_args() {
+        let mut config = create_test_config();
+        config.sponsorblock = SponsorBlockOptions {
+            remove: vec![SponsorBlockCategory::Sponsor, SponsorBlockCategory::Selfpromo],
+            mark: vec![SponsorBlockCategory::Intro, SponsorBlockCategory::Sponsor],
+        };
+
+        let builder = ArgumentBuilder::new(config);
+        let args = builder.build();
+
+        assert!(args.windows(2).any(|w| w == ["--sponsorblock-remove", "sponsor,selfpromo"]));
+        assert!(args.windows(2).any(|w| w == ["--sponsorblock-mark", "intro"]));
+    }
+
+    #[test]
+    fn test_no_sponsorblock_by_default() {
+        let config = create_test_config();
+
+        let builder = ArgumentBuilder::new(config);
+        let args = builder.build();
+
+        assert!(!args.iter().any(|a| a.starts_with("--sponsorblock")));
+    }
+
+    #[test]
+    fn test_split_chapters() {
+        let mut config = create_test_config();
+        config.split_chapters = true;
+
+        let builder = ArgumentBuilder::new(config);
+        let args = builder.build();
+
+        assert!(args.contains(&"--split-chapters".to_string()));
+        assert!(args.windows(2).any(|w| w[0] == "-o" && w[1].starts_with("chapter:")));
+
+        // The main output template is still the first -o
+        let o_index = args.iter().position(|a| a == "-o").unwrap();
+        assert!(!args[o_index + 1].starts_with("chapter:"));
+    }
+
+    #[test]
+    fn test_build_subtitle_args_disabled() {
+        let options = SubtitleOptions::default();
+        let args = build_subtitle_args(&options);
+        assert!(args.is_empty());
+    }
+
+    #[test]
+    fn test_build_subtitle_args_download() {
+        let options = SubtitleOptions {
+            download_subtitles: true,
+            embed_subtitles: false,
+            languages: vec!["en".to_string(), "tr".to_string()],
+            format: "srt".to_string(),
+            include_auto: false,
+        };
+        let args = build_subtitle_args(&options);
+        assert!(args.contains(&"--write-subs".to_string()));
+        assert!(args.contains(&"--sub-langs".to_string()));
+        assert!(args.contains(&"en,tr".to_string()));
+        assert!(args.contains(&"--convert-subs".to_string()));
+    }
+
+    #[test]
+    fn test_build_subtitle_args_embed() {
+        let options = SubtitleOptions {
+            download_subtitles: false,
+            embed_subtitles: true,
+            languages: vec![],
+            format: "srt".to_string(),
+            include_auto: false,
+        };
+        let args = build_subtitle_args(&options);
+        assert!(args.contains(&"--embed-subs".to_string()));
+        assert!(args.contains(&"all".to_string()));
+    }
+
+    #[test]
+    fn test_build_subtitle_args_with_auto() {
+        let options = SubtitleOptions {
+            download_subtitles: true,
+            embed_subtitles: false,
+            languages: vec![],
+            format: "vtt".to_string(),
+            include_auto: true,
+        };
+        let args = build_subtitle_args(&options);
+        assert!(args.contains(&"--write-auto-subs".to_string()));
+        assert!(args.contains(&"vtt".to_string()));
+    }
+
+    #[test]
+    fn test_build_subtitle_args_best_format_skips_conversion() {
+        let options = SubtitleOptions {
+            download_subtitles: true,
+            format: "best".to_string(),
+            ..SubtitleOptions::default()
+        };
+        let args = build_subtitle_args(&options);
+        assert!(args.windows(2).any(|w| w == ["--sub-format", "best"]));
+        assert!(!args.contains(&"--convert-subs".to_string()));
+    }
+
+    #[test]
+    fn test_subtitle_options_in_config() {
+        let mut config = create_test_config();
+        config.subtitles = SubtitleOptions {
+            download_subtitles: true,
+            embed_subtitles: true,
+            languages: vec!["de".to_string()],
+            format: "srt".to_string(),
+            include_auto: true,
+        };
+
+        let builder = ArgumentBuilder::new(config);
+        let args = builder.build();
+
+        assert!(args.contains(&"--write-subs".to_string()));
+        assert!(args.contains(&"--write-auto-subs".to_string()));
+        assert!(args.contains(&"--embed-subs".to_string()));
+        assert!(args.windows(2).any(|w| w == ["--sub-langs", "de"]));
+        assert!(args.windows(2).any(|w| w == ["--convert-subs", "srt"]));
+    }
+
+    #[test]
+    fn test_subtitle_options_override_legacy_embed_flag() {
+        let mut config = create_test_config();
+        config.embed_subtitles = true;
+        config.subtitles = SubtitleOptions {
+            download_subtitles: true,
+            languages: vec!["en".to_string()],
+            ..SubtitleOptions::default()
+        };
+
+        let builder = ArgumentBuilder::new(config);
+        let args = builder.build();
+
+        assert!(!args.contains(&"--embed-subs".to_string()));
+        assert!(args.windows(2).any(|w| w == ["--sub-langs", "en"]));
+    }
+
+    #[test]
+    fn test_custom_template_is_sanitized() {
+        let mut config = create_test_config();
+        config.filename_template = Some("../Music: {uploader}/{title}?".to_string());
+
+        let builder = ArgumentBuilder::new(config);
+        let args = builder.build();
+
+        let o_index = args.iter().position(|a| a == "-o").unwrap();
+        let output_template = &args[o_index + 1];
+        assert!(!output_template.contains(".."));
+        assert!(output_template.starts_with("Music_ %(uploader)s"));
+        assert!(output_template.ends_with("%(title)s_.%(ext)s"));
+    }
+
+    #[test]
+    fn test_no_organize_rules_by_default() {
+        let builder = ArgumentBuilder::new(create_test_config());
+        let args = builder.build();
+
+        let o_index = args.iter().position(|a| a == "-o").unwrap();
+        assert_eq!(args[o_index + 1], "%(title).100B [%(id)s].%(ext)s");
+        assert_eq!(builder.organized_base_folder(), PathBuf::from("C:\\Downloads"));
+    }
+
+    #[test]
+    fn test_organize_rules_prefix_output_template() {
+        let mut config = create_test_config();
+        config.format = OutputFormat::Audio(AudioCodec::Mp3);
+        config.organize_rules = vec![
+            OrganizeRule::MediaType,
+            OrganizeRule::Site,
+            OrganizeRule::Uploader,
+            OrganizeRule::Playlist,
+            OrganizeRule::Year,
+        ];
+
+        let builder = ArgumentBuilder::new(config);
+        let args = builder.build();
+
+        let o_index = args.iter().position(|a| a == "-o").unwrap();
+        assert_eq!(
+            args[o_index + 1],
+            "Audio/%(extractor_key|Other)s/%(uploader,channel|Unknown)s/%(playlist_title|)s/\
+             %(upload_date>%Y|Unknown)s/%(title).100B [%(id)s].%(ext)s"
+        );
+        // Only the leading static rule can be created up front
+        assert_eq!(
+            builder.organized_base_folder(),
+            PathBuf::from("C:\\Downloads").join("Audio")
+        );
+    }
+
+    #[test]
+    fn test_organize_rules_apply_to_chapter_files() {
+        let mut config = create_test_config();
+        config.split_chapters = true;
+        config.organize_rules = vec![OrganizeRule::Site, OrganizeRule::MediaType];
+
+        let builder = ArgumentBuilder::new(config);
+        let args = builder.build();
+
+        assert!(args
+            .iter()
+            .any(|a| a.starts_with("chapter:%(extractor_key|Other)s/Video/")));
+        // A dynamic rule first means nothing below the output folder is known yet
+        assert_eq!(builder.organized_base_folder(), PathBuf::from("C:\\Downloads"));
+    }
+    #[test]
+    fn test_playlist_item_output_template() {
+        let mut config = create_test_config();
+        config.filename_template = Some("{playlist_index} - {title}".to_string());
+        config.organize_rules = vec![OrganizeRule::Playlist];
+        config.playlist = Some(PlaylistContext {
+            group_id: "playlist-1".to_string(),
+            index: 4,
+            count: 25,
+            title: "Road Trip".to_string(),
+        });
+
+        let args = ArgumentBuilder::new(config).build();
+
+        let o_index = args.iter().position(|a| a == "-o").unwrap();
+        assert_eq!(args[o_index + 1], "Road Trip/04 - %(title)s.%(ext)s");
+        // Still one video per item
+        assert!(args.contains(&"--no-playlist".to_string()));
+    }
+
+    #[test]
+    fn test_playlist_filter_args() {
+        let mut config = create_test_config();
+        assert!(!ArgumentBuilder::new(config.clone()).build().contains(&"--match-filters".to_string()));
+
+        config.playlist_filter = PlaylistFilter {
+            date_after: Some("2025-01-01".to_string()),
+            min_duration: Some(300),
+            ..Default::default()
+        };
+        let args = ArgumentBuilder::new(config).build();
+        assert!(args.windows(2).any(|w| w == ["--dateafter", "20250101"]));
+        assert!(args.windows(2).any(|w| w == ["--match-filters", "duration>=?300"]));
+    }
 }
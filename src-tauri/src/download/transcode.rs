@@ -0,0 +1,313 @@
+//! Post-download transcoding with ffmpeg
+//!
+//! Runs the bundled ffmpeg with a `TranscodePreset` once yt-dlp has finished.
+//! Progress comes from ffmpeg's `-progress pipe:1` output (key=value blocks
+//! on stdout); the input duration is read from the banner on stderr.
+
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use tokio::io::{AsyncBufReadExt, BufReader};
+use tokio::sync::mpsc;
+
+use crate::models::{DownloadError, ProgressEvent, TranscodePreset};
+
+/// Progress status reported while transcoding
+pub const TRANSCODING_STATUS: &str = "transcoding";
+
+/// Path of the transcoded file: same folder and stem, preset suffix and extension
+///
+/// `video.webm` with `Phone720p` becomes `video.phone720p.mp4`, so the
+/// original is never overwritten.
+pub fn transcode_output_path(input: &str, preset: TranscodePreset) -> PathBuf {
+    let input = Path::new(input);
+    let stem = input
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_else(|| "output".to_string());
+    input.with_file_name(format!("{}.{}.{}", stem, preset.name(), preset.extension()))
+}
+
+/// Builds the ffmpeg argument list for a preset
+pub fn build_transcode_args(input: &str, output: &str, preset: TranscodePreset) -> Vec<String> {
+    let mut args: Vec<String> = ["-hide_banner", "-nostdin", "-y", "-i", input]
+        .iter()
+        .map(|s| s.to_string())
+        .collect();
+    args.extend(preset.ffmpeg_args().iter().map(|s| s.to_string()));
+    args.extend(
+        ["-progress", "pipe:1", "-nostats", output]
+            .iter()
+            .map(|s| s.to_string()),
+    );
+    args
+}
+
+/// Tracks ffmpeg progress across `-progress` blocks
+#[derive(Debug, Default)]
+pub struct FfmpegProgress {
+    /// Input duration in microseconds (from the stderr banner)
+    duration_us: Option<u64>,
+    /// Output position in microseconds
+    out_time_us: u64,
+    /// Bytes written so far
+    total_size: u64,
+    /// Encoding speed relative to realtime (e.g. 2.5 for "2.5x")
+    speed: Option<f64>,
+}
+
+impl FfmpegProgress {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Picks up the input duration from a stderr line like
+    /// `  Duration: 00:03:25.48, start: 0.000000, bitrate: 1289 kb/s`
+    pub fn parse_duration_line(&mut self, line: &str) {
+        if self.duration_us.is_some() {
+            return;
+        }
+        let Some(rest) = line.trim_start().strip_prefix("Duration:") else {
+            return;
+        };
+        let value = rest.split(',').next().unwrap_or("").trim();
+        if let Some(seconds) = parse_ffmpeg_time(value) {
+            if seconds > 0.0 {
+                self.duration_us = Some((seconds * 1_000_000.0) as u64);
+            }
+        }
+    }
+
+    /// Feeds one line of `-progress` output
+    ///
+    /// Returns a progress event at the end of each block (`progress=...`).
+    pub fn feed(&mut self, line: &str) -> Option<ProgressEvent> {
+        let (key, value) = line.trim().split_once('=')?;
+        let value = value.trim();
+
+        match key {
+            // out_time_ms is microseconds as well (long-standing ffmpeg quirk)
+            "out_time_us" | "out_time_ms" => {
+                if let Ok(us) = value.parse::<u64>() {
+                    self.out_time_us = us;
+                }
+            }
+            "total_size" => {
+                if let Ok(bytes) = value.parse::<u64>() {
+                    self.total_size = bytes;
+                }
+            }
+            "speed" => {
+                self.speed = value.trim_end_matches('x').trim().parse::<f64>().ok();
+            }
+            "progress" => return Some(self.event(value == "end")),
+            _ => {}
+        }
+
+        None
+    }
+
+    /// Current progress as an event
+    fn event(&self, finished: bool) -> ProgressEvent {
+        let percentage = match (finished, self.duration_us) {
+            (true, _) => 100.0,
+            (false, Some(duration)) => {
+                (self.out_time_us as f64 / duration as f64 * 100.0).clamp(0.0, 100.0)
+            }
+            (false, None) => 0.0,
+        };
+
+        let eta_seconds = match (self.duration_us, self.speed) {
+            (Some(duration), Some(speed)) if speed > 0.0 && !finished => {
+                let remaining = duration.saturating_sub(self.out_time_us) as f64 / 1_000_000.0;
+                Some((remaining / speed).round() as u64)
+            }
+            _ => None,
+        };
+
+        ProgressEvent {
+            percentage,
+            downloaded_bytes: self.total_size,
+            total_bytes: None,
+            speed: self.speed.map(|s| format!("{:.2}x", s)).unwrap_or_default(),
+            eta_seconds,
+            status: TRANSCODING_STATUS.to_string(),
+            elapsed_seconds: None,
+        }
+    }
+}
+
+/// Parses an ffmpeg timestamp ("HH:MM:SS.ss") into seconds
+fn parse_ffmpeg_time(value: &str) -> Option<f64> {
+    let parts: Vec<&str> = value.split(':').collect();
+    if parts.len() != 3 {
+        return None;
+    }
+    let hours = parts[0].parse::<f64>().ok()?;
+    let minutes = parts[1].parse::<f64>().ok()?;
+    let seconds = parts[2].parse::<f64>().ok()?;
+    Some(hours * 3600.0 + minutes * 60.0 + seconds)
+}
+
+/// Transcodes `input` with the given preset and returns the new file's path
+///
+/// Progress events (status "transcoding") are sent through `tx`. A partial
+/// output file is removed if ffmpeg fails.
+pub async fn run_transcode(
+    ffmpeg_path: &str,
+    input: &str,
+    preset: TranscodePreset,
+    tx: mpsc::Sender<ProgressEvent>,
+) -> Result<String, DownloadError> {
+    let output = transcode_output_path(input, preset);
+    let output_str = output.to_string_lossy().to_string();
+    let args = build_transcode_args(input, &output_str, preset);
+
+    tracing::info!("Transcoding with preset {:?}: {} {}", preset, ffmpeg_path, args.join(" "));
+
+    let mut child = crate::utils::create_hidden_async_command(ffmpeg_path)
+        .args(&args)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .map_err(|e| {
+            if e.kind() == std::io::ErrorKind::NotFound {
+                DownloadError::ExecutableNotFound(ffmpeg_path.to_string())
+            } else {
+                DownloadError::ProcessSpawnError(e.to_string())
+            }
+        })?;
+
+    let stdout = child
+        .stdout
+        .take()
+        .ok_or_else(|| DownloadError::ProcessSpawnError("Failed to capture ffmpeg stdout".to_string()))?;
+    let stderr = child
+        .stderr
+        .take()
+        .ok_or_else(|| DownloadError::ProcessSpawnError("Failed to capture ffmpeg stderr".to_string()))?;
+
+    let mut stdout_lines = BufReader::new(stdout).lines();
+    let mut stderr_lines = BufReader::new(stderr).lines();
+    let mut progress = FfmpegProgress::new();
+    let mut last_error_line = String::new();
+    let (mut stdout_done, mut stderr_done) = (false, false);
+
+    while !(stdout_done && stderr_done) {
+        tokio::select! {
+            line = stdout_lines.next_line(), if !stdout_done => match line {
+                Ok(Some(line)) => {
+                    if let Some(event) = progress.feed(&line) {
+                        let _ = tx.send(event).await;
+                    }
+                }
+                _ => stdout_done = true,
+            },
+            line = stderr_lines.next_line(), if !stderr_done => match line {
+                Ok(Some(line)) => {
+                    progress.parse_duration_line(&line);
+                    if !line.trim().is_empty() {
+                        last_error_line = line.trim().to_string();
+                    }
+                }
+                _ => stderr_done = true,
+            },
+        }
+    }
+
+    let status = child
+        .wait()
+        .await
+        .map_err(|e| DownloadError::TranscodeFailed(e.to_string()))?;
+
+    if !status.success() {
+        let _ = std::fs::remove_file(&output);
+        tracing::error!("ffmpeg failed ({}): {}", status, last_error_line);
+        return Err(DownloadError::TranscodeFailed(if last_error_line.is_empty() {
+            format!("ffmpeg exited with {}", status)
+        } else {
+            last_error_line
+        }));
+    }
+
+    tracing::info!("Transcoded file: {}", output_str);
+    Ok(output_str)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transcode_output_path() {
+        let input = Path::new("downloads").join("Some Video [abc].webm");
+        let output = transcode_output_path(&input.to_string_lossy(), TranscodePreset::Phone720p);
+        assert_eq!(output, Path::new("downloads").join("Some Video [abc].phone720p.mp4"));
+
+        let output = transcode_output_path("talk.mp3", TranscodePreset::VoiceMemo);
+        assert_eq!(output, PathBuf::from("talk.voice.m4a"));
+    }
+
+    #[test]
+    fn test_build_transcode_args() {
+        let args = build_transcode_args("in.webm", "out.mov", TranscodePreset::EditingIntra);
+        assert_eq!(&args[..5], ["-hide_banner", "-nostdin", "-y", "-i", "in.webm"]);
+        assert!(args.windows(2).any(|w| w == ["-c:v", "prores_ks"]));
+        assert!(args.windows(2).any(|w| w == ["-progress", "pipe:1"]));
+        assert_eq!(args.last().unwrap(), "out.mov");
+    }
+
+    #[test]
+    fn test_parse_duration_line() {
+        let mut progress = FfmpegProgress::new();
+        progress.parse_duration_line("  Duration: 00:01:40.00, start: 0.000000, bitrate: 1289 kb/s");
+        assert_eq!(progress.duration_us, Some(100_000_000));
+
+        // Later inputs/outputs don't override the first duration
+        progress.parse_duration_line("  Duration: 00:00:10.00, start: 0.000000");
+        assert_eq!(progress.duration_us, Some(100_000_000));
+
+        let mut unknown = FfmpegProgress::new();
+        unknown.parse_duration_line("  Duration: N/A, bitrate: N/A");
+        assert_eq!(unknown.duration_us, None);
+    }
+
+    #[test]
+    fn test_progress_blocks() {
+        let mut progress = FfmpegProgress::new();
+        progress.parse_duration_line("  Duration: 00:01:40.00, start: 0.000000");
+
+        let block = [
+            "frame=1200",
+            "total_size=5242880",
+            "out_time_us=25000000",
+            "out_time_ms=25000000",
+            "out_time=00:00:25.000000",
+            "speed=2.5x",
+        ];
+        for line in block {
+            assert!(progress.feed(line).is_none());
+        }
+        let event = progress.feed("progress=continue").unwrap();
+        assert_eq!(event.percentage, 25.0);
+        assert_eq!(event.downloaded_bytes, 5_242_880);
+        assert_eq!(event.speed, "2.50x");
+        assert_eq!(event.eta_seconds, Some(30));
+        assert_eq!(event.status, TRANSCODING_STATUS);
+
+        let done = progress.feed("progress=end").unwrap();
+        assert_eq!(done.percentage, 100.0);
+        assert_eq!(done.eta_seconds, None);
+    }
+
+    #[test]
+    fn test_progress_without_duration() {
+        let mut progress = FfmpegProgress::new();
+        progress.feed("out_time_us=5000000");
+        progress.feed("speed=N/A");
+        let event = progress.feed("progress=continue").unwrap();
+        assert_eq!(event.percentage, 0.0);
+        assert_eq!(event.eta_seconds, None);
+        assert!(event.speed.is_empty());
+    }
+}
@@ -5,7 +5,14 @@ pub mod download;
 pub mod models;
 pub mod utils;
 
+use commands::api::{api_get_info, api_regenerate_token, start_api_server};
+use commands::archive::{
+    archive_export, archive_import, archive_list, archive_prune, pick_archive_export_file, pick_archive_import_file,
+};
+use commands::batch::{batch_parse, pick_batch_file, queue_add_batch};
+use commands::clipboard::start_clipboard_watch;
 use commands::debug::{copy_debug_info, get_recent_logs};
+use commands::deep_link::open_deep_links;
 use commands::download::{cancel_download, get_download_state, reset_download, start_download};
 use commands::executables::{check_executables, get_executable_paths};
 use commands::folder::{
@@ -14,11 +21,15 @@ use commands::folder::{
 };
 use commands::history::{history_add, history_clear, history_get_all, history_get_stats, history_remove};
 use commands::media_info::fetch_media_info;
-use commands::playlist::{check_is_playlist, fetch_playlist_info};
+use commands::playlist::{cancel_playlist_fetch, check_is_playlist, fetch_playlist_info, PlaylistFetches};
 use commands::preferences::{load_preferences, save_preferences};
+use commands::subscriptions::{
+    start_subscription_sync, subscription_add, subscription_list, subscription_remove, subscription_sync_now,
+};
 use commands::subtitles::fetch_subtitles;
+use commands::template::preview_filename_template;
 use commands::queue::{
-    queue_add, queue_cancel, queue_clear_completed, queue_get_all, queue_move_down, queue_move_up,
+    queue_add, queue_add_playlist, queue_cancel, queue_clear_completed, queue_get_all, queue_move_down, queue_move_up,
     queue_pause_all, queue_remove, queue_reorder, queue_resume_all, setup_queue_events, start_queue_processor,
 };
 use commands::update::{check_app_update, check_ytdlp_update, get_app_version, get_ytdlp_version_cmd, install_app_update, update_ytdlp};
@@ -26,8 +37,9 @@ use download::{create_download_manager, create_download_queue, SharedDownloadMan
 use tauri::{
     menu::{Menu, MenuItem},
     tray::{MouseButton, MouseButtonState, TrayIconBuilder, TrayIconEvent},
-    Emitter, Manager, RunEvent, WindowEvent,
+    AppHandle, Emitter, Manager, RunEvent, WindowEvent,
 };
+use tauri_plugin_deep_link::DeepLinkExt;
 use tauri_plugin_store::StoreExt;
 use utils::logging;
 
@@ -36,6 +48,15 @@ fn greet(name: &str) -> String {
     format!("Hello, {}! You've been greeted from Rust!", name)
 }
 
+/// Shows, restores and focuses the main window
+pub(crate) fn show_main_window(app: &AppHandle) {
+    if let Some(window) = app.get_webview_window("main") {
+        let _ = window.show();
+        let _ = window.unminimize();
+        let _ = window.set_focus();
+    }
+}
+
 /// Sets up the system tray with menu items
 /// 
 /// **Validates: Requirements 4.9**
@@ -54,13 +75,7 @@ fn setup_system_tray(app: &tauri::App) -> Result<(), Box<dyn std::error::Error>>
         .show_menu_on_left_click(false)
         .on_menu_event(|app, event| {
             match event.id.as_ref() {
-                "show" => {
-                    if let Some(window) = app.get_webview_window("main") {
-                        let _ = window.show();
-                        let _ = window.unminimize();
-                        let _ = window.set_focus();
-                    }
-                }
+                "show" => show_main_window(app),
                 "quit" => {
                     // Terminate any active download before quitting
                     let download_manager = app.state::<SharedDownloadManager>();
@@ -86,11 +101,7 @@ fn setup_system_tray(app: &tauri::App) -> Result<(), Box<dyn std::error::Error>>
                 ..
             } = event
             {
-                if let Some(window) = tray.app_handle().get_webview_window("main") {
-                    let _ = window.show();
-                    let _ = window.unminimize();
-                    let _ = window.set_focus();
-                }
+                show_main_window(tray.app_handle());
             }
         })
         .build(app)?;
@@ -231,8 +242,21 @@ pub fn run() {
     // Create download queue with max 3 concurrent downloads
     let download_queue = create_download_queue(3, queue_event_tx);
     let queue_for_processor = download_queue.clone();
+    let queue_for_subscriptions = download_queue.clone();
+    let queue_for_clipboard = download_queue.clone();
+    let queue_for_api = download_queue.clone();
+
+    // Queue events for local API clients (each event stream subscribes)
+    let (api_event_tx, _) = tokio::sync::broadcast::channel(256);
+    let api_events_for_server = api_event_tx.clone();
 
     tauri::Builder::default()
+        // Must come first: a second launch (e.g. from a mediagrab:// link)
+        // hands its deep link to this instance and exits
+        .plugin(tauri_plugin_single_instance::init(|app, _args, _cwd| {
+            show_main_window(app);
+        }))
+        .plugin(tauri_plugin_deep_link::init())
         .plugin(tauri_plugin_opener::init())
         .plugin(tauri_plugin_store::Builder::new().build())
         .plugin(tauri_plugin_dialog::init())
@@ -241,13 +265,14 @@ pub fn run() {
         .plugin(tauri_plugin_clipboard_manager::init())
         .manage(download_manager)
         .manage(download_queue)
+        .manage(PlaylistFetches::default())
         .setup(move |app| {
             // Set up system tray
             setup_system_tray(app)?;
 
             // Set up queue event forwarding to frontend
             let app_handle_queue = app.handle().clone();
-            setup_queue_events(app_handle_queue, queue_event_rx);
+            setup_queue_events(app_handle_queue, queue_event_rx, api_event_tx);
 
             // Start queue processor
             let app_handle_processor = app.handle().clone();
@@ -255,6 +280,39 @@ pub fn run() {
                 start_queue_processor(app_handle_processor, queue_for_processor).await;
             });
 
+            // Sync channel/playlist subscriptions in the background
+            let app_handle_subscriptions = app.handle().clone();
+            tauri::async_runtime::spawn(async move {
+                start_subscription_sync(app_handle_subscriptions, queue_for_subscriptions).await;
+            });
+
+            // Pick up media links copied to the clipboard (when enabled)
+            let app_handle_clipboard = app.handle().clone();
+            tauri::async_runtime::spawn(async move {
+                start_clipboard_watch(app_handle_clipboard, queue_for_clipboard).await;
+            });
+
+            // Local HTTP API for browser extensions and scripts (when enabled)
+            let app_handle_api = app.handle().clone();
+            tauri::async_runtime::spawn(async move {
+                start_api_server(app_handle_api, queue_for_api, api_events_for_server).await;
+            });
+
+            // mediagrab:// links. Installers register the scheme; this
+            // covers portable and development builds.
+            #[cfg(any(windows, target_os = "linux"))]
+            if let Err(e) = app.deep_link().register_all() {
+                tracing::warn!("Failed to register the mediagrab:// scheme: {}", e);
+            }
+            let app_handle_links = app.handle().clone();
+            app.deep_link().on_open_url(move |event| {
+                open_deep_links(&app_handle_links, event.urls());
+            });
+            // The link the app was started with, if any
+            if let Ok(Some(links)) = app.deep_link().get_current() {
+                open_deep_links(app.handle(), links);
+            }
+
             // Spawn background executable check
             // **Validates: Requirements 6.1, 11.6**
             let app_handle_exec = app.handle().clone();
@@ -295,6 +353,10 @@ pub fn run() {
             get_executable_paths,
             // Queue commands
             queue_add,
+            queue_add_playlist,
+            queue_add_batch,
+            batch_parse,
+            pick_batch_file,
             queue_get_all,
             queue_cancel,
             queue_remove,
@@ -310,11 +372,29 @@ pub fn run() {
             history_get_stats,
             history_remove,
             history_clear,
+            // Download archive commands
+            archive_list,
+            archive_import,
+            archive_export,
+            archive_prune,
+            pick_archive_import_file,
+            pick_archive_export_file,
             // Playlist commands
             check_is_playlist,
             fetch_playlist_info,
+            cancel_playlist_fetch,
+            // Subscription commands
+            subscription_add,
+            subscription_remove,
+            subscription_list,
+            subscription_sync_now,
             // Subtitle commands
             fetch_subtitles,
+            // Filename template commands
+            preview_filename_template,
+            // Local API commands
+            api_get_info,
+            api_regenerate_token,
             // App update commands
             check_app_update,
             install_app_update,
@@ -0,0 +1,56 @@
+//! Local HTTP API options
+//!
+//! When enabled, a small JSON API on 127.0.0.1 lets a browser extension or
+//! scripts queue downloads, list and cancel items and follow queue events.
+//! Every request needs the token kept in the app data folder.
+
+use serde::{Deserialize, Serialize};
+
+/// Port the API listens on unless changed
+pub const DEFAULT_API_PORT: u16 = 47821;
+
+/// Local HTTP API settings
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApiOptions {
+    /// Run the API server
+    #[serde(default)]
+    pub enabled: bool,
+    /// Port on 127.0.0.1
+    #[serde(default = "default_api_port")]
+    pub port: u16,
+}
+
+fn default_api_port() -> u16 {
+    DEFAULT_API_PORT
+}
+
+impl Default for ApiOptions {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            port: DEFAULT_API_PORT,
+        }
+    }
+}
+
+impl ApiOptions {
+    /// Whether the port can be used without elevated privileges
+    pub fn is_valid_port(&self) -> bool {
+        self.port >= 1024
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_api_options_deserialize_defaults() {
+        let options: ApiOptions = serde_json::from_str(r#"{"enabled":true}"#).unwrap();
+        assert!(options.enabled);
+        assert_eq!(options.port, DEFAULT_API_PORT);
+        assert!(options.is_valid_port());
+        assert!(!ApiOptions { enabled: true, port: 80 }.is_valid_port());
+    }
+}
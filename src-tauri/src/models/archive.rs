@@ -0,0 +1,58 @@
+//! Download archive entries
+//!
+//! yt-dlp's `--download-archive` file has one line per finished download:
+//! the lowercase extractor key and the video id, separated by a space
+//! (e.g. `youtube dQw4w9WgXcQ`). Videos listed there are skipped.
+
+use serde::{Deserialize, Serialize};
+
+/// One video recorded in the download archive
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchiveEntry {
+    /// Lowercase extractor key (e.g. "youtube", "vimeo")
+    pub extractor: String,
+    /// Video id as reported by the extractor
+    pub id: String,
+}
+
+impl ArchiveEntry {
+    /// Parses one archive line; returns None for blank or malformed lines
+    pub fn parse_line(line: &str) -> Option<Self> {
+        let (extractor, id) = line.trim().split_once(char::is_whitespace)?;
+        let id = id.trim();
+        if extractor.is_empty() || id.is_empty() {
+            return None;
+        }
+        Some(Self {
+            extractor: extractor.to_lowercase(),
+            id: id.to_string(),
+        })
+    }
+
+    /// Formats the entry the way yt-dlp writes it
+    pub fn to_line(&self) -> String {
+        format!("{} {}", self.extractor, self.id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_archive_entry_lines() {
+        let entry = ArchiveEntry::parse_line("youtube dQw4w9WgXcQ\n").unwrap();
+        assert_eq!(entry.extractor, "youtube");
+        assert_eq!(entry.id, "dQw4w9WgXcQ");
+        assert_eq!(entry.to_line(), "youtube dQw4w9WgXcQ");
+
+        // Imported files may use other casing or tabs
+        let entry = ArchiveEntry::parse_line("Vimeo\t76979871").unwrap();
+        assert_eq!(entry.to_line(), "vimeo 76979871");
+
+        assert!(ArchiveEntry::parse_line("").is_none());
+        assert!(ArchiveEntry::parse_line("youtube").is_none());
+        assert!(ArchiveEntry::parse_line("   ").is_none());
+    }
+}
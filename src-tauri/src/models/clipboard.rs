@@ -0,0 +1,124 @@
+//! Clipboard monitoring options
+//!
+//! When enabled, links copied anywhere on the system are picked up if their
+//! site is on the allow-list. They are either offered to the user or queued
+//! straight away with the chosen format and quality.
+
+use serde::{Deserialize, Serialize};
+
+use super::format::{OutputFormat, Quality};
+
+/// Sites watched for by default
+pub const DEFAULT_CLIPBOARD_DOMAINS: &[&str] = &[
+    "youtube.com",
+    "youtu.be",
+    "vimeo.com",
+    "dailymotion.com",
+    "twitch.tv",
+    "soundcloud.com",
+    "bandcamp.com",
+    "bilibili.com",
+    "nicovideo.jp",
+    "tiktok.com",
+];
+
+/// What happens to a detected link
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ClipboardAction {
+    /// Tell the frontend, which offers to download it
+    #[default]
+    Notify,
+    /// Add it to the queue
+    Enqueue,
+}
+
+/// Clipboard monitoring settings
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ClipboardOptions {
+    /// Watch the clipboard for links
+    #[serde(default)]
+    pub enabled: bool,
+    #[serde(default)]
+    pub action: ClipboardAction,
+    /// Format for queued links (None = the default format)
+    #[serde(default)]
+    pub format: Option<OutputFormat>,
+    /// Quality for queued links (None = the default quality)
+    #[serde(default)]
+    pub quality: Option<Quality>,
+    /// Sites links are picked up from; subdomains are included
+    #[serde(default = "default_allowed_domains")]
+    pub allowed_domains: Vec<String>,
+}
+
+fn default_allowed_domains() -> Vec<String> {
+    DEFAULT_CLIPBOARD_DOMAINS.iter().map(|d| d.to_string()).collect()
+}
+
+impl Default for ClipboardOptions {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            action: ClipboardAction::default(),
+            format: None,
+            quality: None,
+            allowed_domains: default_allowed_domains(),
+        }
+    }
+}
+
+impl ClipboardOptions {
+    /// Whether `url` is on an allowed site
+    pub fn allows(&self, url: &str) -> bool {
+        let Some(host) = url_host(url) else {
+            return false;
+        };
+        self.allowed_domains.iter().any(|domain| {
+            let domain = domain.trim().trim_start_matches("*.").trim_start_matches('.').to_lowercase();
+            !domain.is_empty()
+                && (host == domain || host.strip_suffix(&domain).is_some_and(|sub| sub.ends_with('.')))
+        })
+    }
+}
+
+/// Lowercase host of an http(s) URL, without credentials or port
+pub(crate) fn url_host(url: &str) -> Option<String> {
+    let rest = url
+        .strip_prefix("https://")
+        .or_else(|| url.strip_prefix("http://"))?;
+    let authority = rest.split(['/', '?', '#']).next()?;
+    let host = authority.rsplit('@').next()?.split(':').next()?;
+    (!host.is_empty()).then(|| host.to_lowercase())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_clipboard_options_allows() {
+        let options = ClipboardOptions::default();
+        assert!(options.allows("https://www.youtube.com/watch?v=dQw4w9WgXcQ"));
+        assert!(options.allows("https://music.youtube.com/watch?v=dQw4w9WgXcQ"));
+        assert!(options.allows("http://youtu.be/dQw4w9WgXcQ"));
+        assert!(!options.allows("https://notyoutube.com/watch?v=1"));
+        assert!(!options.allows("https://example.com/?u=youtube.com"));
+        assert!(!options.allows("https://youtube.com.evil.net/x"));
+
+        let options = ClipboardOptions {
+            allowed_domains: vec!["*.Example.org".to_string(), " ".to_string()],
+            ..ClipboardOptions::default()
+        };
+        assert!(options.allows("https://user@media.example.org:8443/v/1"));
+        assert!(!options.allows("https://youtube.com/watch?v=1"));
+    }
+
+    #[test]
+    fn test_clipboard_options_deserialize_defaults() {
+        let options: ClipboardOptions = serde_json::from_str(r#"{"enabled":true}"#).unwrap();
+        assert_eq!(options.action, ClipboardAction::Notify);
+        assert_eq!(options.allowed_domains.len(), DEFAULT_CLIPBOARD_DOMAINS.len());
+    }
+}
@@ -2,6 +2,16 @@
 
 use serde::{Deserialize, Serialize};
 
+use super::format::{OutputFormat, Quality, VideoCodec};
+use super::live::LiveOptions;
+use super::loudness::LoudnessMeasurement;
+use super::organize::OrganizeRule;
+use super::playlist::{PlaylistContext, PlaylistFilter};
+use super::section::TimeRange;
+use super::sponsorblock::SponsorBlockOptions;
+use super::subtitles::SubtitleOptions;
+use super::transcode::TranscodePreset;
+
 /// Media information fetched from a URL
 /// 
 /// **Validates: Requirements 12.3**
@@ -18,6 +28,24 @@ pub struct MediaInfo {
     pub uploader: Option<String>,
     /// Approximate file size in bytes
     pub filesize_approx: Option<u64>,
+    /// Video ID on the site
+    #[serde(default)]
+    pub id: Option<String>,
+    /// Channel name (may differ from uploader)
+    #[serde(default)]
+    pub channel: Option<String>,
+    /// Upload date as YYYYMMDD
+    #[serde(default)]
+    pub upload_date: Option<String>,
+    /// Video width in pixels
+    #[serde(default)]
+    pub width: Option<u32>,
+    /// Video height in pixels
+    #[serde(default)]
+    pub height: Option<u32>,
+    /// yt-dlp live status ("is_live", "is_upcoming", "was_live", "not_live", ...)
+    #[serde(default)]
+    pub live_status: Option<String>,
 }
 
 /// Download configuration sent from frontend
@@ -26,10 +54,10 @@ pub struct MediaInfo {
 pub struct DownloadConfig {
     /// The URL to download from
     pub url: String,
-    /// Output format (video-mp4, audio-mp3, audio-best)
-    pub format: String,
-    /// Quality setting (best, 1080p, 720p)
-    pub quality: String,
+    /// Output format (serialized as "video-mp4", "audio-mp3", ...)
+    pub format: OutputFormat,
+    /// Quality setting (serialized as "best", "1080p", "720p")
+    pub quality: Quality,
     /// Output folder path
     pub output_folder: String,
     /// Whether to embed subtitles
@@ -45,6 +73,54 @@ pub struct DownloadConfig {
     /// Path to custom cookies.txt file (Netscape format)
     #[serde(default)]
     pub cookies_file_path: Option<String>,
+    /// Maximum frame rate for video downloads (None = no cap)
+    #[serde(default)]
+    pub max_fps: Option<u32>,
+    /// Whether HDR formats may be selected (false prefers SDR)
+    #[serde(default = "default_true")]
+    pub allow_hdr: bool,
+    /// Preferred video codec (None = yt-dlp default ordering)
+    #[serde(default)]
+    pub video_codec: Option<VideoCodec>,
+    /// Time ranges to download instead of the whole video
+    #[serde(default)]
+    pub sections: Vec<TimeRange>,
+    /// Chapter title regexes to download instead of the whole video
+    #[serde(default)]
+    pub chapter_patterns: Vec<String>,
+    /// SponsorBlock categories to remove or mark as chapters
+    #[serde(default)]
+    pub sponsorblock: SponsorBlockOptions,
+    /// Split the download into one file per chapter
+    #[serde(default)]
+    pub split_chapters: bool,
+    /// Subtitle languages, auto-captions, sidecar/embed and target format
+    ///
+    /// When disabled, the legacy `embed_subtitles` flag still embeds all languages.
+    #[serde(default)]
+    pub subtitles: SubtitleOptions,
+    /// Subfolders to sort the download into, outermost first
+    #[serde(default)]
+    pub organize_rules: Vec<OrganizeRule>,
+    /// Re-encode the finished download with ffmpeg (None = keep as downloaded)
+    #[serde(default)]
+    pub transcode_preset: Option<TranscodePreset>,
+    /// Normalize audio downloads to this integrated loudness (LUFS, None = off)
+    #[serde(default)]
+    pub loudness_target_lufs: Option<f64>,
+    /// Livestream recording (start point, duration limit, waiting for the stream)
+    #[serde(default)]
+    pub live: LiveOptions,
+    /// Playlist this item was queued from (fills `{playlist_index}`/`{playlist_title}`)
+    #[serde(default)]
+    pub playlist: Option<PlaylistContext>,
+    /// Skip playlist entries by upload date, duration or title
+    #[serde(default)]
+    pub playlist_filter: PlaylistFilter,
+}
+
+fn default_true() -> bool {
+    true
 }
 
 /// Result of a download operation
@@ -55,6 +131,12 @@ pub struct DownloadResult {
     pub success: bool,
     /// Path to the downloaded file (if successful)
     pub file_path: Option<String>,
+    /// All files produced by the download (main file first, then chapter files)
+    pub file_paths: Vec<String>,
+    /// Sidecar subtitle files written next to the download
+    pub subtitle_files: Vec<String>,
+    /// Loudness measured before normalization (audio downloads only)
+    pub loudness: Option<LoudnessMeasurement>,
     /// Error message (if failed)
     pub error: Option<String>,
 }
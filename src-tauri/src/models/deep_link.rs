@@ -0,0 +1,154 @@
+//! `mediagrab://` deep links
+//!
+//! Bookmarklets and other apps queue downloads with links like
+//! `mediagrab://add?url=https%3A%2F%2Fyoutu.be%2FdQw4w9WgXcQ&preset=audio`.
+//! Besides the (percent-encoded) `url`, a link can set `preset` (`audio` or
+//! `video`), `format` and `quality`; the rest comes from the preferences.
+
+use super::clipboard::url_host;
+use super::error::DownloadError;
+use super::format::{AudioCodec, OutputFormat, Quality, VideoContainer};
+
+/// URL scheme registered for the app
+pub const DEEP_LINK_SCHEME: &str = "mediagrab";
+
+/// Longest deep link accepted
+const MAX_DEEP_LINK_LENGTH: usize = 8 * 1024;
+
+/// Kind of download a deep link asks for, keeping the default format when
+/// it's already of that kind
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeepLinkPreset {
+    Audio,
+    Video,
+}
+
+/// A parsed `mediagrab://add` link
+#[derive(Debug, Clone, PartialEq)]
+pub struct DeepLink {
+    /// Media link to download
+    pub url: String,
+    pub preset: Option<DeepLinkPreset>,
+    /// Exact format, taking precedence over `preset`
+    pub format: Option<OutputFormat>,
+    pub quality: Option<Quality>,
+}
+
+impl DeepLink {
+    /// Parses and validates a deep link
+    pub fn parse(link: &str) -> Result<Self, DownloadError> {
+        let link = link.trim();
+        if link.len() > MAX_DEEP_LINK_LENGTH {
+            return Err(DownloadError::InvalidUrl("deep link is too long".to_string()));
+        }
+
+        let rest = link
+            .split_once(':')
+            .filter(|(scheme, _)| scheme.eq_ignore_ascii_case(DEEP_LINK_SCHEME))
+            .map(|(_, rest)| rest.trim_start_matches('/'))
+            .ok_or_else(|| DownloadError::InvalidUrl(link.to_string()))?;
+        let (action, query) = rest.split_once('?').unwrap_or((rest, ""));
+        let action = action.trim_end_matches('/');
+        if !action.eq_ignore_ascii_case("add") {
+            return Err(DownloadError::InvalidUrl(format!("unknown deep link action \"{}\"", action)));
+        }
+
+        let mut url = None;
+        let mut deep_link = DeepLink {
+            url: String::new(),
+            preset: None,
+            format: None,
+            quality: None,
+        };
+        for pair in query.split('&').filter(|pair| !pair.is_empty()) {
+            let (name, value) = pair.split_once('=').unwrap_or((pair, ""));
+            let value = percent_decode(value)?;
+            match name {
+                "url" => url = Some(value),
+                "preset" => {
+                    deep_link.preset = Some(match value.as_str() {
+                        "audio" => DeepLinkPreset::Audio,
+                        "video" => DeepLinkPreset::Video,
+                        _ => return Err(DownloadError::InvalidFormat(value)),
+                    })
+                }
+                "format" => deep_link.format = Some(value.parse()?),
+                "quality" => deep_link.quality = Some(value.parse()?),
+                // Left for newer versions (or added by the page)
+                _ => {}
+            }
+        }
+
+        let url = url.ok_or_else(|| DownloadError::InvalidUrl("deep link has no url".to_string()))?;
+        if url_host(&url).is_none() || url.contains(char::is_whitespace) {
+            return Err(DownloadError::InvalidUrl(url));
+        }
+        deep_link.url = url;
+        Ok(deep_link)
+    }
+
+    /// Format to download in, given the default format
+    pub fn format(&self, default: OutputFormat) -> OutputFormat {
+        match (self.format, self.preset) {
+            (Some(format), _) => format,
+            (None, Some(DeepLinkPreset::Audio)) if !default.is_audio() => OutputFormat::Audio(AudioCodec::Mp3),
+            (None, Some(DeepLinkPreset::Video)) if default.is_audio() => OutputFormat::Video(VideoContainer::Mp4),
+            _ => default,
+        }
+    }
+}
+
+/// Decodes `%XX` escapes (a `+` is kept: links aren't form-encoded)
+fn percent_decode(value: &str) -> Result<String, DownloadError> {
+    let invalid = || DownloadError::InvalidUrl(format!("invalid escape in deep link: {}", value));
+    let mut bytes = Vec::with_capacity(value.len());
+    let mut rest = value.as_bytes();
+    while let Some((&byte, tail)) = rest.split_first() {
+        if byte == b'%' {
+            let hex = tail.get(..2).and_then(|hex| std::str::from_utf8(hex).ok()).ok_or_else(invalid)?;
+            bytes.push(u8::from_str_radix(hex, 16).map_err(|_| invalid())?);
+            rest = &tail[2..];
+        } else {
+            bytes.push(byte);
+            rest = tail;
+        }
+    }
+    String::from_utf8(bytes).map_err(|_| invalid())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_deep_link() {
+        let link = DeepLink::parse("mediagrab://add?url=https%3A%2F%2Fwww.youtube.com%2Fwatch%3Fv%3DdQw4w9WgXcQ%26t%3D42&preset=audio").unwrap();
+        assert_eq!(link.url, "https://www.youtube.com/watch?v=dQw4w9WgXcQ&t=42");
+        assert_eq!(link.preset, Some(DeepLinkPreset::Audio));
+
+        let link = DeepLink::parse("MediaGrab:///add/?url=https://youtu.be/dQw4w9WgXcQ&format=video-webm&quality=720p&ref=x").unwrap();
+        assert_eq!(link.url, "https://youtu.be/dQw4w9WgXcQ");
+        assert_eq!(link.format, Some(OutputFormat::Video(VideoContainer::Webm)));
+        assert_eq!(link.quality, Some(Quality::P720));
+
+        assert!(DeepLink::parse("https://add?url=https://youtu.be/x").is_err());
+        assert!(DeepLink::parse("mediagrab://remove?url=https://youtu.be/x").is_err());
+        assert!(DeepLink::parse("mediagrab://add?preset=audio").is_err());
+        assert!(DeepLink::parse("mediagrab://add?url=javascript:alert(1)").is_err());
+        assert!(DeepLink::parse("mediagrab://add?url=https://youtu.be/x&preset=loud").is_err());
+        assert!(DeepLink::parse("mediagrab://add?url=https%3A%2F%2Fyoutu.be%2").is_err());
+    }
+
+    #[test]
+    fn test_deep_link_format() {
+        let link = DeepLink::parse("mediagrab://add?url=https://youtu.be/x&preset=audio").unwrap();
+        assert_eq!(link.format(OutputFormat::Video(VideoContainer::Mkv)), OutputFormat::Audio(AudioCodec::Mp3));
+        assert_eq!(link.format(OutputFormat::Audio(AudioCodec::Flac)), OutputFormat::Audio(AudioCodec::Flac));
+
+        let link = DeepLink::parse("mediagrab://add?url=https://youtu.be/x&preset=video").unwrap();
+        assert_eq!(link.format(OutputFormat::Audio(AudioCodec::Flac)), OutputFormat::Video(VideoContainer::Mp4));
+
+        let link = DeepLink::parse("mediagrab://add?url=https://youtu.be/x&preset=audio&format=audio-opus").unwrap();
+        assert_eq!(link.format(OutputFormat::Audio(AudioCodec::Flac)), OutputFormat::Audio(AudioCodec::Opus));
+    }
+}
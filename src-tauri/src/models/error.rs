@@ -17,6 +17,21 @@ pub enum DownloadError {
     #[error("Invalid URL: {0}")]
     InvalidUrl(String),
     
+    #[error("Invalid format: {0}")]
+    InvalidFormat(String),
+    
+    #[error("Invalid quality: {0}")]
+    InvalidQuality(String),
+    
+    #[error("Invalid time range: {0}")]
+    InvalidTimeRange(String),
+    
+    #[error("Invalid filename template: {0}")]
+    InvalidTemplate(String),
+    
+    #[error("Invalid playlist filter: {0}")]
+    InvalidFilter(String),
+    
     #[error("Download already in progress")]
     AlreadyDownloading,
     
@@ -26,6 +41,21 @@ pub enum DownloadError {
     #[error("Download failed: {0}")]
     DownloadFailed(String),
     
+    #[error("Transcoding failed: {0}")]
+    TranscodeFailed(String),
+    
+    #[error("Loudness normalization failed: {0}")]
+    LoudnessNormalizationFailed(String),
+    
+    #[error("Already downloaded (recorded in the download archive): {0}")]
+    AlreadyArchived(String),
+    
+    #[error("Skipped by the playlist filter: {0}")]
+    FilteredOut(String),
+    
+    #[error("Missed the scheduled deadline: {0}")]
+    DeadlineMissed(String),
+    
     #[error("Output folder not accessible: {0}")]
     FolderNotAccessible(String),
     
@@ -68,9 +98,19 @@ impl DownloadError {
     pub fn category(&self) -> &'static str {
         match self {
             DownloadError::InvalidUrl(_) => "validation",
+            DownloadError::InvalidFormat(_) => "validation",
+            DownloadError::InvalidQuality(_) => "validation",
+            DownloadError::InvalidTimeRange(_) => "validation",
+            DownloadError::InvalidTemplate(_) => "validation",
+            DownloadError::InvalidFilter(_) => "validation",
             DownloadError::AlreadyDownloading => "state",
             DownloadError::ProcessSpawnError(_) => "process",
             DownloadError::DownloadFailed(_) => "download",
+            DownloadError::TranscodeFailed(_) => "postprocess",
+            DownloadError::LoudnessNormalizationFailed(_) => "postprocess",
+            DownloadError::AlreadyArchived(_) => "archive",
+            DownloadError::FilteredOut(_) => "filter",
+            DownloadError::DeadlineMissed(_) => "schedule",
             DownloadError::FolderNotAccessible(_) => "filesystem",
             DownloadError::ExecutableNotFound(_) => "dependency",
             DownloadError::PrivateVideo => "access",
@@ -86,6 +126,23 @@ impl DownloadError {
         }
     }
     
+    /// Exit code for the command-line binary, by category
+    ///
+    /// Skips (archive, filter) aren't failures and map to 0.
+    pub fn exit_code(&self) -> i32 {
+        match self.category() {
+            "archive" | "filter" => 0,
+            "validation" => 2,
+            "network" | "rate_limit" => 3,
+            "access" | "auth" => 4,
+            "not_found" => 5,
+            "filesystem" => 6,
+            "dependency" | "process" => 7,
+            "download" | "postprocess" => 8,
+            _ => 1,
+        }
+    }
+
     /// Check if this error type supports retry
     pub fn is_retryable(&self) -> bool {
         matches!(
@@ -107,6 +164,9 @@ impl DownloadError {
             DownloadError::NetworkError(_) => Some("Check your internet connection"),
             DownloadError::FolderNotAccessible(_) => Some("Choose a different output folder"),
             DownloadError::ExecutableNotFound(_) => Some("Reinstall the application"),
+            DownloadError::AlreadyArchived(_) => Some("Remove it from the download archive to download it again"),
+            DownloadError::FilteredOut(_) => Some("Change the playlist filter to include it"),
+            DownloadError::DeadlineMissed(_) => Some("Schedule it again with a later deadline"),
             _ => None,
         }
     }
@@ -460,6 +520,7 @@ mod tests {
     #[test]
     fn test_download_error_category() {
         assert_eq!(DownloadError::InvalidUrl("test".into()).category(), "validation");
+        assert_eq!(DownloadError::InvalidFormat("test".into()).category(), "validation");
         assert_eq!(DownloadError::NetworkError("test".into()).category(), "network");
         assert_eq!(DownloadError::PrivateVideo.category(), "access");
         assert_eq!(DownloadError::RateLimited.category(), "rate_limit");
@@ -521,6 +582,16 @@ mod tests {
         assert!(err.to_command_error().contains("does not exist"));
     }
 
+    #[test]
+    fn test_download_error_exit_code() {
+        assert_eq!(DownloadError::InvalidUrl("test".into()).exit_code(), 2);
+        assert_eq!(DownloadError::Timeout("test".into()).exit_code(), 3);
+        assert_eq!(DownloadError::AuthenticationRequired.exit_code(), 4);
+        assert_eq!(DownloadError::ExecutableNotFound("yt-dlp".into()).exit_code(), 7);
+        assert_eq!(DownloadError::AlreadyArchived("test".into()).exit_code(), 0);
+        assert_eq!(DownloadError::GenericError("test".into()).exit_code(), 1);
+    }
+
     #[test]
     fn test_suggested_action() {
         assert!(DownloadError::PrivateVideo.suggested_action().is_some());
@@ -0,0 +1,364 @@
+//! Output format and quality types
+//!
+//! Typed replacements for the free-form `format` and `quality` strings sent by
+//! the frontend. Both serialize to the same string values as before
+//! ("video-mp4", "audio-mp3", "1080p", ...) so stored preferences and history
+//! entries stay compatible.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+use super::error::DownloadError;
+
+/// Container used when merging video and audio streams
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum VideoContainer {
+    Mp4,
+    Webm,
+    Mkv,
+}
+
+impl VideoContainer {
+    /// File extension, also used as yt-dlp `--merge-output-format`
+    pub fn extension(&self) -> &'static str {
+        match self {
+            VideoContainer::Mp4 => "mp4",
+            VideoContainer::Webm => "webm",
+            VideoContainer::Mkv => "mkv",
+        }
+    }
+}
+
+/// Codec for audio-only downloads
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AudioCodec {
+    Mp3,
+    Aac,
+    Opus,
+    Flac,
+    Wav,
+    /// Keep the source audio stream without re-encoding
+    Best,
+}
+
+impl AudioCodec {
+    /// Value passed to yt-dlp `--audio-format` (None keeps the original codec)
+    pub fn ytdlp_audio_format(&self) -> Option<&'static str> {
+        match self {
+            AudioCodec::Mp3 => Some("mp3"),
+            AudioCodec::Aac => Some("m4a"), // AAC in M4A container
+            AudioCodec::Opus => Some("opus"),
+            AudioCodec::Flac => Some("flac"),
+            AudioCodec::Wav => Some("wav"),
+            AudioCodec::Best => None,
+        }
+    }
+
+    /// Whether the codec is lossy (and therefore takes `--audio-quality`)
+    pub fn is_lossy(&self) -> bool {
+        matches!(self, AudioCodec::Mp3 | AudioCodec::Aac | AudioCodec::Opus)
+    }
+}
+
+/// Output format selected by the user
+///
+/// Serialized as "video-<container>" or "audio-<codec>".
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum OutputFormat {
+    Video(VideoContainer),
+    Audio(AudioCodec),
+}
+
+impl OutputFormat {
+    /// Every supported format, in UI order
+    pub const ALL: [OutputFormat; 9] = [
+        OutputFormat::Video(VideoContainer::Mp4),
+        OutputFormat::Video(VideoContainer::Webm),
+        OutputFormat::Video(VideoContainer::Mkv),
+        OutputFormat::Audio(AudioCodec::Mp3),
+        OutputFormat::Audio(AudioCodec::Aac),
+        OutputFormat::Audio(AudioCodec::Opus),
+        OutputFormat::Audio(AudioCodec::Flac),
+        OutputFormat::Audio(AudioCodec::Wav),
+        OutputFormat::Audio(AudioCodec::Best),
+    ];
+
+    /// Whether this format extracts audio only
+    pub fn is_audio(&self) -> bool {
+        matches!(self, OutputFormat::Audio(_))
+    }
+
+    /// Extension of the final file (audio "best" keeps the source codec, usually m4a)
+    pub fn expected_extension(&self) -> &'static str {
+        match self {
+            OutputFormat::Video(container) => container.extension(),
+            OutputFormat::Audio(codec) => codec.ytdlp_audio_format().unwrap_or("m4a"),
+        }
+    }
+
+    /// Wire representation (e.g. "video-mp4")
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OutputFormat::Video(VideoContainer::Mp4) => "video-mp4",
+            OutputFormat::Video(VideoContainer::Webm) => "video-webm",
+            OutputFormat::Video(VideoContainer::Mkv) => "video-mkv",
+            OutputFormat::Audio(codec) => match codec {
+                AudioCodec::Mp3 => "audio-mp3",
+                AudioCodec::Aac => "audio-aac",
+                AudioCodec::Opus => "audio-opus",
+                AudioCodec::Flac => "audio-flac",
+                AudioCodec::Wav => "audio-wav",
+                AudioCodec::Best => "audio-best",
+            },
+        }
+    }
+}
+
+impl Default for OutputFormat {
+    fn default() -> Self {
+        OutputFormat::Video(VideoContainer::Mp4)
+    }
+}
+
+impl fmt::Display for OutputFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for OutputFormat {
+    type Err = DownloadError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (kind, name) = s
+            .trim()
+            .split_once('-')
+            .ok_or_else(|| DownloadError::InvalidFormat(s.to_string()))?;
+
+        match kind {
+            "video" => {
+                let container = match name {
+                    "mp4" => VideoContainer::Mp4,
+                    "webm" => VideoContainer::Webm,
+                    "mkv" => VideoContainer::Mkv,
+                    _ => return Err(DownloadError::InvalidFormat(s.to_string())),
+                };
+                Ok(OutputFormat::Video(container))
+            }
+            "audio" => {
+                let codec = match name {
+                    "mp3" => AudioCodec::Mp3,
+                    "aac" => AudioCodec::Aac,
+                    "opus" => AudioCodec::Opus,
+                    "flac" => AudioCodec::Flac,
+                    "wav" => AudioCodec::Wav,
+                    "best" => AudioCodec::Best,
+                    _ => return Err(DownloadError::InvalidFormat(s.to_string())),
+                };
+                Ok(OutputFormat::Audio(codec))
+            }
+            _ => Err(DownloadError::InvalidFormat(s.to_string())),
+        }
+    }
+}
+
+impl TryFrom<String> for OutputFormat {
+    type Error = DownloadError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<OutputFormat> for String {
+    fn from(format: OutputFormat) -> Self {
+        format.as_str().to_string()
+    }
+}
+
+/// Video quality (maximum resolution) selected by the user
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub enum Quality {
+    /// Best available resolution
+    #[default]
+    Best,
+    P2160,
+    P1440,
+    P1080,
+    P720,
+    P480,
+    P360,
+}
+
+impl Quality {
+    /// Every supported quality, highest first
+    pub const ALL: [Quality; 7] = [
+        Quality::Best,
+        Quality::P2160,
+        Quality::P1440,
+        Quality::P1080,
+        Quality::P720,
+        Quality::P480,
+        Quality::P360,
+    ];
+
+    /// Maximum video height in pixels (None = unlimited)
+    pub fn max_height(&self) -> Option<u32> {
+        match self {
+            Quality::Best => None,
+            Quality::P2160 => Some(2160),
+            Quality::P1440 => Some(1440),
+            Quality::P1080 => Some(1080),
+            Quality::P720 => Some(720),
+            Quality::P480 => Some(480),
+            Quality::P360 => Some(360),
+        }
+    }
+
+    /// Wire representation (e.g. "1080p")
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Quality::Best => "best",
+            Quality::P2160 => "2160p",
+            Quality::P1440 => "1440p",
+            Quality::P1080 => "1080p",
+            Quality::P720 => "720p",
+            Quality::P480 => "480p",
+            Quality::P360 => "360p",
+        }
+    }
+}
+
+impl fmt::Display for Quality {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Quality {
+    type Err = DownloadError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Quality::ALL
+            .into_iter()
+            .find(|quality| quality.as_str() == s.trim())
+            .ok_or_else(|| DownloadError::InvalidQuality(s.to_string()))
+    }
+}
+
+impl TryFrom<String> for Quality {
+    type Error = DownloadError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<Quality> for String {
+    fn from(quality: Quality) -> Self {
+        quality.as_str().to_string()
+    }
+}
+
+/// Preferred video codec, applied through yt-dlp format sorting
+///
+/// Sorting by `vcodec:<codec>` prefers that codec and anything ranked below it,
+/// so `h264` keeps files playable on older hardware.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum VideoCodec {
+    Av1,
+    Vp9,
+    H264,
+}
+
+impl VideoCodec {
+    /// Value used in the yt-dlp `-S vcodec:` sort field
+    pub fn sort_value(&self) -> &'static str {
+        match self {
+            VideoCodec::Av1 => "av01",
+            VideoCodec::Vp9 => "vp9",
+            VideoCodec::H264 => "h264",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_output_format_roundtrip() {
+        for format in OutputFormat::ALL {
+            let parsed: OutputFormat = format.as_str().parse().unwrap();
+            assert_eq!(parsed, format);
+        }
+    }
+
+    #[test]
+    fn test_output_format_serde_uses_legacy_strings() {
+        let json = serde_json::to_string(&OutputFormat::Audio(AudioCodec::Mp3)).unwrap();
+        assert_eq!(json, "\"audio-mp3\"");
+
+        let parsed: OutputFormat = serde_json::from_str("\"video-mkv\"").unwrap();
+        assert_eq!(parsed, OutputFormat::Video(VideoContainer::Mkv));
+    }
+
+    #[test]
+    fn test_output_format_rejects_unknown() {
+        assert!(matches!(
+            "video-mp5".parse::<OutputFormat>(),
+            Err(DownloadError::InvalidFormat(_))
+        ));
+        assert!(matches!(
+            "mp4".parse::<OutputFormat>(),
+            Err(DownloadError::InvalidFormat(_))
+        ));
+        assert!(serde_json::from_str::<OutputFormat>("\"audio-ogg\"").is_err());
+    }
+
+    #[test]
+    fn test_quality_parsing() {
+        assert_eq!("best".parse::<Quality>().unwrap(), Quality::Best);
+        assert_eq!("1080p".parse::<Quality>().unwrap().max_height(), Some(1080));
+        assert!(matches!(
+            "1080".parse::<Quality>(),
+            Err(DownloadError::InvalidQuality(_))
+        ));
+    }
+
+    #[test]
+    fn test_quality_ladder_roundtrip() {
+        for quality in Quality::ALL {
+            assert_eq!(quality.as_str().parse::<Quality>().unwrap(), quality);
+        }
+        assert_eq!("2160p".parse::<Quality>().unwrap().max_height(), Some(2160));
+        assert_eq!("360p".parse::<Quality>().unwrap().max_height(), Some(360));
+    }
+
+    #[test]
+    fn test_video_codec_serde() {
+        let codec: VideoCodec = serde_json::from_str("\"h264\"").unwrap();
+        assert_eq!(codec, VideoCodec::H264);
+        assert_eq!(codec.sort_value(), "h264");
+        assert_eq!(VideoCodec::Av1.sort_value(), "av01");
+    }
+
+    #[test]
+    fn test_expected_extension() {
+        assert_eq!(OutputFormat::Video(VideoContainer::Mkv).expected_extension(), "mkv");
+        assert_eq!(OutputFormat::Audio(AudioCodec::Aac).expected_extension(), "m4a");
+        assert_eq!(OutputFormat::Audio(AudioCodec::Flac).expected_extension(), "flac");
+    }
+
+    #[test]
+    fn test_audio_codec_flags() {
+        assert_eq!(AudioCodec::Aac.ytdlp_audio_format(), Some("m4a"));
+        assert_eq!(AudioCodec::Best.ytdlp_audio_format(), None);
+        assert!(AudioCodec::Mp3.is_lossy());
+        assert!(!AudioCodec::Flac.is_lossy());
+    }
+}
@@ -0,0 +1,124 @@
+//! Livestream recording options
+//!
+//! Live streams have no total size or ETA; yt-dlp records until the stream
+//! ends (or a duration limit is hit). Scheduled streams can be waited for.
+
+use serde::{Deserialize, Serialize};
+
+/// Default seconds between checks while waiting for a stream to start
+pub const DEFAULT_POLL_INTERVAL_SECONDS: u64 = 60;
+
+/// Where a live recording begins
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum LiveStart {
+    /// Join the stream at the live edge
+    #[default]
+    FromNow,
+    /// Download from the beginning of the stream (where the site keeps it)
+    FromStart,
+}
+
+/// Livestream recording settings
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LiveOptions {
+    /// Treat the URL as a livestream
+    #[serde(default)]
+    pub enabled: bool,
+    /// Record from the beginning or from now
+    #[serde(default)]
+    pub start: LiveStart,
+    /// Stop recording after this many seconds (None = until the stream ends)
+    #[serde(default)]
+    pub max_duration_seconds: Option<u64>,
+    /// Wait for a scheduled/upcoming stream instead of failing
+    #[serde(default)]
+    pub wait_for_stream: bool,
+    /// Seconds between checks while waiting
+    #[serde(default = "default_poll_interval")]
+    pub poll_interval_seconds: u64,
+}
+
+fn default_poll_interval() -> u64 {
+    DEFAULT_POLL_INTERVAL_SECONDS
+}
+
+impl Default for LiveOptions {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            start: LiveStart::default(),
+            max_duration_seconds: None,
+            wait_for_stream: false,
+            poll_interval_seconds: DEFAULT_POLL_INTERVAL_SECONDS,
+        }
+    }
+}
+
+impl LiveOptions {
+    /// yt-dlp arguments for live recording (empty when disabled)
+    pub fn to_args(&self) -> Vec<String> {
+        let mut args = Vec::new();
+        if !self.enabled {
+            return args;
+        }
+
+        args.push(
+            match self.start {
+                LiveStart::FromNow => "--no-live-from-start",
+                LiveStart::FromStart => "--live-from-start",
+            }
+            .to_string(),
+        );
+
+        if self.wait_for_stream {
+            args.push("--wait-for-video".to_string());
+            args.push(self.poll_interval_seconds.max(1).to_string());
+        }
+
+        // yt-dlp has no duration limit of its own; let ffmpeg stop the capture.
+        // MPEG-TS keeps the file playable if the recording is cut short.
+        if let Some(seconds) = self.max_duration_seconds.filter(|s| *s > 0) {
+            args.push("--downloader".to_string());
+            args.push("ffmpeg".to_string());
+            args.push("--downloader-args".to_string());
+            args.push(format!("ffmpeg:-t {}", seconds));
+            args.push("--hls-use-mpegts".to_string());
+        }
+
+        args
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_live_options_deserialize_defaults() {
+        let options: LiveOptions = serde_json::from_str(r#"{"enabled":true}"#).unwrap();
+        assert_eq!(options.start, LiveStart::FromNow);
+        assert_eq!(options.poll_interval_seconds, DEFAULT_POLL_INTERVAL_SECONDS);
+        assert!(!options.wait_for_stream);
+        assert!(options.max_duration_seconds.is_none());
+    }
+
+    #[test]
+    fn test_live_options_args() {
+        assert!(LiveOptions::default().to_args().is_empty());
+
+        let options = LiveOptions {
+            enabled: true,
+            start: LiveStart::FromStart,
+            max_duration_seconds: Some(3600),
+            wait_for_stream: true,
+            poll_interval_seconds: 30,
+        };
+        let args = options.to_args();
+        assert_eq!(args[0], "--live-from-start");
+        assert!(args.windows(2).any(|w| w == ["--wait-for-video", "30"]));
+        assert!(args.windows(2).any(|w| w == ["--downloader-args", "ffmpeg:-t 3600"]));
+        assert!(args.contains(&"--hls-use-mpegts".to_string()));
+    }
+}
@@ -0,0 +1,55 @@
+//! Loudness normalization types (EBU R128)
+//!
+//! Audio downloads can be normalized with ffmpeg's two-pass `loudnorm`
+//! filter. The first pass measures the file; the values are kept in history.
+
+use serde::{Deserialize, Serialize};
+
+/// Default integrated loudness target in LUFS (common for podcasts and streaming)
+pub const DEFAULT_TARGET_LUFS: f64 = -16.0;
+
+/// Maximum true peak in dBTP
+pub const TARGET_TRUE_PEAK: f64 = -1.5;
+
+/// Target loudness range in LU
+pub const TARGET_LRA: f64 = 11.0;
+
+/// Whether a target is within the range ffmpeg's loudnorm accepts
+pub fn is_valid_target_lufs(target: f64) -> bool {
+    (-70.0..=-5.0).contains(&target)
+}
+
+/// Loudness measured by the first `loudnorm` pass
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LoudnessMeasurement {
+    /// Integrated loudness of the download (LUFS)
+    pub input_i: f64,
+    /// True peak of the download (dBTP)
+    pub input_tp: f64,
+    /// Loudness range of the download (LU)
+    pub input_lra: f64,
+    /// Gating threshold (LUFS)
+    pub input_thresh: f64,
+    /// Offset gain applied in the second pass (LU)
+    pub target_offset: f64,
+    /// Target the file was normalized to (LUFS)
+    pub target_i: f64,
+    /// Integrated loudness after normalization (LUFS), if reported
+    #[serde(default)]
+    pub output_i: Option<f64>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_target_range() {
+        assert!(is_valid_target_lufs(DEFAULT_TARGET_LUFS));
+        assert!(is_valid_target_lufs(-23.0));
+        assert!(!is_valid_target_lufs(-80.0));
+        assert!(!is_valid_target_lufs(0.0));
+        assert!(!is_valid_target_lufs(f64::NAN));
+    }
+}
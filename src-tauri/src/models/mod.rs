@@ -2,12 +2,40 @@
 //! 
 //! This module defines all core types used throughout the application.
 
+pub mod api;
+pub mod archive;
+pub mod clipboard;
 pub mod config;
+pub mod deep_link;
 pub mod error;
+pub mod format;
+pub mod live;
+pub mod loudness;
+pub mod organize;
+pub mod playlist;
 pub mod progress;
+pub mod section;
+pub mod sponsorblock;
+pub mod subscription;
+pub mod subtitles;
+pub mod transcode;
 pub mod state;
 
+pub use api::*;
+pub use archive::*;
+pub use clipboard::*;
 pub use config::*;
+pub use deep_link::*;
 pub use error::*;
+pub use format::*;
+pub use live::*;
+pub use loudness::*;
+pub use organize::*;
+pub use playlist::*;
 pub use progress::*;
+pub use section::*;
+pub use sponsorblock::*;
+pub use subscription::*;
+pub use subtitles::*;
+pub use transcode::*;
 pub use state::*;
@@ -0,0 +1,49 @@
+//! Output folder organization rules
+//!
+//! Each rule adds one folder level below the output folder, in the order the
+//! rules are listed (e.g. `[site, uploader]` gives `Youtube/Some Channel/`).
+
+use serde::{Deserialize, Serialize};
+
+/// One level of the output folder hierarchy
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum OrganizeRule {
+    /// Site the media comes from (yt-dlp extractor, e.g. "Youtube")
+    Site,
+    /// Uploader or channel name
+    Uploader,
+    /// Playlist title (no folder for single videos)
+    Playlist,
+    /// "Audio" or "Video", depending on the output format
+    MediaType,
+    /// Upload year
+    Year,
+}
+
+impl OrganizeRule {
+    /// Whether the folder name is known before yt-dlp fetches metadata
+    pub fn is_static(&self) -> bool {
+        matches!(self, OrganizeRule::MediaType)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_organize_rule_serde() {
+        let rules: Vec<OrganizeRule> =
+            serde_json::from_str(r#"["site","mediaType","year"]"#).unwrap();
+        assert_eq!(
+            rules,
+            vec![OrganizeRule::Site, OrganizeRule::MediaType, OrganizeRule::Year]
+        );
+        assert_eq!(
+            serde_json::to_string(&OrganizeRule::Uploader).unwrap(),
+            r#""uploader""#
+        );
+        assert!(serde_json::from_str::<OrganizeRule>(r#""genre""#).is_err());
+    }
+}
@@ -0,0 +1,448 @@
+//! Playlist listings, selection and per-entry playlist context
+//!
+//! Playlists are queued as one item per entry. Each item is still downloaded
+//! with `--no-playlist`, so its position and the playlist title travel in the
+//! config for the filename template and organize rules.
+
+use std::collections::BTreeSet;
+
+use serde::{Deserialize, Serialize};
+
+use super::DownloadError;
+
+/// A single video entry in a playlist
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlaylistEntry {
+    /// Video ID
+    pub id: String,
+    /// Video title
+    pub title: String,
+    /// Video URL
+    pub url: String,
+    /// Thumbnail URL
+    pub thumbnail: Option<String>,
+    /// Duration in seconds
+    pub duration: Option<f64>,
+    /// Position in playlist (1-indexed)
+    pub playlist_index: u32,
+    /// Uploader/channel name
+    pub uploader: Option<String>,
+    /// Upload date (YYYYMMDD), when the listing includes it
+    #[serde(default)]
+    pub upload_date: Option<String>,
+}
+
+/// Playlist information
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlaylistInfo {
+    /// Playlist ID
+    pub id: String,
+    /// Playlist title
+    pub title: String,
+    /// Playlist URL
+    pub url: String,
+    /// Playlist thumbnail
+    pub thumbnail: Option<String>,
+    /// Channel/uploader name
+    pub uploader: Option<String>,
+    /// Number of videos listed
+    pub video_count: u32,
+    /// Size of the whole playlist, when the site reports it (may be more
+    /// than `video_count` if only a page was listed)
+    #[serde(default)]
+    pub total_count: Option<u32>,
+    /// List of videos in the playlist
+    pub entries: Vec<PlaylistEntry>,
+}
+
+/// Which playlist entries to download, by upload date, duration and title
+///
+/// Passed to yt-dlp as `--dateafter`/`--datebefore`/`--match-filters`, and
+/// applied to listed entries with `matches` so the preview shows what will
+/// be downloaded. Like yt-dlp, entries missing a date or duration pass; flat
+/// listings often lack dates, so yt-dlp checks those again when downloading.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlaylistFilter {
+    /// Uploaded on or after this date (YYYYMMDD or YYYY-MM-DD)
+    #[serde(default)]
+    pub date_after: Option<String>,
+    /// Uploaded on or before this date (YYYYMMDD or YYYY-MM-DD)
+    #[serde(default)]
+    pub date_before: Option<String>,
+    /// Seconds
+    #[serde(default)]
+    pub min_duration: Option<u64>,
+    /// Seconds
+    #[serde(default)]
+    pub max_duration: Option<u64>,
+    /// Keep only titles containing one of these (case-insensitive)
+    #[serde(default)]
+    pub title_includes: Vec<String>,
+    /// Drop titles containing any of these (case-insensitive)
+    #[serde(default)]
+    pub title_excludes: Vec<String>,
+}
+
+impl PlaylistFilter {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Checks that the dates parse and the duration bounds are in order
+    pub fn validate(&self) -> Result<(), DownloadError> {
+        for date in [&self.date_after, &self.date_before].into_iter().flatten() {
+            if parse_filter_date(date).is_none() {
+                return Err(DownloadError::InvalidFilter(format!("invalid date \"{}\"", date)));
+            }
+        }
+        if let (Some(after), Some(before)) = (self.after(), self.before()) {
+            if after > before {
+                return Err(DownloadError::InvalidFilter("start date is after end date".to_string()));
+            }
+        }
+        if let (Some(min), Some(max)) = (self.min_duration, self.max_duration) {
+            if min > max {
+                return Err(DownloadError::InvalidFilter(
+                    "minimum duration is longer than maximum".to_string(),
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    fn after(&self) -> Option<String> {
+        self.date_after.as_deref().and_then(parse_filter_date)
+    }
+
+    fn before(&self) -> Option<String> {
+        self.date_before.as_deref().and_then(parse_filter_date)
+    }
+
+    /// Whether a listed entry passes the filter
+    pub fn matches(&self, entry: &PlaylistEntry) -> bool {
+        if let Some(date) = entry.upload_date.as_deref() {
+            if self.after().is_some_and(|after| date < after.as_str())
+                || self.before().is_some_and(|before| date > before.as_str())
+            {
+                return false;
+            }
+        }
+
+        if let Some(duration) = entry.duration {
+            if self.min_duration.is_some_and(|min| duration < min as f64)
+                || self.max_duration.is_some_and(|max| duration > max as f64)
+            {
+                return false;
+            }
+        }
+
+        let title = entry.title.to_lowercase();
+        let includes = words(&self.title_includes);
+        let excludes = words(&self.title_excludes);
+        (includes.is_empty() || includes.iter().any(|word| title.contains(word.as_str())))
+            && !excludes.iter().any(|word| title.contains(word.as_str()))
+    }
+
+    /// yt-dlp arguments for the filter (empty when there is nothing to filter)
+    pub fn to_args(&self) -> Vec<String> {
+        let mut args = Vec::new();
+        if let Some(after) = self.after() {
+            args.push("--dateafter".to_string());
+            args.push(after);
+        }
+        if let Some(before) = self.before() {
+            args.push("--datebefore".to_string());
+            args.push(before);
+        }
+
+        // Several --match-filters are OR'ed, so everything goes into one;
+        // "?" lets entries without the field through
+        let mut conditions = Vec::new();
+        if let Some(min) = self.min_duration {
+            conditions.push(format!("duration>=?{}", min));
+        }
+        if let Some(max) = self.max_duration {
+            conditions.push(format!("duration<=?{}", max));
+        }
+        if let Some(pattern) = title_pattern(&self.title_includes) {
+            conditions.push(format!("title~='{}'", pattern));
+        }
+        if let Some(pattern) = title_pattern(&self.title_excludes) {
+            conditions.push(format!("title!~='{}'", pattern));
+        }
+        if !conditions.is_empty() {
+            args.push("--match-filters".to_string());
+            args.push(conditions.join(" & "));
+        }
+
+        args
+    }
+}
+
+/// Parses YYYYMMDD or YYYY-MM-DD into YYYYMMDD
+fn parse_filter_date(date: &str) -> Option<String> {
+    let date = date.trim();
+    chrono::NaiveDate::parse_from_str(date, "%Y%m%d")
+        .or_else(|_| chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d"))
+        .ok()
+        .map(|date| date.format("%Y%m%d").to_string())
+}
+
+/// Lowercased, non-empty title words
+fn words(words: &[String]) -> Vec<String> {
+    words
+        .iter()
+        .map(|word| word.trim().to_lowercase())
+        .filter(|word| !word.is_empty())
+        .collect()
+}
+
+/// Case-insensitive regex matching any of the words, for `--match-filters`
+///
+/// ASCII punctuation is written as `\xHH` so it means itself in the regex
+/// and can't end the quoted value (`'`) or split the filter (`&`).
+fn title_pattern(title_words: &[String]) -> Option<String> {
+    let alternatives: Vec<String> = title_words
+        .iter()
+        .map(|word| word.trim())
+        .filter(|word| !word.is_empty())
+        .map(|word| {
+            word.chars()
+                .map(|c| match c {
+                    c if c.is_alphanumeric() || c == ' ' || !c.is_ascii() => c.to_string(),
+                    c => format!("\\x{:02x}", c as u32),
+                })
+                .collect()
+        })
+        .collect();
+    (!alternatives.is_empty()).then(|| format!("(?i)({})", alternatives.join("|")))
+}
+
+/// Inclusive range of 1-based playlist positions
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlaylistRange {
+    pub start: u32,
+    /// None = to the end of the playlist
+    #[serde(default)]
+    pub end: Option<u32>,
+}
+
+impl PlaylistRange {
+    fn contains(&self, index: u32) -> bool {
+        index >= self.start && self.end.is_none_or(|end| index <= end)
+    }
+}
+
+/// Which playlist entries to queue, and in what order
+///
+/// Indices and ranges are combined; with neither, every entry is selected.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlaylistSelection {
+    /// 1-based playlist positions
+    #[serde(default)]
+    pub indices: Vec<u32>,
+    #[serde(default)]
+    pub ranges: Vec<PlaylistRange>,
+    /// Keep only the N newest of the selected entries
+    #[serde(default)]
+    pub newest: Option<usize>,
+    /// Queue the last entry first
+    #[serde(default)]
+    pub reverse: bool,
+}
+
+impl PlaylistSelection {
+    fn includes(&self, index: u32) -> bool {
+        (self.indices.is_empty() && self.ranges.is_empty())
+            || self.indices.contains(&index)
+            || self.ranges.iter().any(|range| range.contains(index))
+    }
+
+    /// Applies the selection to entries given as (playlist index, upload date)
+    ///
+    /// Returns positions into `entries` in queueing order. "Newest" goes by
+    /// upload date (`YYYYMMDD`) when every selected entry has one; otherwise
+    /// the first entries are taken, as channels list their newest videos
+    /// first.
+    pub fn apply(&self, entries: &[(u32, Option<&str>)]) -> Vec<usize> {
+        let mut selected: Vec<usize> = (0..entries.len())
+            .filter(|&i| self.includes(entries[i].0))
+            .collect();
+
+        if let Some(count) = self.newest {
+            if selected.iter().all(|&i| entries[i].1.is_some()) {
+                let mut by_date = selected.clone();
+                // Stable sort keeps playlist order for videos from the same day
+                by_date.sort_by(|&a, &b| entries[b].1.cmp(&entries[a].1));
+                let keep: BTreeSet<usize> = by_date.into_iter().take(count).collect();
+                selected.retain(|i| keep.contains(i));
+            } else {
+                selected.truncate(count);
+            }
+        }
+
+        if self.reverse {
+            selected.reverse();
+        }
+        selected
+    }
+}
+
+/// Where a queued item came from when it was part of a playlist
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PlaylistContext {
+    /// Shared by every item queued from the same playlist
+    pub group_id: String,
+    /// 1-based position in the playlist
+    pub index: u32,
+    /// Number of entries in the playlist (for zero-padding the index)
+    pub count: u32,
+    pub title: String,
+}
+
+impl PlaylistContext {
+    /// Playlist index padded to the width of the playlist size, like yt-dlp
+    pub fn padded_index(&self) -> String {
+        let width = self.count.max(self.index).to_string().len();
+        format!("{:0width$}", self.index, width = width)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entries() -> Vec<(u32, Option<&'static str>)> {
+        vec![
+            (1, Some("20240105")),
+            (2, Some("20240301")),
+            (3, Some("20240210")),
+            (4, Some("20231201")),
+            (5, Some("20240401")),
+        ]
+    }
+
+    #[test]
+    fn test_playlist_selection_indices_and_ranges() {
+        assert_eq!(PlaylistSelection::default().apply(&entries()), vec![0, 1, 2, 3, 4]);
+
+        let selection = PlaylistSelection {
+            indices: vec![1],
+            ranges: vec![PlaylistRange { start: 4, end: None }],
+            ..Default::default()
+        };
+        assert_eq!(selection.apply(&entries()), vec![0, 3, 4]);
+
+        let selection = PlaylistSelection {
+            ranges: vec![PlaylistRange { start: 2, end: Some(3) }],
+            reverse: true,
+            ..Default::default()
+        };
+        assert_eq!(selection.apply(&entries()), vec![2, 1]);
+    }
+
+    #[test]
+    fn test_playlist_selection_newest() {
+        let selection = PlaylistSelection {
+            newest: Some(2),
+            ..Default::default()
+        };
+        // By upload date, kept in playlist order
+        assert_eq!(selection.apply(&entries()), vec![1, 4]);
+
+        // Without dates the first entries are the newest
+        let undated = [(1, None), (2, None), (3, Some("20240101"))];
+        assert_eq!(selection.apply(&undated), vec![0, 1]);
+    }
+
+    fn entry(title: &str, duration: Option<f64>, upload_date: Option<&str>) -> PlaylistEntry {
+        PlaylistEntry {
+            id: "id".to_string(),
+            title: title.to_string(),
+            url: "https://example.com/watch".to_string(),
+            thumbnail: None,
+            duration,
+            playlist_index: 1,
+            uploader: None,
+            upload_date: upload_date.map(|d| d.to_string()),
+        }
+    }
+
+    #[test]
+    fn test_playlist_filter_matches() {
+        let filter = PlaylistFilter {
+            date_after: Some("2025-01-01".to_string()),
+            min_duration: Some(300),
+            title_excludes: vec!["#shorts".to_string()],
+            ..Default::default()
+        };
+        assert!(filter.validate().is_ok());
+        assert!(filter.matches(&entry("Long video", Some(600.0), Some("20250102"))));
+        assert!(!filter.matches(&entry("Old video", Some(600.0), Some("20241231"))));
+        assert!(!filter.matches(&entry("Short", Some(59.0), Some("20250102"))));
+        assert!(!filter.matches(&entry("Clip #Shorts", Some(600.0), Some("20250102"))));
+        // Missing fields pass, as in yt-dlp
+        assert!(filter.matches(&entry("Unknown", None, None)));
+
+        let includes = PlaylistFilter {
+            title_includes: vec!["Live".to_string(), "Q&A".to_string()],
+            ..Default::default()
+        };
+        assert!(includes.matches(&entry("Weekly q&a", None, None)));
+        assert!(!includes.matches(&entry("Vlog", None, None)));
+    }
+
+    #[test]
+    fn test_playlist_filter_args() {
+        assert!(PlaylistFilter::default().to_args().is_empty());
+
+        let filter = PlaylistFilter {
+            date_after: Some("2025-01-01".to_string()),
+            date_before: Some("20251231".to_string()),
+            min_duration: Some(300),
+            title_includes: vec!["Q&A".to_string()],
+            title_excludes: vec!["#shorts".to_string(), "it's".to_string()],
+            ..Default::default()
+        };
+        assert_eq!(
+            filter.to_args(),
+            vec![
+                "--dateafter",
+                "20250101",
+                "--datebefore",
+                "20251231",
+                "--match-filters",
+                r"duration>=?300 & title~='(?i)(Q\x26A)' & title!~='(?i)(\x23shorts|it\x27s)'",
+            ]
+        );
+
+        let invalid = PlaylistFilter {
+            date_after: Some("yesterday".to_string()),
+            ..Default::default()
+        };
+        assert!(invalid.validate().is_err());
+        let reversed = PlaylistFilter {
+            min_duration: Some(600),
+            max_duration: Some(60),
+            ..Default::default()
+        };
+        assert!(reversed.validate().is_err());
+    }
+
+    #[test]
+    fn test_padded_index() {
+        let context = PlaylistContext {
+            group_id: "g".to_string(),
+            index: 7,
+            count: 120,
+            title: "Mix".to_string(),
+        };
+        assert_eq!(context.padded_index(), "007");
+    }
+}
@@ -16,6 +16,9 @@ pub struct ProgressEvent {
     pub speed: String,
     /// Estimated time remaining in seconds
     pub eta_seconds: Option<u64>,
-    /// Current status ("downloading" or "merging")
+    /// Current status ("downloading", "recording" or "merging")
     pub status: String,
+    /// Time recorded so far, for livestreams (which have no total or ETA)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub elapsed_seconds: Option<u64>,
 }
@@ -0,0 +1,191 @@
+//! Clip section types
+//!
+//! Time ranges for downloading only part of a video. Ranges travel over IPC
+//! as "hh:mm:ss-hh:mm:ss" strings (end may be omitted for "until the end").
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+use super::error::DownloadError;
+
+/// A time range to download, in whole seconds
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct TimeRange {
+    /// Start offset in seconds
+    pub start: u64,
+    /// End offset in seconds (None = until the end of the video)
+    pub end: Option<u64>,
+}
+
+impl TimeRange {
+    /// Creates a validated range
+    pub fn new(start: u64, end: Option<u64>) -> Result<Self, DownloadError> {
+        if let Some(end) = end {
+            if end <= start {
+                return Err(DownloadError::InvalidTimeRange(format!(
+                    "end {} must be after start {}",
+                    format_timestamp(end),
+                    format_timestamp(start)
+                )));
+            }
+        }
+        Ok(Self { start, end })
+    }
+
+    /// Value for yt-dlp `--download-sections` (e.g. "*90-150", "*600-inf")
+    pub fn to_ytdlp_section(&self) -> String {
+        match self.end {
+            Some(end) => format!("*{}-{}", self.start, end),
+            None => format!("*{}-inf", self.start),
+        }
+    }
+}
+
+impl fmt::Display for TimeRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.end {
+            Some(end) => write!(f, "{}-{}", format_timestamp(self.start), format_timestamp(end)),
+            None => write!(f, "{}-", format_timestamp(self.start)),
+        }
+    }
+}
+
+impl FromStr for TimeRange {
+    type Err = DownloadError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (start, end) = s
+            .trim()
+            .split_once('-')
+            .ok_or_else(|| DownloadError::InvalidTimeRange(format!("missing '-' in \"{}\"", s)))?;
+
+        let start = parse_timestamp(start)
+            .ok_or_else(|| DownloadError::InvalidTimeRange(format!("invalid start \"{}\"", start.trim())))?;
+
+        let end = if end.trim().is_empty() {
+            None
+        } else {
+            Some(
+                parse_timestamp(end)
+                    .ok_or_else(|| DownloadError::InvalidTimeRange(format!("invalid end \"{}\"", end.trim())))?,
+            )
+        };
+
+        TimeRange::new(start, end)
+    }
+}
+
+impl TryFrom<String> for TimeRange {
+    type Error = DownloadError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        value.parse()
+    }
+}
+
+impl From<TimeRange> for String {
+    fn from(range: TimeRange) -> Self {
+        range.to_string()
+    }
+}
+
+/// Parse a timestamp like "01:23:45", "23:45" or "45" to seconds
+///
+/// Returns None for empty or malformed input. Minutes and seconds must be
+/// below 60 when a larger unit is present.
+pub fn parse_timestamp(s: &str) -> Option<u64> {
+    let s = s.trim();
+
+    if s.is_empty() {
+        return None;
+    }
+
+    // Parse time format: HH:MM:SS or MM:SS or SS
+    let parts: Vec<&str> = s.split(':').collect();
+
+    match parts.len() {
+        1 => parts[0].parse().ok(),
+        2 => {
+            let mins: u64 = parts[0].parse().ok()?;
+            let secs: u64 = parts[1].parse().ok()?;
+            if secs >= 60 {
+                return None;
+            }
+            mins.checked_mul(60)?.checked_add(secs)
+        }
+        3 => {
+            let hours: u64 = parts[0].parse().ok()?;
+            let mins: u64 = parts[1].parse().ok()?;
+            let secs: u64 = parts[2].parse().ok()?;
+            if mins >= 60 || secs >= 60 {
+                return None;
+            }
+            hours.checked_mul(3600)?.checked_add(mins * 60 + secs)
+        }
+        _ => None,
+    }
+}
+
+/// Format seconds as "hh:mm:ss"
+pub fn format_timestamp(seconds: u64) -> String {
+    format!("{:02}:{:02}:{:02}", seconds / 3600, (seconds % 3600) / 60, seconds % 60)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn test_parse_timestamp_formats() {
+        assert_eq!(parse_timestamp("01:30"), Some(90));
+        assert_eq!(parse_timestamp("01:30:45"), Some(5445));
+        assert_eq!(parse_timestamp("45"), Some(45));
+        assert_eq!(parse_timestamp(""), None);
+        assert_eq!(parse_timestamp("1:75"), None);
+        assert_eq!(parse_timestamp("a:b"), None);
+    }
+
+    #[test]
+    fn test_parse_timestamp_overflow() {
+        assert_eq!(parse_timestamp(&format!("{}:00", u64::MAX)), None);
+        assert_eq!(parse_timestamp(&format!("{}:00:00", u64::MAX / 1000)), None);
+        assert!(format!("{}:00:00-", u64::MAX).parse::<TimeRange>().is_err());
+    }
+
+    #[test]
+    fn test_time_range_parsing() {
+        let range: TimeRange = "00:01:00-00:02:30".parse().unwrap();
+        assert_eq!(range, TimeRange { start: 60, end: Some(150) });
+        assert_eq!(range.to_ytdlp_section(), "*60-150");
+
+        let open: TimeRange = "10:00-".parse().unwrap();
+        assert_eq!(open.end, None);
+        assert_eq!(open.to_ytdlp_section(), "*600-inf");
+    }
+
+    #[test]
+    fn test_time_range_rejects_invalid() {
+        assert!(matches!(
+            "00:02:00-00:01:00".parse::<TimeRange>(),
+            Err(DownloadError::InvalidTimeRange(_))
+        ));
+        assert!(matches!(
+            "00:01:00".parse::<TimeRange>(),
+            Err(DownloadError::InvalidTimeRange(_))
+        ));
+        assert!(serde_json::from_str::<TimeRange>("\"1:00-x\"").is_err());
+    }
+
+    proptest! {
+        /// Property: formatting then parsing a range yields the same range
+        #[test]
+        fn prop_time_range_roundtrip(start in 0u64..360_000, len in 1u64..36_000) {
+            let range = TimeRange::new(start, Some(start + len)).unwrap();
+            let parsed: TimeRange = range.to_string().parse().unwrap();
+            prop_assert_eq!(parsed, range);
+        }
+    }
+}
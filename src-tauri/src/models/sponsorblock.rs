@@ -0,0 +1,130 @@
+//! SponsorBlock option types
+//!
+//! Categories are sent to yt-dlp via `--sponsorblock-remove` (cut from the
+//! file) and `--sponsorblock-mark` (kept, but added as chapters).
+
+use serde::{Deserialize, Serialize};
+
+/// SponsorBlock segment category
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SponsorBlockCategory {
+    Sponsor,
+    Intro,
+    Outro,
+    Selfpromo,
+    Preview,
+    Filler,
+    Interaction,
+    MusicOfftopic,
+    /// Highlight point; can only be marked, not removed
+    PoiHighlight,
+}
+
+impl SponsorBlockCategory {
+    /// Category name as understood by yt-dlp
+    pub fn ytdlp_name(&self) -> &'static str {
+        match self {
+            SponsorBlockCategory::Sponsor => "sponsor",
+            SponsorBlockCategory::Intro => "intro",
+            SponsorBlockCategory::Outro => "outro",
+            SponsorBlockCategory::Selfpromo => "selfpromo",
+            SponsorBlockCategory::Preview => "preview",
+            SponsorBlockCategory::Filler => "filler",
+            SponsorBlockCategory::Interaction => "interaction",
+            SponsorBlockCategory::MusicOfftopic => "music_offtopic",
+            SponsorBlockCategory::PoiHighlight => "poi_highlight",
+        }
+    }
+
+    /// Whether yt-dlp can cut this category out of the file
+    pub fn is_removable(&self) -> bool {
+        !matches!(self, SponsorBlockCategory::PoiHighlight)
+    }
+}
+
+/// SponsorBlock settings for a download
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SponsorBlockOptions {
+    /// Categories to cut from the file
+    #[serde(default)]
+    pub remove: Vec<SponsorBlockCategory>,
+    /// Categories to keep but mark as chapters
+    #[serde(default)]
+    pub mark: Vec<SponsorBlockCategory>,
+}
+
+impl SponsorBlockOptions {
+    /// Whether SponsorBlock is used at all
+    pub fn is_enabled(&self) -> bool {
+        !self.remove.is_empty() || !self.mark.is_empty()
+    }
+
+    /// Value for `--sponsorblock-remove` (None if nothing is removed)
+    ///
+    /// Categories that cannot be removed are skipped.
+    pub fn remove_arg(&self) -> Option<String> {
+        join_categories(self.remove.iter().filter(|c| c.is_removable()))
+    }
+
+    /// Value for `--sponsorblock-mark` (None if nothing is marked)
+    ///
+    /// Categories that are also removed are skipped, since removal wins.
+    pub fn mark_arg(&self) -> Option<String> {
+        join_categories(
+            self.mark
+                .iter()
+                .filter(|c| !(c.is_removable() && self.remove.contains(c))),
+        )
+    }
+}
+
+/// Joins category names with commas, dropping duplicates
+fn join_categories<'a>(categories: impl Iterator<Item = &'a SponsorBlockCategory>) -> Option<String> {
+    let mut names: Vec<&'static str> = Vec::new();
+    for category in categories {
+        if !names.contains(&category.ytdlp_name()) {
+            names.push(category.ytdlp_name());
+        }
+    }
+
+    if names.is_empty() {
+        None
+    } else {
+        Some(names.join(","))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sponsorblock_serde() {
+        let options: SponsorBlockOptions =
+            serde_json::from_str(r#"{"remove":["sponsor","music_offtopic"],"mark":["intro"]}"#).unwrap();
+        assert_eq!(
+            options.remove,
+            vec![SponsorBlockCategory::Sponsor, SponsorBlockCategory::MusicOfftopic]
+        );
+        assert_eq!(options.mark, vec![SponsorBlockCategory::Intro]);
+
+        let empty: SponsorBlockOptions = serde_json::from_str("{}").unwrap();
+        assert!(!empty.is_enabled());
+    }
+
+    #[test]
+    fn test_remove_wins_over_mark() {
+        let options = SponsorBlockOptions {
+            remove: vec![SponsorBlockCategory::Sponsor, SponsorBlockCategory::PoiHighlight],
+            mark: vec![
+                SponsorBlockCategory::Sponsor,
+                SponsorBlockCategory::Intro,
+                SponsorBlockCategory::PoiHighlight,
+            ],
+        };
+        assert_eq!(options.remove_arg(), Some("sponsor".to_string()));
+        assert_eq!(options.mark_arg(), Some("intro,poi_highlight".to_string()));
+    }
+}
@@ -4,7 +4,7 @@ use serde::Serialize;
 
 /// Download job state
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
-#[serde(rename_all = "lowercase")]
+#[serde(rename_all = "camelCase")]
 pub enum DownloadState {
     /// No active download
     Idle,
@@ -14,10 +14,17 @@ pub enum DownloadState {
     Starting,
     /// Actively downloading
     Downloading,
+    /// Waiting for a scheduled livestream to start
+    WaitingForLive,
+    /// Recording a livestream
+    Recording,
     /// Merging video and audio streams
     Merging,
     /// Download completed successfully
     Completed,
+    /// Nothing was downloaded (already in the download archive or outside
+    /// the playlist filter)
+    Skipped,
     /// Download was cancelled by user
     Cancelled,
     /// Cancellation in progress
@@ -43,11 +50,24 @@ impl DownloadState {
             // From Starting
             (Starting, Downloading) |
             (Starting, Failed) |
-            // From Downloading
+            // From Downloading (live output is only recognized once yt-dlp runs)
             (Downloading, Merging) |
             (Downloading, Completed) |
+            (Downloading, Skipped) |
             (Downloading, Cancelling) |
             (Downloading, Failed) |
+            (Downloading, WaitingForLive) |
+            (Downloading, Recording) |
+            // From WaitingForLive
+            (WaitingForLive, Recording) |
+            (WaitingForLive, Downloading) |
+            (WaitingForLive, Cancelling) |
+            (WaitingForLive, Failed) |
+            // From Recording
+            (Recording, Merging) |
+            (Recording, Completed) |
+            (Recording, Cancelling) |
+            (Recording, Failed) |
             // From Merging
             (Merging, Completed) |
             (Merging, Cancelling) |
@@ -56,6 +76,7 @@ impl DownloadState {
             (Cancelling, Cancelled) |
             // Reset transitions
             (Completed, Idle) |
+            (Skipped, Idle) |
             (Cancelled, Idle) |
             (Failed, Idle)
         )
@@ -74,7 +95,10 @@ impl DownloadState {
     /// Check if a download is currently active (not in a terminal or idle state)
     pub fn is_active(&self) -> bool {
         use DownloadState::*;
-        matches!(self, Analyzing | Starting | Downloading | Merging | Cancelling)
+        matches!(
+            self,
+            Analyzing | Starting | Downloading | WaitingForLive | Recording | Merging | Cancelling
+        )
     }
 }
 
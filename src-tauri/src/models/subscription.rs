@@ -0,0 +1,144 @@
+//! Channel and playlist subscriptions
+//!
+//! A subscription is a channel or playlist URL that is checked on an
+//! interval; entries that haven't been downloaded yet are queued with the
+//! subscription's preset.
+
+use serde::{Deserialize, Serialize};
+
+use super::DownloadConfig;
+
+/// Default minutes between syncs
+pub const DEFAULT_SYNC_INTERVAL_MINUTES: u64 = 60;
+
+/// Shortest allowed sync interval, to go easy on the sites
+pub const MIN_SYNC_INTERVAL_MINUTES: u64 = 5;
+
+/// Entries listed per sync; channels list their newest videos first
+pub const SYNC_LISTING_LIMIT: u32 = 100;
+
+/// Most ignored URLs kept, well above what a sync listing can hold
+const MAX_IGNORED_URLS: usize = 500;
+
+/// A subscribed channel or playlist
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Subscription {
+    pub id: String,
+    /// Channel or playlist URL
+    pub url: String,
+    /// Playlist title from the last sync
+    #[serde(default)]
+    pub title: Option<String>,
+    /// Settings for queued entries; `url` is replaced with each entry's URL
+    pub preset: DownloadConfig,
+    /// Minutes between syncs
+    #[serde(default = "default_interval")]
+    pub interval_minutes: u64,
+    /// Paused subscriptions are only synced on request
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+    /// Unix timestamp
+    pub created_at: i64,
+    /// Unix timestamp of the last sync attempt
+    #[serde(default)]
+    pub last_synced_at: Option<i64>,
+    /// Why the last sync failed, if it did
+    #[serde(default)]
+    pub last_error: Option<String>,
+    /// Entries never to queue: the ones already there when subscribing
+    /// without backfill, and queued ones that were skipped (newest last,
+    /// capped at `MAX_IGNORED_URLS`)
+    #[serde(default)]
+    pub ignored_urls: Vec<String>,
+}
+
+fn default_interval() -> u64 {
+    DEFAULT_SYNC_INTERVAL_MINUTES
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+impl Subscription {
+    /// Whether an automatic sync is due at `now` (Unix time)
+    pub fn is_due(&self, now: i64) -> bool {
+        let interval = self.interval_minutes.max(MIN_SYNC_INTERVAL_MINUTES) as i64 * 60;
+        self.enabled && self.last_synced_at.is_none_or(|last| now - last >= interval)
+    }
+
+    /// Adds entries never to queue, forgetting the oldest ones past the cap
+    pub fn ignore_urls(&mut self, urls: impl IntoIterator<Item = String>) {
+        self.ignored_urls.extend(urls);
+        let excess = self.ignored_urls.len().saturating_sub(MAX_IGNORED_URLS);
+        self.ignored_urls.drain(..excess);
+    }
+
+    /// Download config for one entry of the subscription
+    pub fn config_for(&self, entry_url: &str) -> DownloadConfig {
+        DownloadConfig {
+            url: entry_url.to_string(),
+            ..self.preset.clone()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn subscription() -> Subscription {
+        serde_json::from_value(serde_json::json!({
+            "id": "sub-1",
+            "url": "https://www.youtube.com/@channel/videos",
+            "preset": {
+                "url": "",
+                "format": "video-mp4",
+                "quality": "best",
+                "outputFolder": "/downloads",
+                "embedSubtitles": false,
+                "cookiesFromBrowser": null
+            },
+            "createdAt": 1000
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_subscription_is_due() {
+        let mut sub = subscription();
+        assert!(sub.enabled);
+        assert_eq!(sub.interval_minutes, DEFAULT_SYNC_INTERVAL_MINUTES);
+        assert!(sub.is_due(1000), "never synced");
+
+        sub.last_synced_at = Some(1000);
+        assert!(!sub.is_due(1000 + 59 * 60));
+        assert!(sub.is_due(1000 + 60 * 60));
+
+        // Intervals below the minimum are clamped
+        sub.interval_minutes = 1;
+        assert!(!sub.is_due(1000 + 60));
+
+        sub.enabled = false;
+        assert!(!sub.is_due(1000 + 24 * 3600));
+    }
+
+    #[test]
+    fn test_subscription_ignored_urls_are_capped() {
+        let mut sub = subscription();
+        sub.ignore_urls((0..MAX_IGNORED_URLS).map(|i| format!("https://youtu.be/{}", i)));
+        sub.ignore_urls(["https://youtu.be/new".to_string()]);
+        assert_eq!(sub.ignored_urls.len(), MAX_IGNORED_URLS);
+        assert_eq!(sub.ignored_urls[0], "https://youtu.be/1");
+        assert_eq!(sub.ignored_urls.last().unwrap(), "https://youtu.be/new");
+    }
+
+    #[test]
+    fn test_subscription_config_for() {
+        let sub = subscription();
+        let config = sub.config_for("https://youtu.be/dQw4w9WgXcQ");
+        assert_eq!(config.url, "https://youtu.be/dQw4w9WgXcQ");
+        assert_eq!(config.output_folder, "/downloads");
+    }
+}
@@ -0,0 +1,41 @@
+//! Subtitle option types
+//!
+//! Carried on `DownloadConfig` and turned into yt-dlp flags by
+//! `download::args::build_subtitle_args`.
+
+use serde::{Deserialize, Serialize};
+
+/// Subtitle download options
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SubtitleOptions {
+    /// Whether to download subtitles
+    pub download_subtitles: bool,
+    /// Whether to embed subtitles in video
+    pub embed_subtitles: bool,
+    /// Selected language codes (empty = all)
+    pub languages: Vec<String>,
+    /// Output format: "srt", "vtt", "ass", or "best"
+    pub format: String,
+    /// Whether to include auto-generated captions
+    pub include_auto: bool,
+}
+
+impl SubtitleOptions {
+    /// Whether subtitles are requested at all (sidecar or embedded)
+    pub fn is_enabled(&self) -> bool {
+        self.download_subtitles || self.embed_subtitles
+    }
+}
+
+impl Default for SubtitleOptions {
+    fn default() -> Self {
+        Self {
+            download_subtitles: false,
+            embed_subtitles: false,
+            languages: vec![],
+            format: "srt".to_string(),
+            include_auto: false,
+        }
+    }
+}
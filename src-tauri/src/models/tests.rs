@@ -13,8 +13,11 @@ fn arb_download_state() -> impl Strategy<Value = DownloadState> {
         Just(DownloadState::Analyzing),
         Just(DownloadState::Starting),
         Just(DownloadState::Downloading),
+        Just(DownloadState::WaitingForLive),
+        Just(DownloadState::Recording),
         Just(DownloadState::Merging),
         Just(DownloadState::Completed),
+        Just(DownloadState::Skipped),
         Just(DownloadState::Cancelled),
         Just(DownloadState::Cancelling),
         Just(DownloadState::Failed),
@@ -36,8 +39,21 @@ const VALID_TRANSITIONS: &[(DownloadState, DownloadState)] = &[
     // From Downloading
     (DownloadState::Downloading, DownloadState::Merging),
     (DownloadState::Downloading, DownloadState::Completed),
+    (DownloadState::Downloading, DownloadState::Skipped),
     (DownloadState::Downloading, DownloadState::Cancelling),
     (DownloadState::Downloading, DownloadState::Failed),
+    (DownloadState::Downloading, DownloadState::WaitingForLive),
+    (DownloadState::Downloading, DownloadState::Recording),
+    // From WaitingForLive
+    (DownloadState::WaitingForLive, DownloadState::Recording),
+    (DownloadState::WaitingForLive, DownloadState::Downloading),
+    (DownloadState::WaitingForLive, DownloadState::Cancelling),
+    (DownloadState::WaitingForLive, DownloadState::Failed),
+    // From Recording
+    (DownloadState::Recording, DownloadState::Merging),
+    (DownloadState::Recording, DownloadState::Completed),
+    (DownloadState::Recording, DownloadState::Cancelling),
+    (DownloadState::Recording, DownloadState::Failed),
     // From Merging
     (DownloadState::Merging, DownloadState::Completed),
     (DownloadState::Merging, DownloadState::Cancelling),
@@ -46,6 +62,7 @@ const VALID_TRANSITIONS: &[(DownloadState, DownloadState)] = &[
     (DownloadState::Cancelling, DownloadState::Cancelled),
     // Reset transitions
     (DownloadState::Completed, DownloadState::Idle),
+    (DownloadState::Skipped, DownloadState::Idle),
     (DownloadState::Cancelled, DownloadState::Idle),
     (DownloadState::Failed, DownloadState::Idle),
 ];
@@ -102,6 +119,8 @@ proptest! {
             DownloadState::Analyzing | 
             DownloadState::Starting | 
             DownloadState::Downloading | 
+            DownloadState::WaitingForLive | 
+            DownloadState::Recording | 
             DownloadState::Merging | 
             DownloadState::Cancelling
         );
@@ -0,0 +1,100 @@
+//! Post-download transcoding presets
+//!
+//! A preset re-encodes the finished download with the bundled ffmpeg, e.g.
+//! to make it playable on a phone. The original file is kept.
+
+use serde::{Deserialize, Serialize};
+
+/// Named ffmpeg re-encoding preset
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TranscodePreset {
+    /// H.264/AAC MP4, at most 720p, plays on virtually every phone
+    Phone720p,
+    /// Intra-frame ProRes with PCM audio, for smooth scrubbing in editors
+    EditingIntra,
+    /// 64 kbit/s mono AAC, audio only
+    VoiceMemo,
+}
+
+impl TranscodePreset {
+    /// Every preset, in UI order
+    pub const ALL: [TranscodePreset; 3] = [
+        TranscodePreset::Phone720p,
+        TranscodePreset::EditingIntra,
+        TranscodePreset::VoiceMemo,
+    ];
+
+    /// Short name used as a suffix for the output file
+    pub fn name(&self) -> &'static str {
+        match self {
+            TranscodePreset::Phone720p => "phone720p",
+            TranscodePreset::EditingIntra => "editing",
+            TranscodePreset::VoiceMemo => "voice",
+        }
+    }
+
+    /// Extension of the transcoded file
+    pub fn extension(&self) -> &'static str {
+        match self {
+            TranscodePreset::Phone720p => "mp4",
+            TranscodePreset::EditingIntra => "mov",
+            TranscodePreset::VoiceMemo => "m4a",
+        }
+    }
+
+    /// ffmpeg encoding options (between the input and the output file)
+    pub fn ffmpeg_args(&self) -> &'static [&'static str] {
+        match self {
+            TranscodePreset::Phone720p => &[
+                "-vf", "scale=-2:'min(720,ih)'",
+                "-c:v", "libx264",
+                "-preset", "medium",
+                "-crf", "23",
+                "-pix_fmt", "yuv420p",
+                "-c:a", "aac",
+                "-b:a", "128k",
+                "-movflags", "+faststart",
+            ],
+            TranscodePreset::EditingIntra => &[
+                "-c:v", "prores_ks",
+                "-profile:v", "1",
+                "-pix_fmt", "yuv422p10le",
+                "-c:a", "pcm_s16le",
+            ],
+            TranscodePreset::VoiceMemo => &[
+                "-vn",
+                "-ac", "1",
+                "-c:a", "aac",
+                "-b:a", "64k",
+            ],
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transcode_preset_serde() {
+        let preset: TranscodePreset = serde_json::from_str(r#""editingIntra""#).unwrap();
+        assert_eq!(preset, TranscodePreset::EditingIntra);
+        assert_eq!(
+            serde_json::to_string(&TranscodePreset::Phone720p).unwrap(),
+            r#""phone720p""#
+        );
+        assert!(serde_json::from_str::<TranscodePreset>(r#""tv4k""#).is_err());
+    }
+
+    #[test]
+    fn test_preset_args_come_in_pairs() {
+        for preset in TranscodePreset::ALL {
+            let args = preset.ffmpeg_args();
+            // Only "-vn" stands alone
+            let flags = args.iter().filter(|a| a.starts_with('-')).count();
+            let standalone = args.iter().filter(|a| **a == "-vn").count();
+            assert_eq!(args.len(), (flags - standalone) * 2 + standalone, "{:?}", preset);
+        }
+    }
+}
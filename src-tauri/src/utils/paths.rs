@@ -19,6 +19,12 @@ const APP_IDENTIFIER: &str = "com.mediagrab";
 /// Subdirectory for executables
 const BIN_DIR: &str = "bin";
 
+/// yt-dlp download archive file name
+const DOWNLOAD_ARCHIVE_FILE: &str = "download-archive.txt";
+
+/// Local HTTP API token file name
+const API_TOKEN_FILE: &str = "api-token";
+
 /// Executable names (without extension)
 pub const YTDLP_NAME: &str = "yt-dlp";
 pub const FFMPEG_NAME: &str = "ffmpeg";
@@ -55,6 +61,16 @@ pub fn get_user_bin_dir() -> Result<PathBuf, DownloadError> {
     get_app_data_dir().map(|p| p.join(BIN_DIR))
 }
 
+/// Gets the yt-dlp download archive path (%APPDATA%\com.mediagrab\download-archive.txt)
+pub fn get_download_archive_path() -> Result<PathBuf, DownloadError> {
+    get_app_data_dir().map(|p| p.join(DOWNLOAD_ARCHIVE_FILE))
+}
+
+/// Gets the local HTTP API token path (%APPDATA%\com.mediagrab\api-token)
+pub fn get_api_token_path() -> Result<PathBuf, DownloadError> {
+    get_app_data_dir().map(|p| p.join(API_TOKEN_FILE))
+}
+
 /// Gets the executable extension for the current platform
 fn get_exe_extension() -> &'static str {
     if cfg!(windows) {
@@ -211,6 +227,18 @@ fn ensure_executable(
     Err(DownloadError::ExecutableNotFound(name.to_string()))
 }
 
+/// Finds an executable without an app handle: the user bin directory (where
+/// the app keeps its copies) or the system PATH
+///
+/// Used by the command-line binary, which runs without Tauri.
+pub fn find_executable(name: &str) -> Result<PathBuf, DownloadError> {
+    let user_path = get_user_executable_path(name)?;
+    if user_path.exists() {
+        return Ok(user_path);
+    }
+    which::which(name).map_err(|_| DownloadError::ExecutableNotFound(name.to_string()))
+}
+
 /// Resolves all executable paths, copying bundled executables to user directory if needed
 ///
 /// This function should be called on application startup to ensure all
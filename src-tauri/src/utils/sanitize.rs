@@ -1,3 +1,342 @@
 //! Filename sanitization utilities
+//!
+//! Turns titles and rendered templates into names that are valid on Windows,
+//! macOS and Linux:
+//! - control characters are removed and characters reserved on Windows are replaced
+//! - Unicode is normalized to NFC (optionally transliterated to ASCII)
+//! - runs of repeated separators are collapsed
+//! - reserved device names (CON, NUL, COM1, ...) are escaped
+//! - each path component is limited to 255 bytes (Linux limit), keeping the extension
 
-// TODO: Implement filename sanitization in task 3
+use unicode_normalization::char::is_combining_mark;
+use unicode_normalization::UnicodeNormalization;
+
+/// Maximum length of a single path component in bytes (ext4, btrfs, ...)
+pub const MAX_COMPONENT_BYTES: usize = 255;
+
+/// Name used when sanitization leaves nothing behind
+pub const FALLBACK_NAME: &str = "untitled";
+
+/// Characters not allowed in Windows filenames (also covers path separators)
+const RESERVED_CHARS: &[char] = &['<', '>', ':', '"', '/', '\\', '|', '?', '*'];
+
+/// Characters collapsed when repeated ("a__b" -> "a_b", "a  b" -> "a b")
+const SEPARATORS: &[char] = &[' ', '_', '-', '.'];
+
+/// Device names reserved on Windows, with or without an extension
+const RESERVED_NAMES: &[&str] = &[
+    "CON", "PRN", "AUX", "NUL", "COM1", "COM2", "COM3", "COM4", "COM5", "COM6", "COM7", "COM8",
+    "COM9", "LPT1", "LPT2", "LPT3", "LPT4", "LPT5", "LPT6", "LPT7", "LPT8", "LPT9",
+];
+
+/// Longest suffix treated as a file extension when truncating
+const MAX_EXTENSION_BYTES: usize = 10;
+
+/// Sanitization settings
+#[derive(Debug, Clone)]
+pub struct SanitizeOptions {
+    /// Transliterate to ASCII ("Café" -> "Cafe"); unknown characters are replaced
+    pub ascii_only: bool,
+    /// Maximum bytes per path component
+    pub max_component_bytes: usize,
+    /// Replacement for reserved characters
+    pub replacement: char,
+}
+
+impl Default for SanitizeOptions {
+    fn default() -> Self {
+        Self {
+            ascii_only: false,
+            max_component_bytes: MAX_COMPONENT_BYTES,
+            replacement: '_',
+        }
+    }
+}
+
+/// Sanitizes a single filename with default options
+pub fn sanitize_filename(name: &str) -> String {
+    sanitize_filename_with(name, &SanitizeOptions::default())
+}
+
+/// Sanitizes a single filename (path separators are replaced, not kept)
+pub fn sanitize_filename_with(name: &str, options: &SanitizeOptions) -> String {
+    let mut result = String::with_capacity(name.len());
+    for c in sanitize_fragment(name, options).chars() {
+        // Collapse repeated separators
+        if SEPARATORS.contains(&c) && result.ends_with(c) {
+            continue;
+        }
+        result.push(c);
+    }
+
+    let mut result = trim_name(&result).to_string();
+
+    if is_reserved_name(&result) {
+        result = escape_reserved_name(&result, options.replacement);
+    }
+
+    let result = truncate_preserving_extension(&result, options.max_component_bytes);
+    let result = trim_name(&result);
+
+    if result.is_empty() {
+        FALLBACK_NAME.to_string()
+    } else {
+        result.to_string()
+    }
+}
+
+/// Sanitizes characters only: NFC (or ASCII), control characters removed and
+/// reserved characters replaced
+///
+/// No trimming, collapsing or length limits, so it is safe for parts of a
+/// filename such as literal text in templates.
+pub fn sanitize_fragment(text: &str, options: &SanitizeOptions) -> String {
+    let normalized: String = text.nfc().collect();
+    let text = if options.ascii_only {
+        transliterate(&normalized, options.replacement)
+    } else {
+        normalized
+    };
+
+    text.chars()
+        .filter_map(|c| {
+            if c == '\t' || c == '\n' || c == '\r' {
+                Some(' ')
+            } else if c.is_control() {
+                None
+            } else if RESERVED_CHARS.contains(&c) {
+                Some(options.replacement)
+            } else {
+                Some(c)
+            }
+        })
+        .collect()
+}
+
+/// Truncates a string to at most `max_bytes` bytes on a char boundary
+pub fn truncate_to_bytes(s: &str, max_bytes: usize) -> &str {
+    if s.len() <= max_bytes {
+        return s;
+    }
+
+    let mut end = max_bytes;
+    while !s.is_char_boundary(end) {
+        end -= 1;
+    }
+    &s[..end]
+}
+
+/// Truncates a filename to `max_bytes`, shortening the stem and keeping a short extension
+fn truncate_preserving_extension(name: &str, max_bytes: usize) -> String {
+    if name.len() <= max_bytes {
+        return name.to_string();
+    }
+
+    if let Some((stem, ext)) = name.rsplit_once('.') {
+        let ext_bytes = ext.len() + 1;
+        let is_extension = !stem.is_empty()
+            && !ext.is_empty()
+            && ext.len() <= MAX_EXTENSION_BYTES
+            && ext.chars().all(|c| c.is_ascii_alphanumeric());
+
+        if is_extension && ext_bytes < max_bytes {
+            let stem = trim_name(truncate_to_bytes(stem, max_bytes - ext_bytes));
+            return format!("{}.{}", stem, ext);
+        }
+    }
+
+    truncate_to_bytes(name, max_bytes).to_string()
+}
+
+/// Trims whitespace and dots (trailing dots/spaces are invalid on Windows,
+/// leading dots create hidden files)
+fn trim_name(name: &str) -> &str {
+    name.trim_matches(|c: char| c.is_whitespace() || c == '.')
+}
+
+/// Whether the name (ignoring extension and case) is a reserved Windows device name
+fn is_reserved_name(name: &str) -> bool {
+    let stem = name.split('.').next().unwrap_or(name).trim_end();
+    RESERVED_NAMES.iter().any(|reserved| reserved.eq_ignore_ascii_case(stem))
+}
+
+/// Escapes a reserved name by appending the replacement to its stem ("CON.txt" -> "CON_.txt")
+fn escape_reserved_name(name: &str, replacement: char) -> String {
+    match name.split_once('.') {
+        Some((stem, rest)) => format!("{}{}.{}", stem, replacement, rest),
+        None => format!("{}{}", name, replacement),
+    }
+}
+
+/// Transliterates text to ASCII: strips diacritics and maps common letters
+/// without a decomposition; anything else becomes `replacement`
+fn transliterate(text: &str, replacement: char) -> String {
+    let mut result = String::with_capacity(text.len());
+
+    for c in text.nfkd().filter(|c| !is_combining_mark(*c)) {
+        if c.is_ascii() {
+            result.push(c);
+            continue;
+        }
+
+        let mapped = match c {
+            'ß' => "ss",
+            'æ' => "ae",
+            'Æ' => "AE",
+            'œ' => "oe",
+            'Œ' => "OE",
+            'ø' => "o",
+            'Ø' => "O",
+            'ł' => "l",
+            'Ł' => "L",
+            'đ' | 'ð' => "d",
+            'Đ' | 'Ð' => "D",
+            'þ' => "th",
+            'Þ' => "TH",
+            'ı' => "i",
+            '‘' | '’' | '‚' | '′' => "'",
+            '“' | '”' | '„' | '″' => "\"",
+            '–' | '—' | '−' => "-",
+            '…' => "...",
+            _ => "",
+        };
+
+        if mapped.is_empty() {
+            result.push(replacement);
+        } else {
+            result.push_str(mapped);
+        }
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    //! Property tests for filename sanitization
+
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn test_reserved_characters_replaced() {
+        assert_eq!(sanitize_filename("a<b>c:d\"e|f?g*h"), "a_b_c_d_e_f_g_h");
+        assert_eq!(sanitize_filename("AC/DC - Back\\In Black"), "AC_DC - Back_In Black");
+    }
+
+    #[test]
+    fn test_control_characters() {
+        assert_eq!(sanitize_filename("line\none\ttab"), "line one tab");
+        assert_eq!(sanitize_filename("bell\u{7}null\u{0}"), "bellnull");
+    }
+
+    #[test]
+    fn test_reserved_names() {
+        assert_eq!(sanitize_filename("CON"), "CON_");
+        assert_eq!(sanitize_filename("nul.txt"), "nul_.txt");
+        assert_eq!(sanitize_filename("COM1.tar.gz"), "COM1_.tar.gz");
+        assert_eq!(sanitize_filename("CONSOLE"), "CONSOLE");
+    }
+
+    #[test]
+    fn test_collapse_separators_and_trim() {
+        assert_eq!(sanitize_filename("a   b___c---d"), "a b_c-d");
+        assert_eq!(sanitize_filename("  ..hidden..  "), "hidden");
+        assert_eq!(sanitize_filename("trailing dot."), "trailing dot");
+    }
+
+    #[test]
+    fn test_empty_falls_back() {
+        assert_eq!(sanitize_filename(""), FALLBACK_NAME);
+        assert_eq!(sanitize_filename(" . . "), FALLBACK_NAME);
+        assert_eq!(sanitize_filename("\u{1}\u{2}"), FALLBACK_NAME);
+    }
+
+    #[test]
+    fn test_nfc_normalization() {
+        // "e" + combining acute accent -> precomposed "é"
+        assert_eq!(sanitize_filename("Cafe\u{301}"), "Caf\u{e9}");
+    }
+
+    #[test]
+    fn test_ascii_transliteration() {
+        let options = SanitizeOptions { ascii_only: true, ..SanitizeOptions::default() };
+        assert_eq!(sanitize_filename_with("Çalışma Ölçüsü", &options), "Calisma Olcusu");
+        assert_eq!(sanitize_filename_with("Straße – Œuvre", &options), "Strasse - OEuvre");
+        assert_eq!(sanitize_filename_with("日本", &options), "_");
+    }
+
+    #[test]
+    fn test_truncation_keeps_extension() {
+        let name = format!("{}.mp4", "é".repeat(200));
+        let result = sanitize_filename(&name);
+        assert!(result.len() <= MAX_COMPONENT_BYTES);
+        assert!(result.ends_with(".mp4"));
+    }
+
+    #[test]
+    fn test_sanitize_fragment_keeps_spacing() {
+        let options = SanitizeOptions::default();
+        assert_eq!(sanitize_fragment(" - a:b  ", &options), " - a_b  ");
+        assert_eq!(sanitize_fragment("..", &options), "..");
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig::with_cases(100))]
+
+        /// Property: output never contains control or reserved characters
+        #[test]
+        fn prop_no_invalid_characters(name in any::<String>()) {
+            let result = sanitize_filename(&name);
+            prop_assert!(!result.chars().any(|c| c.is_control() || RESERVED_CHARS.contains(&c)));
+        }
+
+        /// Property: output is never empty and fits in a path component
+        #[test]
+        fn prop_length_limit(name in any::<String>(), max in 16usize..300) {
+            let options = SanitizeOptions { max_component_bytes: max, ..SanitizeOptions::default() };
+            let result = sanitize_filename_with(&name, &options);
+            prop_assert!(!result.is_empty());
+            prop_assert!(result.len() <= max.max(FALLBACK_NAME.len()));
+        }
+
+        /// Property: no leading/trailing dots or spaces, no repeated separators
+        #[test]
+        fn prop_trimmed_and_collapsed(name in "[ ._a-zA-Z0-9-]{0,64}") {
+            let result = sanitize_filename(&name);
+            prop_assert!(!result.starts_with([' ', '.']));
+            prop_assert!(!result.ends_with([' ', '.']));
+            for sep in SEPARATORS {
+                let doubled = format!("{}{}", sep, sep);
+                prop_assert!(!result.contains(&doubled));
+            }
+        }
+
+        /// Property: sanitizing is idempotent
+        #[test]
+        fn prop_idempotent(name in any::<String>()) {
+            let once = sanitize_filename(&name);
+            prop_assert_eq!(sanitize_filename(&once), once.clone());
+        }
+
+        /// Property: ASCII mode only produces ASCII
+        #[test]
+        fn prop_ascii_only(name in any::<String>()) {
+            let options = SanitizeOptions { ascii_only: true, ..SanitizeOptions::default() };
+            prop_assert!(sanitize_filename_with(&name, &options).is_ascii());
+        }
+
+        /// Property: reserved device names never survive
+        #[test]
+        fn prop_reserved_names_escaped(
+            index in 0usize..RESERVED_NAMES.len(),
+            ext in prop::option::of("[a-z]{1,4}"),
+        ) {
+            let name = match ext {
+                Some(ext) => format!("{}.{}", RESERVED_NAMES[index].to_lowercase(), ext),
+                None => RESERVED_NAMES[index].to_string(),
+            };
+            prop_assert!(!is_reserved_name(&sanitize_filename(&name)));
+        }
+    }
+}
@@ -36,13 +36,14 @@ import { useDragDrop } from "@/hooks/useDragDrop";
 
 // Utilities
 import { fadeInVariants, containerVariants, defaultTransition } from "@/lib/animations";
+import { listen } from "@/lib/tauri";
 
 // Types
-import type { DownloadConfig, PlaylistEntry } from "@/types";
+import type { BatchEntry, ClipboardLink, DeepLinkResult, DownloadConfig, PlaylistSelection } from "@/types";
 
 function App() {
   const { t } = useTranslation();
-  const { success, info } = useToast();
+  const { success, info, warning, error: showError, addToast } = useToast();
 
   // Form state management
   const {
@@ -61,7 +62,7 @@ function App() {
   const { preferences } = usePreferences();
 
   // Queue & History
-  const { addToQueue, activeCount, pendingCount } = useQueue();
+  const { addToQueue, addPlaylistToQueue, addBatchToQueue, activeCount, pendingCount } = useQueue();
   const { addToHistory } = useHistory();
 
   // Media info & playlist
@@ -71,6 +72,7 @@ function App() {
     isPlaylist,
     playlistInfo,
     isLoadingPlaylist,
+    playlistProgress,
     fetchMediaInfo,
     handleUrlChange: onMediaUrlChange,
     handlePaste: onPaste,
@@ -133,6 +135,45 @@ function App() {
     onMediaUrlChange(newUrl, setUrl);
   }, [onMediaUrlChange, setUrl]);
 
+  // Links picked up by the clipboard watcher
+  useEffect(() => {
+    const unlisten = listen<ClipboardLink>("clipboard-link", (event) => {
+      const link = event.payload;
+      if (link.item) {
+        info(t("toast.clipboardQueued"), link.item.title ?? link.url);
+      } else if (link.duplicate) {
+        warning(t(link.duplicate.source === "queue" ? "duplicate.inQueue" : "duplicate.inHistory"), link.duplicate.title ?? link.url);
+      } else {
+        addToast({
+          type: "info",
+          title: t("toast.clipboardLink"),
+          description: link.url,
+          action: { label: t("buttons.download"), onClick: () => handleUrlChange(link.url) },
+        });
+      }
+    });
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, [info, warning, addToast, handleUrlChange, t]);
+
+  // mediagrab:// links, queued by the backend
+  useEffect(() => {
+    const unlisten = listen<DeepLinkResult>("deep-link", (event) => {
+      const result = event.payload;
+      if (result.item) {
+        info(t("toast.deepLinkQueued"), result.item.title ?? result.item.config.url);
+      } else if (result.duplicate) {
+        warning(t(result.duplicate.source === "queue" ? "duplicate.inQueue" : "duplicate.inHistory"), result.duplicate.title ?? result.url ?? result.link);
+      } else if (result.error) {
+        showError(t("toast.deepLinkInvalid"), result.error);
+      }
+    });
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, [info, warning, showError, t]);
+
   // Paste handler
   const handlePaste = useCallback(() => {
     onPaste(setUrl);
@@ -146,6 +187,7 @@ function App() {
 
   // Handle multiple URLs pasted at once
   const handleMultipleUrls = useCallback(async (urls: string[]) => {
+    let skipped = 0;
     for (const u of urls) {
       const config: DownloadConfig = {
         url: u,
@@ -157,26 +199,31 @@ function App() {
         filenameTemplate: preferences?.filenameTemplate ?? null,
         proxyUrl: preferences?.proxyEnabled ? preferences?.proxyUrl : null,
         cookiesFilePath: preferences?.cookiesFilePath ?? null,
+        organizeRules: preferences?.organizeRules ?? [],
+        transcodePreset: preferences?.transcodePreset ?? null,
+        loudnessTargetLufs: preferences?.normalizeLoudness ? preferences.loudnessTargetLufs : null,
       };
-      await addToQueue(config);
+      const result = await addToQueue(config);
+      if (result.type === "duplicate") skipped++;
     }
-    info(t("toast.urlsAdded", { count: urls.length }));
+    info(t("toast.urlsAdded", { count: urls.length - skipped }));
+    if (skipped > 0) warning(t("duplicate.skipped", { count: skipped }));
     openQueue();
-  }, [format, quality, outputFolder, preferences, addToQueue, info, t, openQueue]);
+  }, [format, quality, outputFolder, preferences, addToQueue, info, warning, t, openQueue]);
 
   // Playlist download handler
-  const handlePlaylistDownload = useCallback(async (entries: PlaylistEntry[], config: Omit<DownloadConfig, "url">) => {
-    for (const entry of entries) {
-      try {
-        await addToQueue({ ...config, url: entry.url });
-      } catch (err) {
-        console.error(`Failed to add ${entry.title} to queue:`, err);
-      }
+  const handlePlaylistDownload = useCallback(async (selection: PlaylistSelection, config: Omit<DownloadConfig, "url">) => {
+    if (!playlistInfo) return;
+    try {
+      const result = await addPlaylistToQueue(playlistInfo, selection, { ...config, url: playlistInfo.url });
+      if (result.duplicates.length > 0) warning(t("duplicate.skipped", { count: result.duplicates.length }));
+    } catch (err) {
+      console.error(`Failed to add playlist ${playlistInfo.title} to queue:`, err);
     }
     setUrl("");
     clearMediaInfo();
     openQueue();
-  }, [addToQueue, clearMediaInfo, openQueue, setUrl]);
+  }, [playlistInfo, addPlaylistToQueue, clearMediaInfo, openQueue, setUrl, warning, t]);
 
   // Redownload handler
   const handleRedownload = useCallback((config: DownloadConfig) => {
@@ -187,24 +234,30 @@ function App() {
   }, [handleFormatChange, handleQualityChange, setOutputFolder, setUrl]);
 
   // Batch import handler
-  const handleBatchImport = useCallback(async (urls: string[]) => {
-    for (const u of urls) {
-      const config: DownloadConfig = {
-        url: u,
-        format,
-        quality,
-        outputFolder: outputFolder || "",
-        embedSubtitles: preferences?.embedSubtitles ?? false,
-        cookiesFromBrowser: preferences?.cookiesFromBrowser ?? null,
-        filenameTemplate: preferences?.filenameTemplate ?? null,
-        proxyUrl: preferences?.proxyEnabled ? preferences?.proxyUrl : null,
-        cookiesFilePath: preferences?.cookiesFilePath ?? null,
-      };
-      await addToQueue(config);
+  const handleBatchImport = useCallback(async (entries: BatchEntry[]) => {
+    const config: DownloadConfig = {
+      url: "",
+      format,
+      quality,
+      outputFolder: outputFolder || "",
+      embedSubtitles: preferences?.embedSubtitles ?? false,
+      cookiesFromBrowser: preferences?.cookiesFromBrowser ?? null,
+      filenameTemplate: preferences?.filenameTemplate ?? null,
+      proxyUrl: preferences?.proxyEnabled ? preferences?.proxyUrl : null,
+      cookiesFilePath: preferences?.cookiesFilePath ?? null,
+      organizeRules: preferences?.organizeRules ?? [],
+      transcodePreset: preferences?.transcodePreset ?? null,
+      loudnessTargetLufs: preferences?.normalizeLoudness ? preferences.loudnessTargetLufs : null,
+    };
+    try {
+      const result = await addBatchToQueue(entries, config);
+      info(t("toast.urlsAdded", { count: result.items.length }));
+      if (result.duplicates.length > 0) warning(t("duplicate.skipped", { count: result.duplicates.length }));
+    } catch (err) {
+      console.error("Failed to import batch:", err);
     }
-    info(t("toast.urlsAdded", { count: urls.length }));
     openQueue();
-  }, [format, quality, outputFolder, preferences, addToQueue, info, t, openQueue]);
+  }, [format, quality, outputFolder, preferences, addBatchToQueue, info, warning, t, openQueue]);
 
   // Keyboard shortcuts
   useKeyboardShortcuts({
@@ -289,6 +342,7 @@ function App() {
             isPlaylist={isPlaylist}
             playlistInfo={playlistInfo}
             isLoadingPlaylist={isLoadingPlaylist}
+            playlistProgress={playlistProgress}
             downloadState={downloadState}
             progress={progress}
             error={error}
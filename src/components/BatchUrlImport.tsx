@@ -1,68 +1,106 @@
-import { useState, useRef, useCallback } from 'react';
+import { useState, useRef, useCallback, useEffect } from 'react';
 import { motion, AnimatePresence } from 'framer-motion';
 import { FileText, Upload, X, Check, AlertCircle } from 'lucide-react';
 import { useTranslation } from 'react-i18next';
 import { Button } from './ui/button';
-import { validateUrls } from '@/lib/validation';
+import { invoke } from '@/lib/tauri';
 import { cn } from '@/lib/utils';
+import type { BatchEntry, BatchParse } from '@/types';
 
 interface BatchUrlImportProps {
   isOpen: boolean;
   onClose: () => void;
-  onImport: (urls: string[]) => void;
+  onImport: (entries: BatchEntry[]) => void;
 }
 
+// Delay before re-parsing typed text
+const PARSE_DELAY_MS = 300;
+
 export function BatchUrlImport({ isOpen, onClose, onImport }: BatchUrlImportProps) {
   const { t } = useTranslation();
   const [urls, setUrls] = useState('');
-  const [validationResult, setValidationResult] = useState<{ valid: string[]; invalid: { url: string; error: string }[] } | null>(null);
+  const [batch, setBatch] = useState<BatchParse | null>(null);
+  const [fileName, setFileName] = useState<string | null>(null);
+  const [fileError, setFileError] = useState<string | null>(null);
   const [isDragOver, setIsDragOver] = useState(false);
-  const fileInputRef = useRef<HTMLInputElement>(null);
+  const parseTimeoutRef = useRef<ReturnType<typeof setTimeout> | null>(null);
+
+  useEffect(() => () => {
+    if (parseTimeoutRef.current) clearTimeout(parseTimeoutRef.current);
+  }, []);
+
+  const parse = useCallback(async (args: { text?: string; path?: string; csv?: boolean }) => {
+    try {
+      const result = await invoke<BatchParse>('batch_parse', args);
+      setBatch(result.entries.length > 0 || result.issues.length > 0 ? result : null);
+      setFileError(null);
+    } catch (err) {
+      setBatch(null);
+      setFileError(String(err));
+    }
+  }, []);
 
   const handleTextChange = useCallback((text: string) => {
     setUrls(text);
-    // Validate as user types
-    const lines = text.split('\n').filter(line => line.trim().length > 0);
-    if (lines.length > 0) {
-      setValidationResult(validateUrls(lines));
-    } else {
-      setValidationResult(null);
+    setFileName(null);
+    if (parseTimeoutRef.current) clearTimeout(parseTimeoutRef.current);
+    if (!text.trim()) {
+      setBatch(null);
+      return;
     }
-  }, []);
+    parseTimeoutRef.current = setTimeout(() => parse({ text }), PARSE_DELAY_MS);
+  }, [parse]);
 
-  const handleFileSelect = useCallback(async (file: File) => {
+  const handleBrowse = useCallback(async () => {
     try {
-      const text = await file.text();
-      handleTextChange(text);
+      const path = await invoke<string | null>('pick_batch_file');
+      if (!path) return;
+      setUrls('');
+      setFileName(path.split(/[\\/]/).pop() ?? path);
+      await parse({ path });
     } catch (err) {
-      console.error('Failed to read file:', err);
+      console.error('Failed to pick file:', err);
     }
-  }, [handleTextChange]);
+  }, [parse]);
 
-  const handleDrop = useCallback((e: React.DragEvent) => {
+  const handleDrop = useCallback(async (e: React.DragEvent) => {
     e.preventDefault();
     setIsDragOver(false);
-    
+
     const file = e.dataTransfer.files[0];
-    if (file && (file.type === 'text/plain' || file.name.endsWith('.txt'))) {
-      handleFileSelect(file);
+    if (!file) return;
+    const csv = file.name.toLowerCase().endsWith('.csv');
+    if (!csv && !file.name.toLowerCase().endsWith('.txt') && file.type !== 'text/plain') return;
+    try {
+      const text = await file.text();
+      setUrls('');
+      setFileName(file.name);
+      await parse({ text, csv });
+    } catch (err) {
+      console.error('Failed to read file:', err);
     }
-  }, [handleFileSelect]);
+  }, [parse]);
+
+  const reset = useCallback(() => {
+    if (parseTimeoutRef.current) clearTimeout(parseTimeoutRef.current);
+    setUrls('');
+    setBatch(null);
+    setFileName(null);
+    setFileError(null);
+  }, []);
 
   const handleImport = useCallback(() => {
-    if (validationResult && validationResult.valid.length > 0) {
-      onImport(validationResult.valid);
-      setUrls('');
-      setValidationResult(null);
+    if (batch && batch.entries.length > 0) {
+      onImport(batch.entries);
+      reset();
       onClose();
     }
-  }, [validationResult, onImport, onClose]);
+  }, [batch, onImport, reset, onClose]);
 
   const handleClose = useCallback(() => {
-    setUrls('');
-    setValidationResult(null);
+    reset();
     onClose();
-  }, [onClose]);
+  }, [reset, onClose]);
 
   return (
     <AnimatePresence>
@@ -100,48 +138,77 @@ export function BatchUrlImport({ isOpen, onClose, onImport }: BatchUrlImportProp
               onDragOver={(e) => { e.preventDefault(); setIsDragOver(true); }}
               onDragLeave={() => setIsDragOver(false)}
               onDrop={handleDrop}
-              onClick={() => fileInputRef.current?.click()}
+              onClick={handleBrowse}
             >
               <div className="flex flex-col items-center gap-2 text-muted-foreground">
                 <Upload className="h-8 w-8" />
-                <p className="text-sm">{t('batch.dropFile', 'Drop a .txt file here or click to browse')}</p>
+                <p className="text-sm">
+                  {fileName ?? t('batch.dropFile', 'Drop a .txt or .csv file here or click to browse')}
+                </p>
               </div>
-              <input
-                ref={fileInputRef}
-                type="file"
-                accept=".txt,text/plain"
-                className="hidden"
-                onChange={(e) => e.target.files?.[0] && handleFileSelect(e.target.files[0])}
-              />
             </div>
 
             {/* URL textarea */}
             <div className="mb-4">
               <label className="block text-sm font-medium mb-2">
-                {t('batch.pasteUrls', 'Or paste URLs (one per line)')}
+                {t('batch.pasteUrls', 'Or paste text with links')}
               </label>
               <textarea
                 value={urls}
                 onChange={(e) => handleTextChange(e.target.value)}
-                placeholder="https://youtube.com/watch?v=...&#10;https://vimeo.com/...&#10;https://twitter.com/..."
-                className="w-full h-40 rounded-lg border border-input bg-background px-3 py-2 text-sm resize-none focus:outline-none focus:ring-2 focus:ring-ring"
+                placeholder="https://youtube.com/watch?v=...&#10;https://vimeo.com/... | format=mp3&#10;https://twitter.com/... | folder=D:\Videos"
+                className="w-full h-32 rounded-lg border border-input bg-background px-3 py-2 text-sm resize-none focus:outline-none focus:ring-2 focus:ring-ring"
               />
+              <p className="mt-1 text-xs text-muted-foreground">
+                {t('batch.overridesHint', 'Add | format=, | quality= or | folder= after a link to change its settings')}
+              </p>
             </div>
 
-            {/* Validation results */}
-            {validationResult && (
+            {fileError && (
+              <div className="mb-4 flex items-center gap-2 text-sm text-destructive">
+                <AlertCircle className="h-4 w-4" />
+                {fileError}
+              </div>
+            )}
+
+            {/* Preview */}
+            {batch && (
               <div className="mb-4 space-y-2">
-                {validationResult.valid.length > 0 && (
-                  <div className="flex items-center gap-2 text-sm text-green-600 dark:text-green-400">
-                    <Check className="h-4 w-4" />
-                    {t('batch.validUrls', '{{count}} valid URLs', { count: validationResult.valid.length })}
-                  </div>
+                {batch.entries.length > 0 && (
+                  <>
+                    <div className="flex items-center gap-2 text-sm text-green-600 dark:text-green-400">
+                      <Check className="h-4 w-4" />
+                      {t('batch.validUrls', '{{count}} links found', { count: batch.entries.length })}
+                    </div>
+                    <ul className="max-h-40 overflow-y-auto rounded-lg border border-border text-xs divide-y divide-border">
+                      {batch.entries.map((entry) => {
+                        const { format, quality, outputFolder } = entry.overrides;
+                        const details = [format, quality, outputFolder].filter(Boolean).join(' · ');
+                        return (
+                          <li key={`${entry.line}-${entry.url}`} className="flex gap-2 px-2 py-1">
+                            <span className="text-muted-foreground tabular-nums">{entry.line}</span>
+                            <span className="truncate flex-1" title={entry.url}>{entry.url}</span>
+                            {details && <span className="shrink-0 text-muted-foreground">{details}</span>}
+                          </li>
+                        );
+                      })}
+                    </ul>
+                  </>
                 )}
-                {validationResult.invalid.length > 0 && (
-                  <div className="flex items-center gap-2 text-sm text-destructive">
-                    <AlertCircle className="h-4 w-4" />
-                    {t('batch.invalidUrls', '{{count}} invalid URLs', { count: validationResult.invalid.length })}
-                  </div>
+                {batch.issues.length > 0 && (
+                  <>
+                    <div className="flex items-center gap-2 text-sm text-destructive">
+                      <AlertCircle className="h-4 w-4" />
+                      {t('batch.invalidUrls', '{{count}} lines skipped', { count: batch.issues.length })}
+                    </div>
+                    <ul className="max-h-24 overflow-y-auto text-xs text-muted-foreground space-y-0.5">
+                      {batch.issues.map((issue) => (
+                        <li key={`${issue.line}-${issue.text}`} className="truncate" title={issue.text}>
+                          {t('batch.issueLine', 'Line {{line}}: {{error}}', { line: issue.line, error: issue.error })}
+                        </li>
+                      ))}
+                    </ul>
+                  </>
                 )}
               </div>
             )}
@@ -153,9 +220,9 @@ export function BatchUrlImport({ isOpen, onClose, onImport }: BatchUrlImportProp
               </Button>
               <Button
                 onClick={handleImport}
-                disabled={!validationResult || validationResult.valid.length === 0}
+                disabled={!batch || batch.entries.length === 0}
               >
-                {t('batch.import', 'Import {{count}} URLs', { count: validationResult?.valid.length || 0 })}
+                {t('batch.import', 'Import {{count}} URLs', { count: batch?.entries.length || 0 })}
               </Button>
             </div>
           </motion.div>
@@ -11,7 +11,7 @@ interface CancelButtonProps {
 
 export function CancelButton({ onClick, state }: CancelButtonProps) {
   const isCancelling = state === "cancelling";
-  const isVisible = state === "downloading" || state === "merging" || state === "cancelling" || state === "starting";
+  const isVisible = state === "downloading" || state === "waitingForLive" || state === "recording" || state === "merging" || state === "cancelling" || state === "starting";
 
   return (
     <AnimatePresence>
@@ -16,7 +16,7 @@ interface DownloadButtonProps {
  */
 export function DownloadButton({ onClick, state, disabled }: DownloadButtonProps) {
   const isLoading = state === "starting" || state === "analyzing";
-  const isDownloading = state === "downloading" || state === "merging";
+  const isDownloading = state === "downloading" || state === "waitingForLive" || state === "recording" || state === "merging";
   const isDisabled = disabled || isLoading || isDownloading || state === "cancelling";
 
   return (
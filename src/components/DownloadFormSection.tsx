@@ -22,9 +22,11 @@ import { NetworkStatusBanner } from "@/components/NetworkStatusBanner";
 import { Button } from "@/components/ui/button";
 import { useUIStore } from "@/stores/uiStore";
 import { useQueue } from "@/hooks/useQueue";
+import { useDuplicatePrompt } from "@/hooks/useDuplicatePrompt";
 import { usePreferences } from "@/hooks/usePreferences";
 import { useToast } from "@/components/Toast";
 import { validateUrl } from "@/lib/validation";
+import { liveOptionsFor } from "@/lib/live";
 import { itemVariants } from "@/lib/animations";
 import type { NetworkStatus } from "@/hooks/useNetworkStatus";
 import type {
@@ -34,6 +36,7 @@ import type {
     DownloadState,
     ProgressEvent,
     PlaylistInfo,
+    PlaylistProgress,
     RetryEvent,
     DownloadConfig
 } from "@/types";
@@ -62,6 +65,7 @@ interface DownloadFormSectionProps {
     isPlaylist: boolean;
     playlistInfo: PlaylistInfo | null;
     isLoadingPlaylist: boolean;
+    playlistProgress: PlaylistProgress | null;
 
     // Download state
     downloadState: DownloadState;
@@ -100,6 +104,7 @@ export function DownloadFormSection({
     isPlaylist,
     playlistInfo,
     isLoadingPlaylist,
+    playlistProgress,
     downloadState,
     progress,
     error,
@@ -116,6 +121,7 @@ export function DownloadFormSection({
     const { t } = useTranslation();
     const { success } = useToast();
     const { addToQueue } = useQueue();
+    const promptDuplicate = useDuplicatePrompt();
     const { preferences } = usePreferences();
     const { openSettings, openPlaylist, openBatchImport, openSchedule } = useUIStore();
 
@@ -134,14 +140,22 @@ export function DownloadFormSection({
             filenameTemplate: preferences?.filenameTemplate ?? null,
             proxyUrl: preferences?.proxyEnabled ? preferences?.proxyUrl : null,
             cookiesFilePath: preferences?.cookiesFilePath ?? null,
+            organizeRules: preferences?.organizeRules ?? [],
+            transcodePreset: preferences?.transcodePreset ?? null,
+            loudnessTargetLufs: preferences?.normalizeLoudness ? preferences.loudnessTargetLufs : null,
+            live: liveOptionsFor(preferences, mediaInfo),
         };
 
-        await addToQueue(config);
+        const result = await addToQueue(config);
         saveRecentUrl(sanitizedUrl);
-        success(t("toast.addedToQueue"));
+        if (result.type === "duplicate") {
+            promptDuplicate(result.duplicate, config);
+        } else {
+            success(t("toast.addedToQueue"));
+        }
         onUrlChange("");
         clearMediaInfo();
-    }, [url, format, quality, outputFolder, preferences, addToQueue, clearMediaInfo, success, t, onUrlChange]);
+    }, [url, format, quality, outputFolder, preferences, mediaInfo, addToQueue, promptDuplicate, clearMediaInfo, success, t, onUrlChange]);
 
     const isOffline = !networkStatus.isOnline || !networkStatus.isConnected;
 
@@ -216,7 +230,11 @@ export function DownloadFormSection({
                                     </p>
                                 )}
                                 {isLoadingPlaylist && (
-                                    <p className="text-sm text-muted-foreground">{t("playlist.loading")}</p>
+                                    <p className="text-sm text-muted-foreground">
+                                        {playlistProgress
+                                            ? t("playlist.loadingCount", { loaded: playlistProgress.loaded, total: playlistProgress.total ?? "?" })
+                                            : t("playlist.loading")}
+                                    </p>
                                 )}
                             </div>
                         </div>
@@ -254,7 +272,7 @@ export function DownloadFormSection({
                 <label htmlFor="folder-picker" className="mb-2 block text-sm font-medium text-foreground">
                     {t("form.saveTo")}
                 </label>
-                <FolderPicker id="folder-picker" value={outputFolder} onPick={onPickFolder} disabled={isDownloading} />
+                <FolderPicker id="folder-picker" value={outputFolder || preferences?.outputFolder || ""} onPick={onPickFolder} disabled={isDownloading} />
             </motion.div>
 
             {/* Action buttons */}
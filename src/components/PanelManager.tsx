@@ -7,11 +7,12 @@ import { usePanels, useUIStore } from "@/hooks/usePanels";
 import { usePreferences } from "@/hooks/usePreferences";
 import { useExecutables } from "@/hooks/useExecutables";
 import type {
+    BatchEntry,
     Format,
     Quality,
     PlaylistInfo,
     DownloadConfig,
-    PlaylistEntry,
+    PlaylistSelection,
     ScheduledDownload
 } from "@/types";
 
@@ -38,7 +39,7 @@ interface PanelManagerProps {
     // Playlist
     playlistInfo: PlaylistInfo | null;
     isLoadingPlaylist: boolean;
-    onPlaylistDownload: (entries: PlaylistEntry[], config: Omit<DownloadConfig, "url">) => void;
+    onPlaylistDownload: (selection: PlaylistSelection, config: Omit<DownloadConfig, "url">) => void;
 
     // Form state for panels
     format: Format;
@@ -49,7 +50,7 @@ interface PanelManagerProps {
     onRedownload: (config: DownloadConfig) => void;
 
     // Batch import
-    onBatchImport: (urls: string[]) => void;
+    onBatchImport: (entries: BatchEntry[]) => void;
 
     // Schedule
     scheduledDownloads: ScheduledDownload[];
@@ -0,0 +1,102 @@
+import { useState } from 'react';
+import { useTranslation } from 'react-i18next';
+import { parseFilterWords } from '@/lib/playlistFilter';
+import type { PlaylistFilter } from '@/types';
+
+interface PlaylistFilterFieldsProps {
+  value: PlaylistFilter;
+  onChange: (filter: PlaylistFilter) => void;
+}
+
+const inputClass = 'w-full rounded border border-input bg-background px-2 py-1 text-sm';
+
+// yyyy-mm-dd for <input type="date">, from YYYYMMDD or yyyy-mm-dd
+const toDateInput = (date: string | null | undefined) =>
+  date ? date.replace(/^(\d{4})(\d{2})(\d{2})$/, '$1-$2-$3') : '';
+
+const toMinutes = (seconds: number | null | undefined) =>
+  seconds != null ? String(Math.round(seconds / 60)) : '';
+
+const toSeconds = (minutes: string) => {
+  const value = parseFloat(minutes);
+  return Number.isFinite(value) && value >= 0 ? Math.round(value * 60) : null;
+};
+
+/**
+ * Date, duration and title inputs for a playlist filter
+ */
+export function PlaylistFilterFields({ value, onChange }: PlaylistFilterFieldsProps) {
+  const { t } = useTranslation();
+  // Word lists are kept as typed so a trailing comma isn't swallowed
+  const [includes, setIncludes] = useState((value.titleIncludes ?? []).join(', '));
+  const [excludes, setExcludes] = useState((value.titleExcludes ?? []).join(', '));
+
+  return (
+    <div className="grid grid-cols-2 gap-2 text-sm text-muted-foreground">
+      <label className="space-y-1">
+        <span>{t('playlist.filterDateAfter', 'Uploaded after')}</span>
+        <input
+          type="date"
+          value={toDateInput(value.dateAfter)}
+          onChange={(e) => onChange({ ...value, dateAfter: e.target.value || null })}
+          className={inputClass}
+        />
+      </label>
+      <label className="space-y-1">
+        <span>{t('playlist.filterDateBefore', 'Uploaded before')}</span>
+        <input
+          type="date"
+          value={toDateInput(value.dateBefore)}
+          onChange={(e) => onChange({ ...value, dateBefore: e.target.value || null })}
+          className={inputClass}
+        />
+      </label>
+      <label className="space-y-1">
+        <span>{t('playlist.filterMinMinutes', 'Longer than (min)')}</span>
+        <input
+          type="number"
+          min="0"
+          value={toMinutes(value.minDuration)}
+          onChange={(e) => onChange({ ...value, minDuration: toSeconds(e.target.value) })}
+          className={inputClass}
+        />
+      </label>
+      <label className="space-y-1">
+        <span>{t('playlist.filterMaxMinutes', 'Shorter than (min)')}</span>
+        <input
+          type="number"
+          min="0"
+          value={toMinutes(value.maxDuration)}
+          onChange={(e) => onChange({ ...value, maxDuration: toSeconds(e.target.value) })}
+          className={inputClass}
+        />
+      </label>
+      <label className="space-y-1">
+        <span>{t('playlist.filterIncludes', 'Title contains')}</span>
+        <input
+          type="text"
+          value={includes}
+          onChange={(e) => {
+            setIncludes(e.target.value);
+            onChange({ ...value, titleIncludes: parseFilterWords(e.target.value) });
+          }}
+          placeholder={t('playlist.filterWordsPlaceholder', 'Comma-separated')}
+          className={inputClass}
+        />
+      </label>
+      <label className="space-y-1">
+        <span>{t('playlist.filterExcludes', 'Title doesn\'t contain')}</span>
+        <input
+          type="text"
+          value={excludes}
+          onChange={(e) => {
+            setExcludes(e.target.value);
+            onChange({ ...value, titleExcludes: parseFilterWords(e.target.value) });
+          }}
+          placeholder="#shorts"
+          className={inputClass}
+        />
+      </label>
+    </div>
+  );
+}
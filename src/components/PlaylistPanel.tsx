@@ -1,4 +1,4 @@
-import { useState, useCallback } from 'react';
+import { useState, useCallback, useMemo } from 'react';
 import { motion, AnimatePresence } from 'framer-motion';
 import {
   X,
@@ -9,16 +9,20 @@ import {
   Clock,
   RefreshCw,
   Play,
+  Filter,
 } from 'lucide-react';
+import { useTranslation } from 'react-i18next';
 import { Button } from './ui/button';
-import type { PlaylistInfo, PlaylistEntry, DownloadConfig, Format, Quality } from '@/types';
+import { PlaylistFilterFields } from './PlaylistFilterFields';
+import { isPlaylistFilterActive, matchesPlaylistFilter } from '@/lib/playlistFilter';
+import type { PlaylistInfo, PlaylistEntry, PlaylistFilter, PlaylistSelection, DownloadConfig, Format, Quality } from '@/types';
 
 interface PlaylistPanelProps {
   isOpen: boolean;
   onClose: () => void;
   playlistInfo: PlaylistInfo | null;
   isLoading: boolean;
-  onDownloadSelected: (entries: PlaylistEntry[], config: Omit<DownloadConfig, 'url'>) => void;
+  onDownloadSelected: (selection: PlaylistSelection, config: Omit<DownloadConfig, 'url'>) => void;
   format: Format;
   quality: Quality;
   outputFolder: string;
@@ -38,17 +42,30 @@ export function PlaylistPanel({
   embedSubtitles,
   cookiesFromBrowser,
 }: PlaylistPanelProps) {
+  const { t } = useTranslation();
   const [selectedIds, setSelectedIds] = useState<Set<string>>(new Set());
+  const [newest, setNewest] = useState('');
+  const [reverse, setReverse] = useState(false);
+  const [filter, setFilter] = useState<PlaylistFilter>({});
+  const [showFilter, setShowFilter] = useState(false);
+
+  // Entries that pass the filter; the backend applies the same filter when queueing
+  const visibleEntries = useMemo(
+    () => playlistInfo?.entries.filter((e) => matchesPlaylistFilter(e, filter)) ?? [],
+    [playlistInfo, filter]
+  );
+  const selectedEntries = visibleEntries.filter((e) => selectedIds.has(e.id));
+  const allSelected = visibleEntries.length > 0 && selectedEntries.length === visibleEntries.length;
+  const hiddenCount = (playlistInfo?.entries.length ?? 0) - visibleEntries.length;
 
   // Select/deselect all
   const handleSelectAll = useCallback(() => {
-    if (!playlistInfo) return;
-    if (selectedIds.size === playlistInfo.entries.length) {
+    if (allSelected) {
       setSelectedIds(new Set());
     } else {
-      setSelectedIds(new Set(playlistInfo.entries.map((e) => e.id)));
+      setSelectedIds(new Set(visibleEntries.map((e) => e.id)));
     }
-  }, [playlistInfo, selectedIds.size]);
+  }, [allSelected, visibleEntries]);
 
   // Toggle single item
   const toggleItem = useCallback((id: string) => {
@@ -65,17 +82,25 @@ export function PlaylistPanel({
 
   // Download selected
   const handleDownload = useCallback(() => {
-    if (!playlistInfo || selectedIds.size === 0) return;
-    const selectedEntries = playlistInfo.entries.filter((e) => selectedIds.has(e.id));
-    onDownloadSelected(selectedEntries, {
+    if (!playlistInfo || selectedEntries.length === 0) return;
+    // Everything selected is sent as "all" so the backend doesn't get a huge index list
+    const indices = allSelected ? [] : selectedEntries.map((e) => e.playlistIndex);
+    const newestCount = parseInt(newest) || 0;
+    onDownloadSelected({
+      indices,
+      ranges: [],
+      newest: newestCount > 0 ? newestCount : null,
+      reverse,
+    }, {
       format,
       quality,
       outputFolder,
       embedSubtitles,
       cookiesFromBrowser,
+      playlistFilter: isPlaylistFilterActive(filter) ? filter : undefined,
     });
     onClose();
-  }, [playlistInfo, selectedIds, format, quality, outputFolder, embedSubtitles, cookiesFromBrowser, onDownloadSelected, onClose]);
+  }, [playlistInfo, selectedEntries, allSelected, newest, reverse, filter, format, quality, outputFolder, embedSubtitles, cookiesFromBrowser, onDownloadSelected, onClose]);
 
   // Format duration
   const formatDuration = (seconds: number | null): string => {
@@ -86,9 +111,7 @@ export function PlaylistPanel({
   };
 
   // Calculate total duration of selected
-  const totalDuration = playlistInfo?.entries
-    .filter((e) => selectedIds.has(e.id))
-    .reduce((sum, e) => sum + (e.duration || 0), 0) || 0;
+  const totalDuration = selectedEntries.reduce((sum, e) => sum + (e.duration || 0), 0);
 
   return (
     <AnimatePresence>
@@ -159,18 +182,42 @@ export function PlaylistPanel({
                       onClick={handleSelectAll}
                       className="gap-2"
                     >
-                      {selectedIds.size === playlistInfo.entries.length ? (
+                      {allSelected ? (
                         <CheckSquare className="h-4 w-4" />
                       ) : (
                         <Square className="h-4 w-4" />
                       )}
-                      {selectedIds.size === playlistInfo.entries.length
+                      {allSelected
                         ? 'Seçimi Kaldır'
                         : 'Tümünü Seç'}
                     </Button>
+                    <Button
+                      variant={isPlaylistFilterActive(filter) ? 'secondary' : 'ghost'}
+                      size="sm"
+                      onClick={() => setShowFilter(!showFilter)}
+                      className="gap-2"
+                    >
+                      <Filter className="h-4 w-4" />
+                      {t('playlist.filter', 'Filter')}
+                    </Button>
                     <div className="flex items-center gap-4 text-sm text-muted-foreground">
-                      <span>{selectedIds.size} seçili</span>
-                      {selectedIds.size > 0 && (
+                      <label className="flex items-center gap-1">
+                        {t('playlist.newest', 'Newest')}
+                        <input
+                          type="number"
+                          min="1"
+                          value={newest}
+                          onChange={(e) => setNewest(e.target.value)}
+                          placeholder={t('playlist.newestPlaceholder', 'All')}
+                          className="w-16 rounded border border-input bg-background px-2 py-1 text-sm"
+                        />
+                      </label>
+                      <label className="flex cursor-pointer items-center gap-1">
+                        <input type="checkbox" checked={reverse} onChange={(e) => setReverse(e.target.checked)} />
+                        {t('playlist.reverse', 'Reverse order')}
+                      </label>
+                      <span>{selectedEntries.length} seçili</span>
+                      {selectedEntries.length > 0 && (
                         <span className="flex items-center gap-1">
                           <Clock className="h-3 w-3" />
                           {Math.floor(totalDuration / 60)} dk
@@ -179,10 +226,22 @@ export function PlaylistPanel({
                     </div>
                   </div>
 
+                  {/* Filter */}
+                  {showFilter && (
+                    <div className="space-y-2 border-b border-border px-6 py-3">
+                      <PlaylistFilterFields value={filter} onChange={setFilter} />
+                      {hiddenCount > 0 && (
+                        <p className="text-xs text-muted-foreground">
+                          {t('playlist.filterHidden', { count: hiddenCount })}
+                        </p>
+                      )}
+                    </div>
+                  )}
+
                   {/* Video List */}
                   <div className="flex-1 overflow-y-auto px-6 py-4">
                     <div className="space-y-2">
-                      {playlistInfo.entries.map((entry) => (
+                      {visibleEntries.map((entry) => (
                         <PlaylistEntryCard
                           key={entry.id}
                           entry={entry}
@@ -198,11 +257,11 @@ export function PlaylistPanel({
                   <div className="border-t border-border px-6 py-4">
                     <Button
                       className="w-full"
-                      disabled={selectedIds.size === 0}
+                      disabled={selectedEntries.length === 0}
                       onClick={handleDownload}
                     >
                       <Download className="mr-2 h-4 w-4" />
-                      {selectedIds.size} Video İndir
+                      {selectedEntries.length} Video İndir
                     </Button>
                   </div>
                 </>
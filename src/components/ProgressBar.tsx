@@ -46,6 +46,9 @@ export function ProgressBar({ progress, showSpeedGraph = true, className }: Prog
   const speed = progress?.speed ?? "--";
   const eta = progress?.etaSeconds ?? null;
   const isMerging = progress?.status === "merging";
+  // Livestreams have no total, so the bar can't fill up
+  const isRecording = progress?.status === "recording";
+  const elapsed = progress?.elapsedSeconds ?? null;
   const downloadedBytes = progress?.downloadedBytes ?? null;
   const totalBytes = progress?.totalBytes ?? null;
 
@@ -63,10 +66,10 @@ export function ProgressBar({ progress, showSpeedGraph = true, className }: Prog
     >
       {/* Progress bar container */}
       <div className="relative h-5 overflow-hidden rounded-full bg-muted">
-        {isIndeterminate ? (
+        {isIndeterminate || isRecording ? (
           /* Indeterminate loading animation */
           <motion.div
-            className="absolute inset-y-0 left-0 w-1/3 rounded-full bg-primary"
+            className={cn("absolute inset-y-0 left-0 w-1/3 rounded-full", isRecording ? "bg-destructive" : "bg-primary")}
             animate={{
               x: ["-100%", "400%"],
             }}
@@ -117,7 +120,7 @@ export function ProgressBar({ progress, showSpeedGraph = true, className }: Prog
                 animate={{ opacity: 1 }}
                 transition={{ duration: 0.15 }}
               >
-                {percentage.toFixed(1)}%
+                {isRecording ? t("status.recordingElapsed", { elapsed: formatEta(elapsed) }) : `${percentage.toFixed(1)}%`}
               </motion.span>
               {/* File size info */}
               {(downloadedBytes !== null || totalBytes !== null) && (
@@ -149,7 +152,7 @@ export function ProgressBar({ progress, showSpeedGraph = true, className }: Prog
               <SpeedGraph speed={speed} />
             )}
             <span>{speed}</span>
-            <span>ETA: {formatEta(eta)}</span>
+            {!isRecording && <span>ETA: {formatEta(eta)}</span>}
           </div>
         )}
       </div>
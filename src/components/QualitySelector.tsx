@@ -3,11 +3,15 @@ import { useTranslation } from "react-i18next";
 import { Select, type SelectOption } from "./ui/select";
 import type { Quality } from "@/types";
 
-// Estimated file sizes per minute of video (rough approximations)
-const ESTIMATED_SIZES: Record<Quality, string> = {
-  best: "~150 MB/min",
-  "1080p": "~100 MB/min",
-  "720p": "~50 MB/min",
+// Estimated file sizes per minute of video in MB (rough approximations)
+const MB_PER_MINUTE: Record<Quality, number> = {
+  best: 150,
+  "2160p": 400,
+  "1440p": 200,
+  "1080p": 100,
+  "720p": 50,
+  "480p": 25,
+  "360p": 15,
 };
 
 interface QualitySelectorProps {
@@ -28,6 +32,16 @@ export function QualitySelector({ id, value, onChange, disabled, className, dura
       label: t("quality.best", "Best Quality"), 
       icon: <Sparkles className="h-4 w-4" />,
     },
+    { 
+      value: "2160p", 
+      label: t("quality.2160p", "2160p 4K"), 
+      icon: <MonitorPlay className="h-4 w-4" />,
+    },
+    { 
+      value: "1440p", 
+      label: t("quality.1440p", "1440p QHD"), 
+      icon: <MonitorPlay className="h-4 w-4" />,
+    },
     { 
       value: "1080p", 
       label: t("quality.1080p", "1080p HD"), 
@@ -38,15 +52,24 @@ export function QualitySelector({ id, value, onChange, disabled, className, dura
       label: t("quality.720p", "720p HD"), 
       icon: <MonitorPlay className="h-4 w-4" />,
     },
+    { 
+      value: "480p", 
+      label: t("quality.480p", "480p"), 
+      icon: <MonitorPlay className="h-4 w-4" />,
+    },
+    { 
+      value: "360p", 
+      label: t("quality.360p", "360p"), 
+      icon: <MonitorPlay className="h-4 w-4" />,
+    },
   ];
 
   // Calculate estimated file size if duration is provided
   const getEstimatedSize = (quality: Quality): string => {
-    if (!duration || duration <= 0) return ESTIMATED_SIZES[quality];
+    if (!duration || duration <= 0) return `~${MB_PER_MINUTE[quality]} MB/min`;
     
     const minutes = duration / 60;
-    const mbPerMin = quality === "best" ? 150 : quality === "1080p" ? 100 : 50;
-    const totalMb = minutes * mbPerMin;
+    const totalMb = minutes * MB_PER_MINUTE[quality];
     
     if (totalMb >= 1024) {
       return `~${(totalMb / 1024).toFixed(1)} GB`;
@@ -1,4 +1,4 @@
-import { useState } from 'react';
+import { useEffect, useState } from 'react';
 import { motion } from 'framer-motion';
 import {
   X,
@@ -14,6 +14,9 @@ import {
   AlertCircle,
   GripVertical,
   ZoomIn,
+  Radio,
+  CalendarClock,
+  SkipForward,
 } from 'lucide-react';
 import { useTranslation } from 'react-i18next';
 import { Button } from './ui/button';
@@ -40,12 +43,21 @@ export function QueueItemCard({
 }: QueueItemCardProps) {
   const { t } = useTranslation();
   const [showThumbnailPreview, setShowThumbnailPreview] = useState(false);
-  const isActive = item.status === 'downloading' || item.status === 'merging';
-  const isPending = item.status === 'pending';
+  const isActive = item.status === 'downloading' || item.status === 'waitingForLive' || item.status === 'recording' || item.status === 'merging' || item.status === 'transcoding';
+  const isPending = item.status === 'pending' || item.status === 'scheduled';
   const isCompleted = item.status === 'completed';
   const isFailed = item.status === 'failed';
+  const isSkipped = item.status === 'skipped';
   const isCancelled = item.status === 'cancelled';
-  const isTerminal = isCompleted || isFailed || isCancelled;
+  const isTerminal = isCompleted || isSkipped || isFailed || isCancelled;
+  const [now, setNow] = useState(() => Date.now());
+
+  // Tick the countdown while the item waits for its start time
+  useEffect(() => {
+    if (item.status !== 'scheduled') return;
+    const interval = setInterval(() => setNow(Date.now()), 1000);
+    return () => clearInterval(interval);
+  }, [item.status]);
 
   const handleOpenFile = async () => {
     if (item.filePath) {
@@ -82,12 +94,21 @@ export function QueueItemCard({
     switch (item.status) {
       case 'pending':
         return <Clock className="h-4 w-4 text-muted-foreground" />;
+      case 'scheduled':
+        return <CalendarClock className="h-4 w-4 text-primary" />;
+      case 'waitingForLive':
+        return <Clock className="h-4 w-4 animate-pulse text-primary" />;
+      case 'recording':
+        return <Radio className="h-4 w-4 animate-pulse text-destructive" />;
       case 'downloading':
       case 'merging':
+      case 'transcoding':
       case 'cancelling':
         return <Loader2 className="h-4 w-4 animate-spin text-primary" />;
       case 'completed':
         return <CheckCircle className="h-4 w-4 text-green-500" />;
+      case 'skipped':
+        return <SkipForward className="h-4 w-4 text-muted-foreground" />;
       case 'failed':
         return <XCircle className="h-4 w-4 text-destructive" />;
       case 'cancelled':
@@ -99,14 +120,30 @@ export function QueueItemCard({
     switch (item.status) {
       case 'pending':
         return t('queue.pending', 'Waiting...');
+      case 'scheduled': {
+        const startsIn = Math.max(0, Math.ceil((item.schedule?.notBefore ?? 0) - now / 1000));
+        const deadline = item.schedule?.deadline;
+        const startText = t('queue.startsIn', { time: formatEta(startsIn) });
+        return deadline
+          ? `${startText} • ${t('queue.deadline', { time: new Date(deadline * 1000).toLocaleString() })}`
+          : startText;
+      }
       case 'downloading':
         return `${item.progress.toFixed(1)}% • ${item.speed}${item.etaSeconds ? ` • ${formatEta(item.etaSeconds)}` : ''}`;
+      case 'waitingForLive':
+        return t('status.waitingForLive', 'Waiting for the livestream to start...');
+      case 'recording':
+        return `${t('status.recordingElapsed', { elapsed: formatEta(item.elapsedSeconds) })} • ${item.speed}`;
       case 'merging':
         return t('status.merging', 'Merging...');
+      case 'transcoding':
+        return `${t('status.transcoding', 'Transcoding...')} ${item.progress.toFixed(1)}%${item.etaSeconds ? ` • ${formatEta(item.etaSeconds)}` : ''}`;
       case 'cancelling':
         return t('status.cancelling', 'Cancelling...');
       case 'completed':
         return t('queue.completed', 'Completed');
+      case 'skipped':
+        return item.skipReason || t('queue.skipped', 'Skipped');
       case 'failed':
         return item.error || t('queue.failed', 'Failed');
       case 'cancelled':
@@ -242,10 +279,10 @@ export function QueueItemCard({
             <motion.div
               className={cn(
                 'h-full rounded-full',
-                item.status === 'merging' ? 'bg-yellow-500' : 'bg-primary'
+                item.status === 'recording' ? 'bg-destructive animate-pulse' : item.status === 'merging' ? 'bg-yellow-500' : 'bg-primary'
               )}
               initial={{ width: 0 }}
-              animate={{ width: `${item.progress}%` }}
+              animate={{ width: item.status === 'recording' ? '100%' : `${item.progress}%` }}
               transition={{ duration: 0.3 }}
             />
           </div>
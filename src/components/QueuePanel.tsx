@@ -25,15 +25,15 @@ export function QueuePanel({ isOpen, onClose }: QueuePanelProps) {
   const [isDragging, setIsDragging] = useState(false);
   const parentRef = useRef<HTMLDivElement>(null);
 
-  const hasCompletedOrFailed = completedCount > 0 || failedCount > 0;
+  const hasCompletedOrFailed = completedCount > 0 || failedCount > 0 || items.some(i => i.status === 'skipped');
   const hasPending = pendingCount > 0;
   const hasActive = activeCount > 0;
   const hasSelection = selectedIds.size > 0;
 
   // Separate items by status
-  const pendingItems = items.filter(i => i.status === 'pending');
-  const activeItems = items.filter(i => i.status === 'downloading' || i.status === 'merging');
-  const terminalItems = items.filter(i => i.status === 'completed' || i.status === 'failed' || i.status === 'cancelled');
+  const pendingItems = items.filter(i => i.status === 'pending' || i.status === 'scheduled');
+  const activeItems = items.filter(i => i.status === 'downloading' || i.status === 'waitingForLive' || i.status === 'recording' || i.status === 'merging' || i.status === 'transcoding');
+  const terminalItems = items.filter(i => i.status === 'completed' || i.status === 'skipped' || i.status === 'failed' || i.status === 'cancelled');
 
   // Use virtualization only when there are many items
   const useVirtualization = items.length > VIRTUALIZATION_THRESHOLD;
@@ -68,7 +68,7 @@ export function QueuePanel({ isOpen, onClose }: QueuePanelProps) {
   }, []);
 
   const selectAll = useCallback(() => {
-    setSelectedIds(new Set(items.filter(i => i.status === 'pending').map(i => i.id)));
+    setSelectedIds(new Set(items.filter(i => i.status === 'pending' || i.status === 'scheduled').map(i => i.id)));
   }, [items]);
 
   const deselectAll = useCallback(() => {
@@ -32,6 +32,7 @@ export function ScheduleDownload({
   const [url, setUrl] = useState('');
   const [date, setDate] = useState('');
   const [time, setTime] = useState('');
+  const [deadline, setDeadline] = useState('');
   const [format, setFormat] = useState<Format>(currentFormat);
   const [quality, setQuality] = useState<Quality>(currentQuality);
   const [error, setError] = useState<string | null>(null);
@@ -64,17 +65,25 @@ export function ScheduleDownload({
       return;
     }
 
+    const deadlineTime = deadline ? new Date(deadline).getTime() : null;
+    if (deadlineTime !== null && deadlineTime <= scheduledTime) {
+      setError(t('schedule.deadlineBeforeStart', 'The deadline must be after the start time'));
+      return;
+    }
+
     onSchedule({
       url: validation.sanitizedUrl || url,
       format,
       quality,
       scheduledTime,
+      deadline: deadlineTime,
       enabled: true,
     });
 
     setUrl('');
+    setDeadline('');
     setError(null);
-  }, [url, date, time, format, quality, onSchedule]);
+  }, [url, date, time, deadline, format, quality, onSchedule, t]);
 
   const formatScheduledTime = (timestamp: number) => {
     const date = new Date(timestamp);
@@ -157,6 +166,16 @@ export function ScheduleDownload({
                 </div>
               </div>
 
+              <div>
+                <label className="block text-xs text-muted-foreground mb-1">{t('schedule.deadline', 'Finish by (optional)')}</label>
+                <input
+                  type="datetime-local"
+                  value={deadline}
+                  onChange={(e) => { setDeadline(e.target.value); setError(null); }}
+                  className="w-full rounded-lg border border-input bg-background px-3 py-2 text-sm focus:outline-none focus:ring-2 focus:ring-ring"
+                />
+              </div>
+
               <div className="grid grid-cols-2 gap-3">
                 <div>
                   <label className="block text-xs text-muted-foreground mb-1">{t('form.format', 'Format')}</label>
@@ -220,6 +239,11 @@ export function ScheduleDownload({
                           <span>•</span>
                           <span className="text-primary">{getTimeRemaining(download.scheduledTime)}</span>
                         </div>
+                        {download.deadline && (
+                          <p className="text-xs text-muted-foreground">
+                            {t('schedule.finishBy', { time: formatScheduledTime(download.deadline) })}
+                          </p>
+                        )}
                       </div>
                       <div className="flex items-center gap-1">
                         <Button
@@ -1,22 +1,39 @@
 import { useState, useEffect, useCallback, useRef } from "react";
 import { motion, AnimatePresence } from "framer-motion";
-import { X, Settings, Cookie, Subtitles, Globe, Shield, Languages, Download, RefreshCw, CheckCircle, Palette, Sun, Moon, Monitor, FileText, RotateCcw, Gauge, Contrast } from "lucide-react";
+import { X, Settings, Cookie, Subtitles, Globe, Shield, Languages, Download, RefreshCw, CheckCircle, Palette, Sun, Moon, Monitor, FileText, RotateCcw, Gauge, Contrast, Film, Folder, FolderTree, Volume2, Archive, Radio, Rss, Clipboard, Plug, Copy } from "lucide-react";
 import { invoke, listen } from "@/lib/tauri";
 import { useTranslation } from "react-i18next";
 import { Button } from "./ui/button";
 import { Select, type SelectOption } from "./ui/select";
 import { UpdateButton } from "./UpdateButton";
 import { CopyDebugInfoButton } from "./CopyDebugInfoButton";
+import { PlaylistFilterFields } from "./PlaylistFilterFields";
+import { FormatSelector } from "./FormatSelector";
+import { QualitySelector } from "./QualitySelector";
 import { useTheme, ACCENT_COLORS, type AccentColor, type Theme } from "./ThemeProvider";
 import { useToast } from "./Toast";
 import { useFocusTrap } from "@/hooks/useFocusTrap";
 import { buttonVariants, springTransition, fadeInVariants, defaultTransition } from "@/lib/animations";
 import { supportedLanguages, type SupportedLanguage } from "@/i18n";
 import { cn } from "@/lib/utils";
-import type { Preferences } from "@/types";
+import type { ApiInfo, ApiOptions, ArchiveEntry, ClipboardAction, ClipboardOptions, DownloadConfig, LiveOptions, LiveStart, MediaInfo, OrganizeRule, PlaylistFilter, Preferences, Subscription, SubscriptionSyncResult, TranscodePreset } from "@/types";
 
 type SettingsTab = "general" | "download" | "appearance" | "advanced" | "about";
 
+// Sample metadata used to preview filename templates
+const SAMPLE_MEDIA_INFO: MediaInfo = {
+  title: "Never Gonna Give You Up",
+  thumbnail: null,
+  duration: 213,
+  uploader: "Rick Astley",
+  filesizeApprox: null,
+  id: "dQw4w9WgXcQ",
+  channel: "Rick Astley",
+  uploadDate: "20091025",
+  width: 1920,
+  height: 1080,
+};
+
 const BROWSER_OPTIONS: { value: string; labelKey: string; label: string }[] = [
   { value: "", labelKey: "settings.cookiesNone", label: "None" },
   { value: "chrome", labelKey: "", label: "Google Chrome" },
@@ -52,10 +69,31 @@ const DEFAULT_PREFERENCES: Preferences = {
   proxyUrl: null,
   filenameTemplate: null,
   cookiesFilePath: null,
+  organizeRules: [],
+  audioOutputFolder: null,
+  videoOutputFolder: null,
+  formatOutputFolders: {},
+  transcodePreset: null,
+  normalizeLoudness: false,
+  loudnessTargetLufs: -16,
+  useDownloadArchive: false,
+  live: { enabled: false, start: "fromNow", maxDurationSeconds: null, waitForStream: false, pollIntervalSeconds: 60 },
+  clipboard: { enabled: false, action: "notify", format: null, quality: null, allowedDomains: ["youtube.com", "youtu.be", "vimeo.com", "dailymotion.com", "twitch.tv", "soundcloud.com", "bandcamp.com", "bilibili.com", "nicovideo.jp", "tiktok.com"] },
+  api: { enabled: false, port: 47821 },
   bandwidthLimit: null,
   scheduledDownloads: null,
 };
 
+// Folder organization rules, in display order
+const ORGANIZE_RULES: OrganizeRule[] = ["site", "uploader", "playlist", "mediaType", "year"];
+
+// Post-download transcoding presets ("" = keep as downloaded)
+const TRANSCODE_PRESETS: Array<TranscodePreset | ""> = ["", "phone720p", "editingIntra", "voiceMemo"];
+// Where live recordings begin
+const LIVE_STARTS: LiveStart[] = ["fromNow", "fromStart"];
+// What happens to copied links
+const CLIPBOARD_ACTIONS: ClipboardAction[] = ["notify", "enqueue"];
+
 // Bandwidth limit presets in KB/s
 const BANDWIDTH_PRESETS = [
   { value: 0, label: 'Unlimited' },
@@ -88,9 +126,32 @@ export function SettingsPanel({ isOpen, onClose, preferences, onPreferencesChang
   const [proxyEnabled, setProxyEnabled] = useState(preferences?.proxyEnabled ?? false);
   const [proxyUrl, setProxyUrl] = useState(preferences?.proxyUrl ?? "");
   const [filenameTemplate, setFilenameTemplate] = useState(preferences?.filenameTemplate ?? "");
+  const [templatePreview, setTemplatePreview] = useState<{ path: string | null; error: string | null }>({ path: null, error: null });
   const [checkAppUpdatesOnStartup, setCheckAppUpdatesOnStartup] = useState(preferences?.checkAppUpdatesOnStartup ?? true);
   const [cookiesFilePath, setCookiesFilePath] = useState<string>(preferences?.cookiesFilePath ?? "");
   const [bandwidthLimit, setBandwidthLimit] = useState<number>(preferences?.bandwidthLimit ?? 0);
+  const [organizeRules, setOrganizeRules] = useState<OrganizeRule[]>(preferences?.organizeRules ?? []);
+  const [audioOutputFolder, setAudioOutputFolder] = useState<string>(preferences?.audioOutputFolder ?? "");
+  const [videoOutputFolder, setVideoOutputFolder] = useState<string>(preferences?.videoOutputFolder ?? "");
+  const [transcodePreset, setTranscodePreset] = useState<string>(preferences?.transcodePreset ?? "");
+  const [normalizeLoudness, setNormalizeLoudness] = useState(preferences?.normalizeLoudness ?? false);
+  const [loudnessTargetLufs, setLoudnessTargetLufs] = useState<number>(preferences?.loudnessTargetLufs ?? -16);
+  const [useDownloadArchive, setUseDownloadArchive] = useState(preferences?.useDownloadArchive ?? false);
+  const [archiveEntries, setArchiveEntries] = useState<ArchiveEntry[]>([]);
+  const [live, setLive] = useState<LiveOptions>(preferences?.live ?? DEFAULT_PREFERENCES.live);
+  const [clipboard, setClipboard] = useState<ClipboardOptions>(preferences?.clipboard ?? DEFAULT_PREFERENCES.clipboard);
+  const [clipboardDomains, setClipboardDomains] = useState((preferences?.clipboard ?? DEFAULT_PREFERENCES.clipboard).allowedDomains.join(", "));
+  const [api, setApi] = useState<ApiOptions>(preferences?.api ?? DEFAULT_PREFERENCES.api);
+  const [apiPort, setApiPort] = useState(String((preferences?.api ?? DEFAULT_PREFERENCES.api).port));
+  const [apiInfo, setApiInfo] = useState<ApiInfo | null>(null);
+  const [subscriptions, setSubscriptions] = useState<Subscription[]>([]);
+  const [subscriptionUrl, setSubscriptionUrl] = useState("");
+  const [subscriptionIncludeExisting, setSubscriptionIncludeExisting] = useState(false);
+  const [subscriptionError, setSubscriptionError] = useState<string | null>(null);
+  const [subscriptionFilter, setSubscriptionFilter] = useState<PlaylistFilter>({});
+  // Bumped to clear the filter inputs after subscribing
+  const [subscriptionFormKey, setSubscriptionFormKey] = useState(0);
+  const [isSubscribing, setIsSubscribing] = useState(false);
 
   const currentLanguage = (i18n.language?.split("-")[0] || "en") as SupportedLanguage;
 
@@ -99,13 +160,37 @@ export function SettingsPanel({ isOpen, onClose, preferences, onPreferencesChang
     label: opt.labelKey ? t(opt.labelKey) : opt.label,
   }));
 
+  const transcodeOptions: SelectOption[] = TRANSCODE_PRESETS.map((preset) => ({
+    value: preset,
+    label: t(`settings.transcodePresets.${preset || "none"}`),
+  }));
+
+  const liveStartOptions: SelectOption[] = LIVE_STARTS.map((start) => ({
+    value: start,
+    label: t(`settings.liveStarts.${start}`),
+  }));
+
+  const clipboardActionOptions: SelectOption[] = CLIPBOARD_ACTIONS.map((action) => ({
+    value: action,
+    label: t(`settings.clipboardActions.${action}`),
+  }));
+
   useEffect(() => {
     if (isOpen) {
       invoke<string>("get_ytdlp_version_cmd").then(setYtdlpVersion).catch(() => setYtdlpVersion(null));
       invoke<string>("get_app_version").then(setAppVersion).catch(() => setAppVersion(null));
+      invoke<ArchiveEntry[]>("archive_list").then(setArchiveEntries).catch(() => setArchiveEntries([]));
+      invoke<Subscription[]>("subscription_list").then(setSubscriptions).catch(() => setSubscriptions([]));
     }
   }, [isOpen]);
 
+  useEffect(() => {
+    // Creates the token the first time the API is turned on
+    if (isOpen && api.enabled) {
+      invoke<ApiInfo>("api_get_info").then(setApiInfo).catch(() => setApiInfo(null));
+    }
+  }, [isOpen, api.enabled, api.port]);
+
   useEffect(() => {
     const unlistenAvailable = listen<{ currentVersion: string; newVersion: string }>("app-update-available", (event) => {
       setAppUpdateAvailable(true);
@@ -131,6 +216,18 @@ export function SettingsPanel({ isOpen, onClose, preferences, onPreferencesChang
       setFilenameTemplate(preferences.filenameTemplate ?? "");
       setCookiesFilePath(preferences.cookiesFilePath ?? "");
       setBandwidthLimit(preferences.bandwidthLimit ?? 0);
+      setOrganizeRules(preferences.organizeRules ?? []);
+      setAudioOutputFolder(preferences.audioOutputFolder ?? "");
+      setVideoOutputFolder(preferences.videoOutputFolder ?? "");
+      setTranscodePreset(preferences.transcodePreset ?? "");
+      setNormalizeLoudness(preferences.normalizeLoudness ?? false);
+      setLoudnessTargetLufs(preferences.loudnessTargetLufs ?? -16);
+      setUseDownloadArchive(preferences.useDownloadArchive ?? false);
+      setLive(preferences.live ?? DEFAULT_PREFERENCES.live);
+      setClipboard(preferences.clipboard ?? DEFAULT_PREFERENCES.clipboard);
+      setClipboardDomains((preferences.clipboard ?? DEFAULT_PREFERENCES.clipboard).allowedDomains.join(", "));
+      setApi(preferences.api ?? DEFAULT_PREFERENCES.api);
+      setApiPort(String((preferences.api ?? DEFAULT_PREFERENCES.api).port));
     }
   }, [preferences]);
 
@@ -162,6 +259,143 @@ export function SettingsPanel({ isOpen, onClose, preferences, onPreferencesChang
   const handleProxyToggle = useCallback(() => { const v = !proxyEnabled; setProxyEnabled(v); savePreference("proxyEnabled", v); }, [proxyEnabled, savePreference]);
   const handleProxyUrlChange = useCallback((value: string) => { setProxyUrl(value); savePreference("proxyUrl", value || null); }, [savePreference]);
   const handleFilenameTemplateChange = useCallback((value: string) => { setFilenameTemplate(value); savePreference("filenameTemplate", value || null); }, [savePreference]);
+  useEffect(() => {
+    let cancelled = false;
+    invoke<string>("preview_filename_template", { template: filenameTemplate, info: SAMPLE_MEDIA_INFO, format: preferences?.format ?? null })
+      .then((path) => { if (!cancelled) setTemplatePreview({ path, error: null }); })
+      .catch((error) => { if (!cancelled) setTemplatePreview({ path: null, error: String(error) }); });
+    return () => { cancelled = true; };
+  }, [filenameTemplate, preferences?.format]);
+  const handleOrganizeRuleToggle = useCallback((rule: OrganizeRule) => {
+    const v = organizeRules.includes(rule) ? organizeRules.filter((r) => r !== rule) : [...organizeRules, rule];
+    setOrganizeRules(v);
+    savePreference("organizeRules", v);
+  }, [organizeRules, savePreference]);
+  const handlePickMediaFolder = useCallback(async (key: "audioOutputFolder" | "videoOutputFolder") => {
+    try {
+      const folder = await invoke<string | null>("pick_folder");
+      if (folder) {
+        (key === "audioOutputFolder" ? setAudioOutputFolder : setVideoOutputFolder)(folder);
+        savePreference(key, folder);
+      }
+    } catch (err) {
+      console.error("Failed to pick folder:", err);
+    }
+  }, [savePreference]);
+  const handleClearMediaFolder = useCallback((key: "audioOutputFolder" | "videoOutputFolder") => {
+    (key === "audioOutputFolder" ? setAudioOutputFolder : setVideoOutputFolder)("");
+    savePreference(key, null);
+  }, [savePreference]);
+  const handleTranscodePresetChange = useCallback((value: string) => { setTranscodePreset(value); savePreference("transcodePreset", (value || null) as TranscodePreset | null); }, [savePreference]);
+  const handleNormalizeLoudnessToggle = useCallback(() => { const v = !normalizeLoudness; setNormalizeLoudness(v); savePreference("normalizeLoudness", v); }, [normalizeLoudness, savePreference]);
+  const handleLoudnessTargetChange = useCallback((value: number) => {
+    // ffmpeg's loudnorm accepts -70..-5 LUFS
+    const v = Math.min(-5, Math.max(-70, value));
+    setLoudnessTargetLufs(v);
+    savePreference("loudnessTargetLufs", v);
+  }, [savePreference]);
+  const handleDownloadArchiveToggle = useCallback(() => { const v = !useDownloadArchive; setUseDownloadArchive(v); savePreference("useDownloadArchive", v); }, [useDownloadArchive, savePreference]);
+  const handleLiveChange = useCallback((patch: Partial<LiveOptions>) => { const v = { ...live, ...patch }; setLive(v); savePreference("live", v); }, [live, savePreference]);
+  const handleClipboardChange = useCallback((patch: Partial<ClipboardOptions>) => { const v = { ...clipboard, ...patch }; setClipboard(v); savePreference("clipboard", v); }, [clipboard, savePreference]);
+  const handleApiChange = useCallback((patch: Partial<ApiOptions>) => { const v = { ...api, ...patch }; setApi(v); savePreference("api", v); }, [api, savePreference]);
+  const handleApiPortBlur = useCallback(() => {
+    const port = parseInt(apiPort);
+    if (port >= 1024 && port <= 65535) handleApiChange({ port });
+    else setApiPort(String(api.port));
+  }, [apiPort, api.port, handleApiChange]);
+  const handleCopyApiToken = useCallback(async () => {
+    if (!apiInfo) return;
+    await navigator.clipboard.writeText(apiInfo.token);
+    success(t("toast.copiedToClipboard"));
+  }, [apiInfo, success, t]);
+  const handleRegenerateApiToken = useCallback(() => {
+    invoke<ApiInfo>("api_regenerate_token").then(setApiInfo).catch((err) => console.error("Failed to regenerate API token:", err));
+  }, []);
+  const refreshArchive = useCallback(() => {
+    invoke<ArchiveEntry[]>("archive_list").then(setArchiveEntries).catch((err) => console.error("Failed to load download archive:", err));
+  }, []);
+  const handleImportArchive = useCallback(async () => {
+    try {
+      const path = await invoke<string | null>("pick_archive_import_file");
+      if (!path) return;
+      const added = await invoke<number>("archive_import", { path });
+      success(t("settings.archiveImported", { count: added }));
+      refreshArchive();
+    } catch (err) {
+      console.error("Failed to import download archive:", err);
+    }
+  }, [refreshArchive, success, t]);
+  const handleExportArchive = useCallback(async () => {
+    try {
+      const path = await invoke<string | null>("pick_archive_export_file");
+      if (!path) return;
+      const written = await invoke<number>("archive_export", { path });
+      success(t("settings.archiveExported", { count: written }));
+    } catch (err) {
+      console.error("Failed to export download archive:", err);
+    }
+  }, [success, t]);
+  const handleRemoveArchiveEntry = useCallback(async (entry: ArchiveEntry) => {
+    try {
+      await invoke<number>("archive_prune", { entries: [entry] });
+      refreshArchive();
+    } catch (err) {
+      console.error("Failed to remove archive entry:", err);
+    }
+  }, [refreshArchive]);
+  const refreshSubscriptions = useCallback(() => {
+    invoke<Subscription[]>("subscription_list").then(setSubscriptions).catch((err) => console.error("Failed to load subscriptions:", err));
+  }, []);
+  const handleAddSubscription = useCallback(async () => {
+    if (!subscriptionUrl.trim() || !preferences) return;
+    // New entries are downloaded with the current default settings
+    const preset: DownloadConfig = {
+      url: "",
+      format: preferences.format,
+      quality: preferences.quality,
+      outputFolder: preferences.outputFolder,
+      embedSubtitles: preferences.embedSubtitles,
+      cookiesFromBrowser: preferences.cookiesFromBrowser,
+      filenameTemplate: preferences.filenameTemplate,
+      proxyUrl: preferences.proxyEnabled ? preferences.proxyUrl : null,
+      cookiesFilePath: preferences.cookiesFilePath,
+      organizeRules: preferences.organizeRules,
+      transcodePreset: preferences.transcodePreset,
+      loudnessTargetLufs: preferences.normalizeLoudness ? preferences.loudnessTargetLufs : null,
+      playlistFilter: subscriptionFilter,
+    };
+    setIsSubscribing(true);
+    setSubscriptionError(null);
+    try {
+      await invoke<Subscription>("subscription_add", { url: subscriptionUrl.trim(), preset, intervalMinutes: null, includeExisting: subscriptionIncludeExisting });
+      setSubscriptionUrl("");
+      setSubscriptionFilter({});
+      setSubscriptionFormKey((key) => key + 1);
+      success(t("settings.subscriptionAdded"));
+      refreshSubscriptions();
+    } catch (err) {
+      setSubscriptionError(String(err));
+    } finally {
+      setIsSubscribing(false);
+    }
+  }, [subscriptionUrl, subscriptionIncludeExisting, subscriptionFilter, preferences, refreshSubscriptions, success, t]);
+  const handleRemoveSubscription = useCallback(async (id: string) => {
+    try {
+      await invoke("subscription_remove", { id });
+      refreshSubscriptions();
+    } catch (err) {
+      console.error("Failed to remove subscription:", err);
+    }
+  }, [refreshSubscriptions]);
+  const handleSyncSubscription = useCallback(async (id: string) => {
+    try {
+      const [result] = await invoke<SubscriptionSyncResult[]>("subscription_sync_now", { id });
+      if (result && !result.error) success(t("settings.subscriptionSynced", { count: result.queued }));
+      refreshSubscriptions();
+    } catch (err) {
+      console.error("Failed to sync subscription:", err);
+    }
+  }, [refreshSubscriptions, success, t]);
   const handleCheckAppUpdatesToggle = useCallback(() => { const v = !checkAppUpdatesOnStartup; setCheckAppUpdatesOnStartup(v); savePreference("checkAppUpdatesOnStartup", v); }, [checkAppUpdatesOnStartup, savePreference]);
   const handlePickCookiesFile = useCallback(async () => {
     try {
@@ -350,6 +584,12 @@ export function SettingsPanel({ isOpen, onClose, preferences, onPreferencesChang
                         <div className="space-y-3">
                           <p className="text-xs text-muted-foreground">{t("settings.filenameTemplateDescription")}</p>
                           <input type="text" value={filenameTemplate} onChange={(e) => handleFilenameTemplateChange(e.target.value)} placeholder="{title}" className="w-full rounded-md border border-input bg-background px-3 py-2 text-sm focus:outline-none focus:ring-2 focus:ring-ring" />
+                          {templatePreview.error ? (
+                            <p className="text-xs text-destructive">{templatePreview.error}</p>
+                          ) : templatePreview.path && (
+                            <p className="text-xs text-muted-foreground break-all">{t("settings.templatePreview")}: <code className="bg-muted px-1 rounded">{templatePreview.path}</code></p>
+                          )}
+                          <p className="text-xs text-muted-foreground">{t("settings.templateSyntaxHint")}</p>
                           <div className="text-xs text-muted-foreground space-y-1">
                             <p className="font-medium">{t("settings.availablePlaceholders")}:</p>
                             <div className="grid grid-cols-3 gap-1">
@@ -359,10 +599,209 @@ export function SettingsPanel({ isOpen, onClose, preferences, onPreferencesChang
                               <code className="bg-muted px-1 rounded">{"{date}"}</code>
                               <code className="bg-muted px-1 rounded">{"{quality}"}</code>
                               <code className="bg-muted px-1 rounded">{"{id}"}</code>
+                              <code className="bg-muted px-1 rounded">{"{resolution}"}</code>
+                              <code className="bg-muted px-1 rounded">{"{duration}"}</code>
+                              <code className="bg-muted px-1 rounded">{"{playlist_index}"}</code>
                             </div>
                           </div>
                         </div>
                       </Section>
+                      <Section title={t("settings.mediaFolders")} icon={<Folder className="h-4 w-4" />}>
+                        <div className="space-y-3">
+                          <p className="text-xs text-muted-foreground">{t("settings.mediaFoldersDescription")}</p>
+                          {([["audioOutputFolder", audioOutputFolder], ["videoOutputFolder", videoOutputFolder]] as const).map(([key, value]) => (
+                            <div key={key} className="space-y-2">
+                              <label className="text-sm font-medium">{t(`settings.${key}`)}</label>
+                              <div className="flex gap-2">
+                                <input
+                                  type="text"
+                                  value={value}
+                                  readOnly
+                                  placeholder={preferences?.outputFolder || t("settings.mediaFolderPlaceholder")}
+                                  className="flex-1 rounded-md border border-input bg-muted/50 px-3 py-2 text-sm"
+                                />
+                                <Button variant="outline" size="sm" onClick={() => handlePickMediaFolder(key)}>
+                                  {t("buttons.browse")}
+                                </Button>
+                                {value && (
+                                  <Button variant="ghost" size="sm" onClick={() => handleClearMediaFolder(key)}>
+                                    <X className="h-4 w-4" />
+                                  </Button>
+                                )}
+                              </div>
+                            </div>
+                          ))}
+                        </div>
+                      </Section>
+                      <Section title={t("settings.transcodePreset")} icon={<Film className="h-4 w-4" />}>
+                        <div className="space-y-2">
+                          <p className="text-xs text-muted-foreground">{t("settings.transcodePresetDescription")}</p>
+                          <Select value={transcodePreset} onChange={handleTranscodePresetChange} options={transcodeOptions} />
+                        </div>
+                      </Section>
+                      <Section title={t("settings.loudness")} icon={<Volume2 className="h-4 w-4" />}>
+                        <div className="space-y-3">
+                          <p className="text-xs text-muted-foreground">{t("settings.loudnessDescription")}</p>
+                          <ToggleSwitch id="normalize-loudness" label={t("settings.normalizeLoudness")} checked={normalizeLoudness} onChange={handleNormalizeLoudnessToggle} />
+                          <div className="flex items-center gap-2">
+                            <input
+                              type="number"
+                              value={loudnessTargetLufs}
+                              onChange={(e) => handleLoudnessTargetChange(parseFloat(e.target.value) || -16)}
+                              min="-70"
+                              max="-5"
+                              step="1"
+                              disabled={!normalizeLoudness}
+                              className="w-24 rounded-md border border-input bg-background px-3 py-2 text-sm focus:outline-none focus:ring-2 focus:ring-ring"
+                            />
+                            <span className="text-sm text-muted-foreground">LUFS</span>
+                          </div>
+                        </div>
+                      </Section>
+                      <Section title={t("settings.organizeFolders")} icon={<FolderTree className="h-4 w-4" />}>
+                        <div className="space-y-3">
+                          <p className="text-xs text-muted-foreground">{t("settings.organizeFoldersDescription")}</p>
+                          <div className="flex flex-wrap gap-2">
+                            {ORGANIZE_RULES.map((rule) => {
+                              const position = organizeRules.indexOf(rule);
+                              return (
+                                <Button key={rule} size="sm" variant={position >= 0 ? "default" : "outline"} onClick={() => handleOrganizeRuleToggle(rule)}>
+                                  {position >= 0 && <span className="mr-1 text-xs opacity-70">{position + 1}.</span>}
+                                  {t(`settings.organizeRules.${rule}`)}
+                                </Button>
+                              );
+                            })}
+                          </div>
+                          {organizeRules.length > 0 && (
+                            <p className="text-xs text-muted-foreground break-all">
+                              {t("settings.templatePreview")}: <code className="bg-muted px-1 rounded">{organizeRules.map((rule) => t(`settings.organizeRules.${rule}`)).join("/")}/</code>
+                            </p>
+                          )}
+                        </div>
+                      </Section>
+                      <Section title={t("settings.downloadArchive")} icon={<Archive className="h-4 w-4" />}>
+                        <div className="space-y-3">
+                          <p className="text-xs text-muted-foreground">{t("settings.downloadArchiveDescription")}</p>
+                          <ToggleSwitch id="use-download-archive" label={t("settings.useDownloadArchive")} checked={useDownloadArchive} onChange={handleDownloadArchiveToggle} />
+                          <div className="flex items-center gap-2">
+                            <span className="flex-1 text-sm text-muted-foreground">{t("settings.archiveCount", { count: archiveEntries.length })}</span>
+                            <Button variant="outline" size="sm" onClick={handleImportArchive}>{t("settings.archiveImport")}</Button>
+                            <Button variant="outline" size="sm" onClick={handleExportArchive} disabled={archiveEntries.length === 0}>{t("settings.archiveExport")}</Button>
+                          </div>
+                          {archiveEntries.length > 0 && (
+                            <ul className="max-h-40 overflow-y-auto rounded-md border border-input divide-y divide-border">
+                              {[...archiveEntries].reverse().map((entry) => (
+                                <li key={`${entry.extractor} ${entry.id}`} className="flex items-center gap-2 px-3 py-1 text-xs">
+                                  <span className="text-muted-foreground">{entry.extractor}</span>
+                                  <code className="flex-1 truncate">{entry.id}</code>
+                                  <Button variant="ghost" size="sm" onClick={() => handleRemoveArchiveEntry(entry)} aria-label={t("settings.archiveRemove")} title={t("settings.archiveRemove")}>
+                                    <X className="h-3 w-3" />
+                                  </Button>
+                                </li>
+                              ))}
+                            </ul>
+                          )}
+                        </div>
+                      </Section>
+                      <Section title={t("settings.subscriptions")} icon={<Rss className="h-4 w-4" />}>
+                        <div className="space-y-3">
+                          <p className="text-xs text-muted-foreground">{t("settings.subscriptionsDescription")}</p>
+                          <div className="flex items-center gap-2">
+                            <input
+                              type="url"
+                              value={subscriptionUrl}
+                              onChange={(e) => { setSubscriptionUrl(e.target.value); setSubscriptionError(null); }}
+                              placeholder="https://www.youtube.com/@channel"
+                              className="flex-1 rounded-md border border-input bg-background px-3 py-2 text-sm focus:outline-none focus:ring-2 focus:ring-ring"
+                            />
+                            <Button variant="outline" size="sm" onClick={handleAddSubscription} disabled={!subscriptionUrl.trim() || isSubscribing}>{t("settings.subscriptionAdd")}</Button>
+                          </div>
+                          <ToggleSwitch id="subscription-include-existing" label={t("settings.subscriptionIncludeExisting")} checked={subscriptionIncludeExisting} onChange={() => setSubscriptionIncludeExisting(!subscriptionIncludeExisting)} />
+                          <details className="text-sm">
+                            <summary className="cursor-pointer text-muted-foreground">{t("settings.subscriptionFilter")}</summary>
+                            <div className="pt-2">
+                              <PlaylistFilterFields key={subscriptionFormKey} value={subscriptionFilter} onChange={setSubscriptionFilter} />
+                            </div>
+                          </details>
+                          {subscriptionError && <p className="text-xs text-destructive">{subscriptionError}</p>}
+                          {subscriptions.length > 0 && (
+                            <ul className="max-h-40 overflow-y-auto rounded-md border border-input divide-y divide-border">
+                              {subscriptions.map((subscription) => (
+                                <li key={subscription.id} className="flex items-center gap-2 px-3 py-1 text-xs">
+                                  <div className="flex-1 min-w-0">
+                                    <p className="truncate">{subscription.title ?? subscription.url}</p>
+                                    <p className={cn("truncate", subscription.lastError ? "text-destructive" : "text-muted-foreground")}>
+                                      {subscription.lastError ?? (subscription.lastSyncedAt ? t("settings.subscriptionLastSynced", { time: new Date(subscription.lastSyncedAt * 1000).toLocaleString() }) : t("settings.subscriptionNeverSynced"))}
+                                    </p>
+                                  </div>
+                                  <Button variant="ghost" size="sm" onClick={() => handleSyncSubscription(subscription.id)} aria-label={t("settings.subscriptionSyncNow")} title={t("settings.subscriptionSyncNow")}>
+                                    <RefreshCw className="h-3 w-3" />
+                                  </Button>
+                                  <Button variant="ghost" size="sm" onClick={() => handleRemoveSubscription(subscription.id)} aria-label={t("settings.subscriptionRemove")} title={t("settings.subscriptionRemove")}>
+                                    <X className="h-3 w-3" />
+                                  </Button>
+                                </li>
+                              ))}
+                            </ul>
+                          )}
+                        </div>
+                      </Section>
+                      <Section title={t("settings.live")} icon={<Radio className="h-4 w-4" />}>
+                        <div className="space-y-3">
+                          <p className="text-xs text-muted-foreground">{t("settings.liveDescription")}</p>
+                          <ToggleSwitch id="live-enabled" label={t("settings.liveEnabled")} checked={live.enabled} onChange={() => handleLiveChange({ enabled: !live.enabled })} />
+                          <Select value={live.start} onChange={(value) => handleLiveChange({ start: value as LiveStart })} options={liveStartOptions} disabled={!live.enabled} />
+                          <div className="flex items-center gap-2">
+                            <input
+                              type="number"
+                              value={live.maxDurationSeconds ? Math.round(live.maxDurationSeconds / 60) : 0}
+                              onChange={(e) => { const minutes = Math.max(0, parseInt(e.target.value) || 0); handleLiveChange({ maxDurationSeconds: minutes > 0 ? minutes * 60 : null }); }}
+                              min="0"
+                              step="5"
+                              disabled={!live.enabled}
+                              className="w-24 rounded-md border border-input bg-background px-3 py-2 text-sm focus:outline-none focus:ring-2 focus:ring-ring"
+                            />
+                            <span className="text-sm text-muted-foreground">{t("settings.liveMaxDuration")}</span>
+                          </div>
+                          <ToggleSwitch id="live-wait" label={t("settings.liveWaitForStream")} checked={live.waitForStream} onChange={() => handleLiveChange({ waitForStream: !live.waitForStream })} />
+                          <div className="flex items-center gap-2">
+                            <input
+                              type="number"
+                              value={live.pollIntervalSeconds}
+                              onChange={(e) => handleLiveChange({ pollIntervalSeconds: Math.max(1, parseInt(e.target.value) || 60) })}
+                              min="1"
+                              disabled={!live.enabled || !live.waitForStream}
+                              className="w-24 rounded-md border border-input bg-background px-3 py-2 text-sm focus:outline-none focus:ring-2 focus:ring-ring"
+                            />
+                            <span className="text-sm text-muted-foreground">{t("settings.livePollInterval")}</span>
+                          </div>
+                        </div>
+                      </Section>
+                      <Section title={t("settings.clipboard")} icon={<Clipboard className="h-4 w-4" />}>
+                        <div className="space-y-3">
+                          <p className="text-xs text-muted-foreground">{t("settings.clipboardDescription")}</p>
+                          <ToggleSwitch id="clipboard-enabled" label={t("settings.clipboardEnabled")} checked={clipboard.enabled} onChange={() => handleClipboardChange({ enabled: !clipboard.enabled })} />
+                          <Select value={clipboard.action} onChange={(value) => handleClipboardChange({ action: value as ClipboardAction })} options={clipboardActionOptions} disabled={!clipboard.enabled} />
+                          {clipboard.action === "enqueue" && (
+                            <div className="grid grid-cols-2 gap-2">
+                              <FormatSelector value={clipboard.format ?? preferences?.format ?? "video-mp4"} onChange={(format) => handleClipboardChange({ format })} disabled={!clipboard.enabled} />
+                              <QualitySelector value={clipboard.quality ?? preferences?.quality ?? "best"} onChange={(quality) => handleClipboardChange({ quality })} disabled={!clipboard.enabled} />
+                            </div>
+                          )}
+                          <div className="space-y-1">
+                            <label htmlFor="clipboard-domains" className="text-sm text-muted-foreground">{t("settings.clipboardDomains")}</label>
+                            <input
+                              id="clipboard-domains"
+                              type="text"
+                              value={clipboardDomains}
+                              onChange={(e) => setClipboardDomains(e.target.value)}
+                              onBlur={() => handleClipboardChange({ allowedDomains: clipboardDomains.split(/[\s,]+/).filter(Boolean) })}
+                              disabled={!clipboard.enabled}
+                              className="w-full rounded-md border border-input bg-background px-3 py-2 text-sm focus:outline-none focus:ring-2 focus:ring-ring"
+                            />
+                          </div>
+                        </div>
+                      </Section>
                     </div>
                   )}
 
@@ -436,6 +875,46 @@ export function SettingsPanel({ isOpen, onClose, preferences, onPreferencesChang
                           )}
                         </div>
                       </Section>
+                      <Section title={t("settings.api")} icon={<Plug className="h-4 w-4" />}>
+                        <div className="space-y-3">
+                          <p className="text-xs text-muted-foreground">{t("settings.apiDescription")}</p>
+                          <ToggleSwitch id="api-enabled" label={t("settings.apiEnabled")} checked={api.enabled} onChange={() => handleApiChange({ enabled: !api.enabled })} />
+                          <div className="flex items-center gap-2">
+                            <input
+                              id="api-port"
+                              type="number"
+                              value={apiPort}
+                              onChange={(e) => setApiPort(e.target.value)}
+                              onBlur={handleApiPortBlur}
+                              min="1024"
+                              max="65535"
+                              disabled={!api.enabled}
+                              className="w-28 rounded-md border border-input bg-background px-3 py-2 text-sm focus:outline-none focus:ring-2 focus:ring-ring"
+                            />
+                            <label htmlFor="api-port" className="text-sm text-muted-foreground">{t("settings.apiPort")}</label>
+                          </div>
+                          {api.enabled && apiInfo && (
+                            <div className="space-y-1">
+                              <p className="text-xs text-muted-foreground">{t("settings.apiAddress", { url: apiInfo.url })}</p>
+                              <div className="flex items-center gap-2">
+                                <input
+                                  type="password"
+                                  readOnly
+                                  value={apiInfo.token}
+                                  aria-label={t("settings.apiToken")}
+                                  className="flex-1 rounded-md border border-input bg-background px-3 py-2 font-mono text-sm focus:outline-none focus:ring-2 focus:ring-ring"
+                                />
+                                <Button variant="ghost" size="sm" onClick={handleCopyApiToken} aria-label={t("settings.apiCopyToken")} title={t("settings.apiCopyToken")}>
+                                  <Copy className="h-3 w-3" />
+                                </Button>
+                                <Button variant="ghost" size="sm" onClick={handleRegenerateApiToken} aria-label={t("settings.apiRegenerateToken")} title={t("settings.apiRegenerateToken")}>
+                                  <RefreshCw className="h-3 w-3" />
+                                </Button>
+                              </div>
+                            </div>
+                          )}
+                        </div>
+                      </Section>
                       <Section title={t("settings.resetToDefaults")} icon={<RotateCcw className="h-4 w-4" />}>
                         <Button variant="outline" onClick={handleResetToDefaults} className="w-full">
                           <RotateCcw className="h-4 w-4 mr-2" />{t("settings.resetToDefaults")}
@@ -13,7 +13,9 @@ import {
   Lock,
   Globe,
   FileQuestion,
-  WifiOff
+  WifiOff,
+  Radio,
+  SkipForward
 } from "lucide-react";
 import { useTranslation } from "react-i18next";
 import { slideUpVariants, defaultTransition, buttonVariants, springTransition } from "@/lib/animations";
@@ -90,16 +92,37 @@ const statusConfigs: Record<DownloadState, StatusConfig> = {
     message: "Downloading...",
     color: "text-primary",
   },
+  waitingForLive: {
+    icon: Clock,
+    message: "Waiting for the livestream to start...",
+    color: "text-primary",
+  },
+  recording: {
+    icon: Radio,
+    message: "Recording livestream...",
+    color: "text-destructive",
+  },
   merging: {
     icon: Merge,
     message: "Merging video and audio...",
     color: "text-warning",
   },
+  transcoding: {
+    icon: Loader2,
+    message: "Transcoding...",
+    color: "text-warning",
+    animate: true,
+  },
   completed: {
     icon: CheckCircle2,
     message: "Download complete!",
     color: "text-success",
   },
+  skipped: {
+    icon: SkipForward,
+    message: "Nothing to download",
+    color: "text-muted-foreground",
+  },
   cancelled: {
     icon: XCircle,
     message: "Download cancelled",
@@ -239,10 +262,12 @@ export function StatusDisplay({
   
   const errorInfo = error ? getErrorInfo(error) : null;
   
-  // Override message if retrying
+  // Override message if retrying; a skip reports its reason as the error
   const displayMessage = retryInfo 
     ? t("status.retrying", { attempt: retryInfo.attempt, maxRetries: retryInfo.maxRetries })
-    : config.message;
+    : state === "skipped" && error
+      ? error
+      : config.message;
   
   // Check if this is an auth error and cookies are not enabled
   const showCookieSuggestion = 
@@ -15,6 +15,10 @@ export interface Toast {
     label: string;
     onClick: () => void;
   } | undefined;
+  secondaryAction?: {
+    label: string;
+    onClick: () => void;
+  } | undefined;
 }
 
 interface ToastContextType {
@@ -126,13 +130,25 @@ function ToastItem({ toast, onRemove }: { toast: Toast; onRemove: (id: string) =
         {toast.description && (
           <p className="mt-1 text-sm text-muted-foreground">{toast.description}</p>
         )}
-        {toast.action && (
-          <button
-            onClick={toast.action.onClick}
-            className="mt-2 text-sm font-medium hover:underline"
-          >
-            {toast.action.label}
-          </button>
+        {(toast.action || toast.secondaryAction) && (
+          <div className="mt-2 flex gap-3">
+            {toast.action && (
+              <button
+                onClick={toast.action.onClick}
+                className="text-sm font-medium hover:underline"
+              >
+                {toast.action.label}
+              </button>
+            )}
+            {toast.secondaryAction && (
+              <button
+                onClick={toast.secondaryAction.onClick}
+                className="text-sm font-medium hover:underline"
+              >
+                {toast.secondaryAction.label}
+              </button>
+            )}
+          </div>
         )}
       </div>
       <button
@@ -5,6 +5,7 @@
 import { useState, useCallback, useEffect, useRef } from "react";
 import { invoke, listen } from "@/lib/tauri";
 import { validateUrl } from "@/lib/validation";
+import { liveOptionsFor } from "@/lib/live";
 import type {
   DownloadState,
   Format,
@@ -15,6 +16,7 @@ import type {
   DownloadConfig,
   RetryEvent,
   Preferences,
+  LoudnessMeasurement,
 } from "@/types";
 
 interface UseDownloadOptions {
@@ -34,7 +36,8 @@ interface UseDownloadOptions {
     fileSize: number | null,
     duration: number | null,
     status: "completed" | "failed",
-    error: string | null
+    error: string | null,
+    loudness?: LoudnessMeasurement | null
   ) => Promise<unknown>;
   /** Whether the user is currently offline */
   isOffline?: boolean;
@@ -92,6 +95,8 @@ export function useDownload({
 
   const isDownloading =
     downloadState === "downloading" ||
+    downloadState === "waitingForLive" ||
+    downloadState === "recording" ||
     downloadState === "merging" ||
     downloadState === "starting" ||
     downloadState === "analyzing";
@@ -99,6 +104,7 @@ export function useDownload({
   const isIdle =
     downloadState === "idle" ||
     downloadState === "completed" ||
+    downloadState === "skipped" ||
     downloadState === "cancelled" ||
     downloadState === "failed";
 
@@ -135,7 +141,7 @@ export function useDownload({
     );
 
     // History tracking for completed downloads
-    const unlistenHistoryComplete = listen<{ success: boolean; filePath?: string; error?: string }>(
+    const unlistenHistoryComplete = listen<{ success: boolean; filePath?: string; error?: string; loudness?: LoudnessMeasurement | null }>(
       "download-complete",
       async (event) => {
         const currentUrl = urlRef.current;
@@ -162,7 +168,8 @@ export function useDownload({
               currentMediaInfo?.filesizeApprox || null,
               currentMediaInfo?.duration || null,
               event.payload.success ? "completed" : "failed",
-              event.payload.error || null
+              event.payload.error || null,
+              event.payload.loudness ?? null
             );
           } catch (err) {
             console.error("Failed to add to history:", err);
@@ -203,7 +210,7 @@ export function useDownload({
     setRetryInfo(null);
 
     // Reset backend download state if in terminal state (completed, failed, cancelled)
-    if (downloadState === "completed" || downloadState === "failed" || downloadState === "cancelled") {
+    if (downloadState === "completed" || downloadState === "skipped" || downloadState === "failed" || downloadState === "cancelled") {
       try {
         await invoke("reset_download");
       } catch (err) {
@@ -259,6 +266,10 @@ export function useDownload({
       filenameTemplate: preferences?.filenameTemplate ?? null,
       proxyUrl: preferences?.proxyEnabled ? preferences?.proxyUrl : null,
       cookiesFilePath: preferences?.cookiesFilePath ?? null,
+      organizeRules: preferences?.organizeRules ?? [],
+      transcodePreset: preferences?.transcodePreset ?? null,
+      loudnessTargetLufs: preferences?.normalizeLoudness ? preferences.loudnessTargetLufs : null,
+      live: liveOptionsFor(preferences, mediaInfo),
     };
 
     try {
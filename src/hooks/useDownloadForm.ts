@@ -43,6 +43,8 @@ export function useDownloadForm(): UseDownloadFormReturn {
     const [url, setUrl] = useState("");
     const [format, setFormat] = useState<Format>("video-mp4");
     const [quality, setQuality] = useState<Quality>("best");
+    // Folder picked for this session; empty lets the backend pick the
+    // default folder for the format
     const [outputFolder, setOutputFolder] = useState("");
     const [folderError, setFolderError] = useState<string | null>(null);
 
@@ -52,7 +54,6 @@ export function useDownloadForm(): UseDownloadFormReturn {
     // Apply preferences to form state on load
     useEffect(() => {
         if (preferences && !preferencesAppliedRef.current) {
-            if (preferences.outputFolder) setOutputFolder(preferences.outputFolder);
             if (preferences.format) setFormat(preferences.format);
             if (preferences.quality) setQuality(preferences.quality);
             preferencesAppliedRef.current = true;
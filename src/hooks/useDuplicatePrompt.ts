@@ -0,0 +1,49 @@
+import { useCallback } from 'react';
+import { useTranslation } from 'react-i18next';
+import { invoke } from '@/lib/tauri';
+import { useToast } from '@/components/Toast';
+import { useQueueStore } from '@/stores/queueStore';
+import type { DownloadConfig, QueueDuplicate } from '@/types';
+
+/**
+ * Hook for telling the user that a URL wasn't queued because it's a duplicate
+ *
+ * Shows a toast offering to queue it anyway or, if the earlier download is
+ * still on disk, to open that file. Dismissing the toast skips the URL.
+ */
+export function useDuplicatePrompt() {
+  const { t } = useTranslation();
+  const { addToast, removeToast, success } = useToast();
+  const addToQueue = useQueueStore((state) => state.addToQueue);
+
+  return useCallback((duplicate: QueueDuplicate, config: DownloadConfig) => {
+    const filePath = duplicate.filePath;
+    const toastId = addToast({
+      type: 'warning',
+      title: t(duplicate.source === 'queue' ? 'duplicate.inQueue' : 'duplicate.inHistory'),
+      description: duplicate.title ?? duplicate.url,
+      duration: 10000,
+      action: {
+        label: t('duplicate.addAnyway'),
+        onClick: async () => {
+          removeToast(toastId);
+          try {
+            await addToQueue(config, true);
+            success(t('toast.addedToQueue'));
+          } catch (err) {
+            console.error('Failed to add duplicate to queue:', err);
+          }
+        },
+      },
+      secondaryAction: filePath
+        ? {
+            label: t('duplicate.openFile'),
+            onClick: () => {
+              removeToast(toastId);
+              invoke('open_file', { path: filePath }).catch((err) => console.error('Failed to open file:', err));
+            },
+          }
+        : undefined,
+    });
+  }, [addToQueue, addToast, removeToast, success, t]);
+}
@@ -4,9 +4,9 @@
  */
 
 import { useState, useCallback, useEffect, useRef } from "react";
-import { invoke } from "@/lib/tauri";
+import { invoke, listen } from "@/lib/tauri";
 import { validateUrl } from "@/lib/validation";
-import type { MediaInfo, PlaylistInfo } from "@/types";
+import type { MediaInfo, PlaylistInfo, PlaylistProgress } from "@/types";
 
 // LRU Cache for media info
 const CACHE_MAX_SIZE = 50;
@@ -79,6 +79,7 @@ interface UseMediaInfoReturn {
   isPlaylist: boolean;
   playlistInfo: PlaylistInfo | null;
   isLoadingPlaylist: boolean;
+  playlistProgress: PlaylistProgress | null;
   fetchMediaInfo: (url: string) => Promise<void>;
   handleUrlChange: (newUrl: string, setUrl: (url: string) => void) => void;
   handlePaste: (setUrl: (url: string) => void) => Promise<void>;
@@ -92,8 +93,11 @@ export function useMediaInfo(): UseMediaInfoReturn {
   const [isPlaylist, setIsPlaylist] = useState(false);
   const [playlistInfo, setPlaylistInfo] = useState<PlaylistInfo | null>(null);
   const [isLoadingPlaylist, setIsLoadingPlaylist] = useState(false);
+  const [playlistProgress, setPlaylistProgress] = useState<PlaylistProgress | null>(null);
 
   const debounceTimeoutRef = useRef<ReturnType<typeof setTimeout> | null>(null);
+  // URL of the playlist currently being listed
+  const playlistUrlRef = useRef<string | null>(null);
 
   // Cleanup debounce timeout on unmount
   useEffect(() => {
@@ -104,6 +108,29 @@ export function useMediaInfo(): UseMediaInfoReturn {
     };
   }, []);
 
+  // Follow the listing of the current playlist
+  useEffect(() => {
+    const unlisten = listen<PlaylistProgress>("playlist-progress", (event) => {
+      if (event.payload.url === playlistUrlRef.current) {
+        setPlaylistProgress(event.payload);
+      }
+    });
+    return () => {
+      unlisten.then((fn) => fn());
+    };
+  }, []);
+
+  /** Stops listing a playlist the user has moved away from */
+  const cancelPlaylistFetch = useCallback(() => {
+    const url = playlistUrlRef.current;
+    playlistUrlRef.current = null;
+    setIsLoadingPlaylist(false);
+    setPlaylistProgress(null);
+    if (url) {
+      invoke("cancel_playlist_fetch", { url }).catch(() => {});
+    }
+  }, []);
+
   const fetchMediaInfo = useCallback(async (urlToFetch: string) => {
     const validation = validateUrl(urlToFetch);
     if (!validation.isValid) {
@@ -138,15 +165,26 @@ export function useMediaInfo(): UseMediaInfoReturn {
       const isPlaylistUrl = await invoke<boolean>("check_is_playlist", { url: urlToCheck });
       setIsPlaylist(isPlaylistUrl);
       if (isPlaylistUrl) {
+        playlistUrlRef.current = urlToCheck;
+        setPlaylistProgress(null);
         setIsLoadingPlaylist(true);
         try {
           const info = await invoke<PlaylistInfo>("fetch_playlist_info", { url: urlToCheck });
-          setPlaylistInfo(info);
+          // Ignore listings that were cancelled or replaced meanwhile
+          if (playlistUrlRef.current === urlToCheck) {
+            setPlaylistInfo(info);
+          }
         } catch (err) {
-          console.error("Failed to fetch playlist info:", err);
-          setPlaylistInfo(null);
+          if (playlistUrlRef.current === urlToCheck) {
+            console.error("Failed to fetch playlist info:", err);
+            setPlaylistInfo(null);
+          }
         } finally {
-          setIsLoadingPlaylist(false);
+          if (playlistUrlRef.current === urlToCheck) {
+            playlistUrlRef.current = null;
+            setIsLoadingPlaylist(false);
+            setPlaylistProgress(null);
+          }
         }
       } else {
         setPlaylistInfo(null);
@@ -164,6 +202,7 @@ export function useMediaInfo(): UseMediaInfoReturn {
       setMediaInfo(null);
       setIsPlaylist(false);
       setPlaylistInfo(null);
+      cancelPlaylistFetch();
 
       // Clear any existing debounce timeout
       if (debounceTimeoutRef.current) {
@@ -181,7 +220,7 @@ export function useMediaInfo(): UseMediaInfoReturn {
         }, 500);
       }
     },
-    [fetchMediaInfo, checkPlaylist]
+    [fetchMediaInfo, checkPlaylist, cancelPlaylistFetch]
   );
 
   const handlePaste = useCallback(
@@ -207,7 +246,8 @@ export function useMediaInfo(): UseMediaInfoReturn {
     setMediaInfo(null);
     setIsPlaylist(false);
     setPlaylistInfo(null);
-  }, []);
+    cancelPlaylistFetch();
+  }, [cancelPlaylistFetch]);
 
   const clearCache = useCallback(() => {
     mediaInfoCache.clear();
@@ -219,6 +259,7 @@ export function useMediaInfo(): UseMediaInfoReturn {
     isPlaylist,
     playlistInfo,
     isLoadingPlaylist,
+    playlistProgress,
     fetchMediaInfo,
     handleUrlChange,
     handlePaste,
@@ -17,6 +17,8 @@ export function useQueue() {
     items: store.items,
     isLoading: store.isLoading,
     addToQueue: store.addToQueue,
+    addPlaylistToQueue: store.addPlaylistToQueue,
+    addBatchToQueue: store.addBatchToQueue,
     cancelItem: store.cancelItem,
     removeItem: store.removeItem,
     clearCompleted: store.clearCompleted,
@@ -1,5 +1,10 @@
 /**
  * useSchedule hook - Manages scheduled downloads
+ *
+ * Scheduled downloads are queued right away with a start time (and optional
+ * deadline); the backend queue holds them back until they're due. The list
+ * is also kept in preferences so it survives a restart, when the entries are
+ * queued again.
  */
 
 import { useState, useEffect, useCallback, useRef } from "react";
@@ -20,10 +25,12 @@ interface UseScheduleReturn {
     toggleScheduledDownload: (id: string) => void;
 }
 
+const toSeconds = (ms: number) => Math.floor(ms / 1000);
+
 export function useSchedule({ outputFolder }: UseScheduleOptions): UseScheduleReturn {
     const { t } = useTranslation();
     const { success, info } = useToast();
-    const { addToQueue } = useQueue();
+    const { items, addToQueue, cancelItem, removeItem } = useQueue();
     const { preferences, setPreferences } = usePreferences();
 
     const [scheduledDownloads, setScheduledDownloads] = useState<ScheduledDownload[]>([]);
@@ -31,45 +38,6 @@ export function useSchedule({ outputFolder }: UseScheduleOptions): UseScheduleRe
     // Track if we've loaded from preferences
     const loadedFromPrefs = useRef(false);
 
-    // Load scheduled downloads from preferences
-    useEffect(() => {
-        if (preferences?.scheduledDownloads && !loadedFromPrefs.current) {
-            setScheduledDownloads(preferences.scheduledDownloads);
-            loadedFromPrefs.current = true;
-        }
-    }, [preferences?.scheduledDownloads]);
-
-    // Check scheduled downloads every minute
-    useEffect(() => {
-        const checkScheduled = () => {
-            const now = Date.now();
-            scheduledDownloads.forEach(async (download) => {
-                if (download.enabled && download.scheduledTime <= now) {
-                    // Time to start this download
-                    const config: DownloadConfig = {
-                        url: download.url,
-                        format: download.format,
-                        quality: download.quality,
-                        outputFolder: outputFolder || "",
-                        embedSubtitles: preferences?.embedSubtitles ?? false,
-                        cookiesFromBrowser: preferences?.cookiesFromBrowser ?? null,
-                        filenameTemplate: preferences?.filenameTemplate ?? null,
-                        proxyUrl: preferences?.proxyEnabled ? preferences?.proxyUrl : null,
-                        cookiesFilePath: preferences?.cookiesFilePath ?? null,
-                    };
-                    await addToQueue(config);
-                    // Remove from scheduled
-                    removeScheduledDownload(download.id);
-                    info(t("schedule.started", "Scheduled download started"));
-                }
-            });
-        };
-
-        const interval = setInterval(checkScheduled, 60000); // Check every minute
-        checkScheduled(); // Check immediately on mount
-        return () => clearInterval(interval);
-    }, [scheduledDownloads, outputFolder, preferences, addToQueue, info, t]);
-
     // Persist to preferences whenever scheduledDownloads changes
     const persistToPreferences = useCallback((downloads: ScheduledDownload[]) => {
         if (preferences) {
@@ -77,30 +45,125 @@ export function useSchedule({ outputFolder }: UseScheduleOptions): UseScheduleRe
         }
     }, [preferences, setPreferences]);
 
-    const addScheduledDownload = useCallback((download: Omit<ScheduledDownload, "id">) => {
+    const buildConfig = useCallback((download: ScheduledDownload): DownloadConfig => ({
+        url: download.url,
+        format: download.format,
+        quality: download.quality,
+        outputFolder: outputFolder || "",
+        embedSubtitles: preferences?.embedSubtitles ?? false,
+        cookiesFromBrowser: preferences?.cookiesFromBrowser ?? null,
+        filenameTemplate: preferences?.filenameTemplate ?? null,
+        proxyUrl: preferences?.proxyEnabled ? preferences?.proxyUrl : null,
+        cookiesFilePath: preferences?.cookiesFilePath ?? null,
+        organizeRules: preferences?.organizeRules ?? [],
+        transcodePreset: preferences?.transcodePreset ?? null,
+        loudnessTargetLufs: preferences?.normalizeLoudness ? preferences.loudnessTargetLufs : null,
+    }), [outputFolder, preferences]);
+
+    /** Hands a scheduled download to the queue; returns the queue item id */
+    const enqueue = useCallback(async (download: ScheduledDownload): Promise<number | null> => {
+        // Scheduled explicitly, so queue it even if it was downloaded before
+        const result = await addToQueue(buildConfig(download), true, {
+            notBefore: toSeconds(download.scheduledTime),
+            deadline: download.deadline ? toSeconds(download.deadline) : null,
+        });
+        return result.type === "added" ? result.item.id : null;
+    }, [addToQueue, buildConfig]);
+
+    // Load scheduled downloads from preferences and queue the enabled ones.
+    // The queue doesn't outlive the app, so ids from an earlier run are stale.
+    useEffect(() => {
+        if (!preferences?.scheduledDownloads || loadedFromPrefs.current) return;
+        loadedFromPrefs.current = true;
+
+        const saved = preferences.scheduledDownloads;
+        Promise.all(saved.map(async (download) => ({
+            ...download,
+            queueItemId: download.enabled ? await enqueue(download).catch(() => null) : null,
+        }))).then((queued) => {
+            setScheduledDownloads(queued);
+            persistToPreferences(queued);
+        });
+    }, [preferences?.scheduledDownloads, enqueue, persistToPreferences]);
+
+    // Drop entries once their queue item has left the scheduled state
+    useEffect(() => {
+        const finished = scheduledDownloads.filter((download) => {
+            if (download.queueItemId == null) return false;
+            const item = items.find((i) => i.id === download.queueItemId);
+            return !item || item.status !== "scheduled";
+        });
+        if (finished.length === 0) return;
+
+        const started = finished.filter((download) =>
+            items.some((i) => i.id === download.queueItemId && i.status !== "failed" && i.status !== "cancelled")
+        );
+        if (started.length > 0) {
+            info(t("schedule.started", "Scheduled download started"));
+        }
+
+        const updated = scheduledDownloads.filter((d) => !finished.includes(d));
+        setScheduledDownloads(updated);
+        persistToPreferences(updated);
+    }, [items, scheduledDownloads, persistToPreferences, info, t]);
+
+    /** Takes a download out of the queue without touching the schedule list */
+    const dequeue = useCallback(async (queueItemId: number | null | undefined) => {
+        if (queueItemId == null) return;
+        try {
+            await cancelItem(queueItemId);
+            await removeItem(queueItemId);
+        } catch (err) {
+            console.error("Failed to remove scheduled item from queue:", err);
+        }
+    }, [cancelItem, removeItem]);
+
+    const addScheduledDownload = useCallback(async (download: Omit<ScheduledDownload, "id">) => {
         const newDownload: ScheduledDownload = {
             ...download,
             id: crypto.randomUUID(),
+            queueItemId: null,
         };
-        const updated = [...scheduledDownloads, newDownload];
-        setScheduledDownloads(updated);
-        persistToPreferences(updated);
+        try {
+            newDownload.queueItemId = download.enabled ? await enqueue(newDownload) : null;
+        } catch (err) {
+            console.error("Failed to schedule download:", err);
+            return;
+        }
+        setScheduledDownloads((current) => {
+            const updated = [...current, newDownload];
+            persistToPreferences(updated);
+            return updated;
+        });
         success(t("schedule.added", "Download scheduled"));
-    }, [scheduledDownloads, persistToPreferences, success, t]);
+    }, [enqueue, persistToPreferences, success, t]);
 
     const removeScheduledDownload = useCallback((id: string) => {
+        const download = scheduledDownloads.find(d => d.id === id);
         const updated = scheduledDownloads.filter(d => d.id !== id);
         setScheduledDownloads(updated);
         persistToPreferences(updated);
-    }, [scheduledDownloads, persistToPreferences]);
+        void dequeue(download?.queueItemId);
+    }, [scheduledDownloads, persistToPreferences, dequeue]);
 
-    const toggleScheduledDownload = useCallback((id: string) => {
-        const updated = scheduledDownloads.map(d =>
-            d.id === id ? { ...d, enabled: !d.enabled } : d
-        );
-        setScheduledDownloads(updated);
-        persistToPreferences(updated);
-    }, [scheduledDownloads, persistToPreferences]);
+    const toggleScheduledDownload = useCallback(async (id: string) => {
+        const download = scheduledDownloads.find(d => d.id === id);
+        if (!download) return;
+
+        // Pausing forgets the queue item first so its cancellation isn't
+        // mistaken for the download having run
+        const queueItemId = download.enabled ? null : await enqueue(download).catch(() => null);
+        setScheduledDownloads((current) => {
+            const updated = current.map(d =>
+                d.id === id ? { ...d, enabled: !d.enabled, queueItemId } : d
+            );
+            persistToPreferences(updated);
+            return updated;
+        });
+        if (download.enabled) {
+            await dequeue(download.queueItemId);
+        }
+    }, [scheduledDownloads, enqueue, dequeue, persistToPreferences]);
 
     return {
         scheduledDownloads,
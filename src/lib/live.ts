@@ -0,0 +1,29 @@
+/**
+ * Livestream helpers
+ */
+
+import type { LiveOptions, MediaInfo, Preferences } from '@/types';
+
+// yt-dlp live_status values that need live mode
+const LIVE_STATUSES = ['is_live', 'is_upcoming'];
+
+/**
+ * Whether the media is a running or scheduled livestream
+ */
+export function isLiveMedia(mediaInfo: MediaInfo | null | undefined): boolean {
+  return !!mediaInfo?.liveStatus && LIVE_STATUSES.includes(mediaInfo.liveStatus);
+}
+
+/**
+ * Live recording options for a download, from the user's live settings
+ *
+ * Only livestreams get them: a duration limit would otherwise cut regular
+ * videos short.
+ */
+export function liveOptionsFor(
+  preferences: Preferences | null | undefined,
+  mediaInfo: MediaInfo | null | undefined
+): LiveOptions | undefined {
+  if (!preferences?.live?.enabled || !isLiveMedia(mediaInfo)) return undefined;
+  return preferences.live;
+}
@@ -0,0 +1,39 @@
+/**
+ * Tests for the playlist filter preview (kept in line with the backend)
+ */
+
+import { describe, it, expect } from 'vitest';
+import { isPlaylistFilterActive, matchesPlaylistFilter, normalizeFilterDate } from './playlistFilter';
+import type { PlaylistEntry } from '@/types';
+
+const entry = (title: string, duration: number | null, uploadDate: string | null): PlaylistEntry => ({
+  id: 'id',
+  title,
+  url: 'https://example.com/watch',
+  thumbnail: null,
+  duration,
+  playlistIndex: 1,
+  uploader: null,
+  uploadDate,
+});
+
+describe('Playlist filter', () => {
+  const filter = { dateAfter: '2025-01-01', minDuration: 300, titleExcludes: ['#shorts'] };
+
+  it('should drop entries by date, duration and title', () => {
+    expect(matchesPlaylistFilter(entry('Long video', 600, '20250102'), filter)).toBe(true);
+    expect(matchesPlaylistFilter(entry('Old video', 600, '20241231'), filter)).toBe(false);
+    expect(matchesPlaylistFilter(entry('Short', 59, '20250102'), filter)).toBe(false);
+    expect(matchesPlaylistFilter(entry('Clip #Shorts', 600, '20250102'), filter)).toBe(false);
+  });
+
+  it('should let entries without a date or duration through', () => {
+    expect(matchesPlaylistFilter(entry('Unknown', null, null), filter)).toBe(true);
+  });
+
+  it('should only count usable values as active', () => {
+    expect(isPlaylistFilterActive({ dateAfter: 'soon', titleIncludes: [' '] })).toBe(false);
+    expect(isPlaylistFilterActive(filter)).toBe(true);
+    expect(normalizeFilterDate('2025-01-01')).toBe('20250101');
+  });
+});
@@ -0,0 +1,65 @@
+/**
+ * Playlist filter helpers
+ *
+ * Mirrors `PlaylistFilter::matches` in the backend, so the playlist preview
+ * shows the entries that will be queued.
+ */
+
+import type { PlaylistEntry, PlaylistFilter } from '@/types';
+
+/**
+ * YYYYMMDD for a YYYYMMDD or YYYY-MM-DD date, null if it isn't one
+ */
+export function normalizeFilterDate(date: string | null | undefined): string | null {
+  const match = date?.trim().match(/^(\d{4})-?(\d{2})-?(\d{2})$/);
+  return match ? `${match[1]}${match[2]}${match[3]}` : null;
+}
+
+const words = (list: string[] | undefined) =>
+  (list ?? []).map((word) => word.trim().toLowerCase()).filter(Boolean);
+
+/**
+ * Whether a filter would drop anything
+ */
+export function isPlaylistFilterActive(filter: PlaylistFilter | undefined): boolean {
+  if (!filter) return false;
+  return !!(
+    normalizeFilterDate(filter.dateAfter) ||
+    normalizeFilterDate(filter.dateBefore) ||
+    filter.minDuration != null ||
+    filter.maxDuration != null ||
+    words(filter.titleIncludes).length ||
+    words(filter.titleExcludes).length
+  );
+}
+
+/**
+ * Whether an entry passes the filter; missing dates and durations pass
+ */
+export function matchesPlaylistFilter(entry: PlaylistEntry, filter: PlaylistFilter | undefined): boolean {
+  if (!filter) return true;
+
+  const after = normalizeFilterDate(filter.dateAfter);
+  const before = normalizeFilterDate(filter.dateBefore);
+  if (entry.uploadDate && ((after && entry.uploadDate < after) || (before && entry.uploadDate > before))) {
+    return false;
+  }
+
+  if (entry.duration != null) {
+    if (filter.minDuration != null && entry.duration < filter.minDuration) return false;
+    if (filter.maxDuration != null && entry.duration > filter.maxDuration) return false;
+  }
+
+  const title = entry.title.toLowerCase();
+  const includes = words(filter.titleIncludes);
+  const excludes = words(filter.titleExcludes);
+  return (includes.length === 0 || includes.some((word) => title.includes(word)))
+    && !excludes.some((word) => title.includes(word));
+}
+
+/**
+ * Splits a comma-separated input into filter words
+ */
+export function parseFilterWords(input: string): string[] {
+  return input.split(',').map((word) => word.trim()).filter(Boolean);
+}
@@ -6,7 +6,7 @@
 
 import { describe, it, expect } from 'vitest';
 import * as fc from 'fast-check';
-import type { Preferences, Format, Quality } from '../types';
+import type { Preferences, Format, Quality, OrganizeRule, TranscodePreset } from '../types';
 
 // Valid format options
 const VALID_FORMATS: Format[] = ['video-mp4', 'audio-mp3', 'audio-best'];
@@ -30,6 +30,32 @@ const preferencesArbitrary = fc.record({
   proxyUrl: fc.option(fc.string(), { nil: null }),
   filenameTemplate: fc.option(fc.string(), { nil: null }),
   cookiesFilePath: fc.option(fc.string(), { nil: null }),
+  organizeRules: fc.subarray<OrganizeRule>(['site', 'uploader', 'playlist', 'mediaType', 'year']),
+  audioOutputFolder: fc.option(fc.string(), { nil: null }),
+  videoOutputFolder: fc.option(fc.string(), { nil: null }),
+  formatOutputFolders: fc.dictionary(fc.constantFrom(...VALID_FORMATS), fc.string()),
+  transcodePreset: fc.option(fc.constantFrom<TranscodePreset>('phone720p', 'editingIntra', 'voiceMemo'), { nil: null }),
+  normalizeLoudness: fc.boolean(),
+  loudnessTargetLufs: fc.integer({ min: -70, max: -5 }),
+  useDownloadArchive: fc.boolean(),
+  live: fc.record({
+    enabled: fc.boolean(),
+    start: fc.constantFrom('fromNow' as const, 'fromStart' as const),
+    maxDurationSeconds: fc.option(fc.integer({ min: 1, max: 86400 }), { nil: null }),
+    waitForStream: fc.boolean(),
+    pollIntervalSeconds: fc.integer({ min: 1, max: 3600 }),
+  }),
+  clipboard: fc.record({
+    enabled: fc.boolean(),
+    action: fc.constantFrom('notify' as const, 'enqueue' as const),
+    format: fc.option(fc.constantFrom('video-mp4' as const, 'audio-mp3' as const), { nil: null }),
+    quality: fc.option(fc.constantFrom('best' as const, '720p' as const), { nil: null }),
+    allowedDomains: fc.array(fc.domain(), { maxLength: 5 }),
+  }),
+  api: fc.record({
+    enabled: fc.boolean(),
+    port: fc.integer({ min: 1024, max: 65535 }),
+  }),
   bandwidthLimit: fc.option(fc.integer({ min: 0, max: 100000 }), { nil: null }),
   scheduledDownloads: fc.constant(null),
 });
@@ -89,6 +115,17 @@ describe('Preferences Round-Trip - Property Tests', () => {
             proxyUrl: null,
             filenameTemplate: null,
             cookiesFilePath: null,
+            organizeRules: [],
+            audioOutputFolder: null,
+            videoOutputFolder: null,
+            formatOutputFolders: {},
+            transcodePreset: null,
+            normalizeLoudness: false,
+            loudnessTargetLufs: -16,
+            useDownloadArchive: true,
+            live: { enabled: false, start: 'fromNow', maxDurationSeconds: null, waitForStream: false, pollIntervalSeconds: 60 },
+            clipboard: { enabled: false, action: 'notify', format: null, quality: null, allowedDomains: ['youtube.com'] },
+            api: { enabled: false, port: 47821 },
             bandwidthLimit: null,
             scheduledDownloads: null,
           };
@@ -120,6 +157,17 @@ describe('Preferences Round-Trip - Property Tests', () => {
             proxyUrl: null,
             filenameTemplate: null,
             cookiesFilePath: null,
+            organizeRules: [],
+            audioOutputFolder: null,
+            videoOutputFolder: null,
+            formatOutputFolders: {},
+            transcodePreset: null,
+            normalizeLoudness: false,
+            loudnessTargetLufs: -16,
+            useDownloadArchive: true,
+            live: { enabled: false, start: 'fromNow', maxDurationSeconds: null, waitForStream: false, pollIntervalSeconds: 60 },
+            clipboard: { enabled: false, action: 'notify', format: null, quality: null, allowedDomains: ['youtube.com'] },
+            api: { enabled: false, port: 47821 },
             bandwidthLimit: null,
             scheduledDownloads: null,
           };
@@ -151,6 +199,17 @@ describe('Preferences Round-Trip - Property Tests', () => {
             proxyUrl: null,
             filenameTemplate: null,
             cookiesFilePath: null,
+            organizeRules: [],
+            audioOutputFolder: null,
+            videoOutputFolder: null,
+            formatOutputFolders: {},
+            transcodePreset: null,
+            normalizeLoudness: false,
+            loudnessTargetLufs: -16,
+            useDownloadArchive: true,
+            live: { enabled: false, start: 'fromNow', maxDurationSeconds: null, waitForStream: false, pollIntervalSeconds: 60 },
+            clipboard: { enabled: false, action: 'notify', format: null, quality: null, allowedDomains: ['youtube.com'] },
+            api: { enabled: false, port: 47821 },
             bandwidthLimit: null,
             scheduledDownloads: null,
           };
@@ -178,6 +237,15 @@ describe('Preferences Round-Trip - Property Tests', () => {
       proxyUrl: null,
       filenameTemplate: null,
       cookiesFilePath: null,
+      organizeRules: [],
+      audioOutputFolder: null,
+      videoOutputFolder: null,
+      formatOutputFolders: {},
+      transcodePreset: null,
+      normalizeLoudness: false,
+      loudnessTargetLufs: -16,
+      useDownloadArchive: true,
+      live: { enabled: false, start: 'fromNow', maxDurationSeconds: null, waitForStream: false, pollIntervalSeconds: 60 },
       bandwidthLimit: null,
       scheduledDownloads: null,
     };
@@ -204,6 +272,15 @@ describe('Preferences Round-Trip - Property Tests', () => {
         proxyUrl: null,
         filenameTemplate: null,
         cookiesFilePath: null,
+        organizeRules: [],
+        audioOutputFolder: null,
+        videoOutputFolder: null,
+        formatOutputFolders: {},
+        transcodePreset: null,
+        normalizeLoudness: false,
+        loudnessTargetLufs: -16,
+        useDownloadArchive: true,
+        live: { enabled: false, start: 'fromNow', maxDurationSeconds: null, waitForStream: false, pollIntervalSeconds: 60 },
         bandwidthLimit: null,
         scheduledDownloads: null,
       };
@@ -37,12 +37,27 @@ const mockPreferences = {
   proxyUrl: null,
   filenameTemplate: null,
   cookiesFilePath: null,
+  organizeRules: [],
+  audioOutputFolder: null,
+  videoOutputFolder: null,
+  formatOutputFolders: {},
+  transcodePreset: null,
+  normalizeLoudness: false,
+  loudnessTargetLufs: -16,
+  useDownloadArchive: false,
+  live: { enabled: false, start: 'fromNow', maxDurationSeconds: null, waitForStream: false, pollIntervalSeconds: 60 },
+  clipboard: { enabled: false, action: 'notify', format: null, quality: null, allowedDomains: ['youtube.com', 'youtu.be', 'vimeo.com', 'dailymotion.com', 'twitch.tv', 'soundcloud.com', 'bandcamp.com', 'bilibili.com', 'nicovideo.jp', 'tiktok.com'] },
+  api: { enabled: false, port: 47821 },
   bandwidthLimit: null,
   scheduledDownloads: null,
 };
 
 const mockQueue: any[] = [];
 const mockHistory: any[] = [];
+let mockArchive: { extractor: string; id: string }[] = [
+  { extractor: 'youtube', id: 'dQw4w9WgXcQ' },
+];
+let mockSubscriptions: any[] = [];
 let mockQueueId = 1;
 
 // Mock invoke function
@@ -61,21 +76,108 @@ export async function mockInvoke(cmd: string, args?: any): Promise<any> {
       return mockQueue;
     
     case 'queue_add':
+      const existing = mockQueue.find(i => i.config?.url === args?.config?.url && i.status !== 'completed');
+      if (existing && !args?.force) {
+        return {
+          type: 'duplicate',
+          duplicate: {
+            source: 'queue',
+            matchedBy: 'url',
+            url: existing.config.url,
+            title: existing.title,
+            queueItemId: existing.id,
+            historyId: null,
+            filePath: null,
+            downloadedAt: null,
+          },
+        };
+      }
       const newItem = {
         id: mockQueueId++,
         config: args?.config,
-        status: 'pending',
+        status: args?.schedule?.notBefore && args.schedule.notBefore * 1000 > Date.now() ? 'scheduled' : 'pending',
         progress: 0,
         speed: '--',
         etaSeconds: null,
+        elapsedSeconds: null,
         error: null,
+        skipReason: null,
         filePath: null,
         title: args?.config?.url || 'Mock Video',
         thumbnail: null,
+        schedule: args?.schedule ?? null,
+        groupId: null,
       };
       mockQueue.push(newItem);
-      return newItem;
+      return { type: 'added', item: newItem };
     
+    case 'queue_add_playlist': {
+      const groupId = `playlist-${Date.now()}`;
+      const indices: number[] = args?.selection?.indices ?? [];
+      const entries = (args?.playlist?.entries ?? []).filter((e: any) => indices.length === 0 || indices.includes(e.playlistIndex));
+      const items = entries.map((entry: any) => {
+        const item = {
+          id: mockQueueId++,
+          config: { ...args?.config, url: entry.url },
+          status: 'pending',
+          progress: 0,
+          speed: '--',
+          etaSeconds: null,
+          elapsedSeconds: null,
+          error: null,
+          skipReason: null,
+          filePath: null,
+          title: entry.title,
+          thumbnail: entry.thumbnail,
+          schedule: null,
+          groupId,
+        };
+        mockQueue.push(item);
+        return item;
+      });
+      return { groupId, items, duplicates: [] };
+    }
+
+    case 'batch_parse': {
+      const entries: any[] = [];
+      const issues: any[] = [];
+      String(args?.text ?? '').split('\n').forEach((text, i) => {
+        const url = text.match(/https?:\/\/\S+/)?.[0];
+        if (!url) return;
+        const first = entries.find((e) => e.url === url);
+        if (first) issues.push({ line: i + 1, text: url, error: `same link as line ${first.line}` });
+        else entries.push({ line: i + 1, url, overrides: {} });
+      });
+      return { entries, issues };
+    }
+
+    case 'pick_batch_file':
+      return null;
+
+    case 'queue_add_batch': {
+      const items = (args?.entries ?? []).map((entry: any) => {
+        const item = {
+          id: mockQueueId++,
+          config: { ...args?.config, ...entry.overrides, url: entry.url },
+          status: 'pending',
+          progress: 0,
+          speed: '--',
+          etaSeconds: null,
+          elapsedSeconds: null,
+          error: null,
+          skipReason: null,
+          filePath: null,
+          title: entry.url,
+          thumbnail: null,
+          schedule: null,
+          groupId: null,
+        };
+        mockQueue.push(item);
+        return item;
+      });
+      return { items, duplicates: [] };
+    }
+
     case 'queue_remove':
       const removeIdx = mockQueue.findIndex(i => i.id === args?.id);
       if (removeIdx >= 0) mockQueue.splice(removeIdx, 1);
@@ -88,7 +190,7 @@ export async function mockInvoke(cmd: string, args?: any): Promise<any> {
     
     case 'queue_clear_completed':
       const toRemove = mockQueue.filter(i => 
-        i.status === 'completed' || i.status === 'failed' || i.status === 'cancelled'
+        i.status === 'completed' || i.status === 'skipped' || i.status === 'failed' || i.status === 'cancelled'
       );
       toRemove.forEach(item => {
         const idx = mockQueue.indexOf(item);
@@ -121,6 +223,54 @@ export async function mockInvoke(cmd: string, args?: any): Promise<any> {
       mockHistory.length = 0;
       return null;
     
+    case 'subscription_list':
+      return mockSubscriptions;
+
+    case 'subscription_add': {
+      const subscription = {
+        id: `sub-${Date.now()}`,
+        url: args?.url,
+        title: 'Mock Channel',
+        preset: args?.preset,
+        intervalMinutes: args?.intervalMinutes ?? 60,
+        enabled: true,
+        createdAt: Math.floor(Date.now() / 1000),
+        lastSyncedAt: null,
+        lastError: null,
+        ignoredUrls: [],
+      };
+      mockSubscriptions.push(subscription);
+      return subscription;
+    }
+
+    case 'subscription_remove':
+      mockSubscriptions = mockSubscriptions.filter(s => s.id !== args?.id);
+      return null;
+
+    case 'subscription_sync_now':
+      return mockSubscriptions
+        .filter(s => !args?.id || s.id === args.id)
+        .map(s => ({ subscriptionId: s.id, title: s.title, queued: 0, error: null }));
+
+    case 'archive_list':
+      return mockArchive;
+    
+    case 'archive_prune':
+      const before = mockArchive.length;
+      const pruned = (args?.entries ?? []) as { extractor: string; id: string }[];
+      mockArchive = mockArchive.filter(e => !pruned.some(p => p.extractor === e.extractor && p.id === e.id));
+      return before - mockArchive.length;
+    
+    case 'archive_import':
+      return 0;
+    
+    case 'archive_export':
+      return mockArchive.length;
+
+    case 'api_get_info':
+    case 'api_regenerate_token':
+      return { url: `http://127.0.0.1:${mockPreferences.api.port}/api`, token: 'mock-api-token' };
+    
     case 'pick_folder':
       return 'C:\\Downloads\\MediaGrab';
     
@@ -177,6 +327,9 @@ export async function mockInvoke(cmd: string, args?: any): Promise<any> {
     
     case 'cancel_download':
       return null;
+
+    case 'cancel_playlist_fetch':
+      return false;
     
     default:
       console.warn(`[Mock] Unhandled command: ${cmd}`);
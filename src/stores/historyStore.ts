@@ -1,7 +1,7 @@
 import { create } from 'zustand';
 import { persist, createJSONStorage } from 'zustand/middleware';
 import { invoke } from '@/lib/tauri';
-import type { HistoryItem, DownloadStats, DownloadConfig } from '@/types';
+import type { HistoryItem, DownloadStats, DownloadConfig, LoudnessMeasurement } from '@/types';
 
 interface HistoryState {
   items: HistoryItem[];
@@ -19,7 +19,8 @@ interface HistoryState {
     fileSize: number | null,
     duration: number | null,
     status: 'completed' | 'failed',
-    error: string | null
+    error: string | null,
+    loudness?: LoudnessMeasurement | null
   ) => Promise<HistoryItem>;
   removeItem: (id: string) => Promise<void>;
   clearHistory: () => Promise<void>;
@@ -75,7 +76,8 @@ export const useHistoryStore = create<HistoryState>()(
         fileSize,
         duration,
         status,
-        error
+        error,
+        loudness
       ) => {
         const item: HistoryItem = {
           id: `${Date.now()}-${Math.random().toString(36).substr(2, 9)}`,
@@ -90,6 +92,7 @@ export const useHistoryStore = create<HistoryState>()(
           downloadedAt: Math.floor(Date.now() / 1000),
           status,
           error,
+          loudness: loudness ?? null,
         };
 
         // Optimistic update
@@ -15,6 +15,17 @@ const DEFAULT_PREFERENCES: Preferences = {
   proxyUrl: null,
   filenameTemplate: null,
   cookiesFilePath: null,
+  organizeRules: [],
+  audioOutputFolder: null,
+  videoOutputFolder: null,
+  formatOutputFolders: {},
+  transcodePreset: null,
+  normalizeLoudness: false,
+  loudnessTargetLufs: -16,
+  useDownloadArchive: false,
+  live: { enabled: false, start: 'fromNow', maxDurationSeconds: null, waitForStream: false, pollIntervalSeconds: 60 },
+  clipboard: { enabled: false, action: 'notify', format: null, quality: null, allowedDomains: ['youtube.com', 'youtu.be', 'vimeo.com', 'dailymotion.com', 'twitch.tv', 'soundcloud.com', 'bandcamp.com', 'bilibili.com', 'nicovideo.jp', 'tiktok.com'] },
+  api: { enabled: false, port: 47821 },
   bandwidthLimit: null,
   scheduledDownloads: null,
 };
@@ -1,7 +1,7 @@
 import { create } from 'zustand';
 import { persist, createJSONStorage } from 'zustand/middleware';
 import { invoke, listen } from '@/lib/tauri';
-import type { QueueItem, QueueEvent, DownloadConfig } from '@/types';
+import type { QueueItem, QueueEvent, QueueAddResult, BatchAddResult, BatchEntry, QueueSchedule, DownloadConfig, PlaylistAddResult, PlaylistInfo, PlaylistSelection } from '@/types';
 
 interface QueueState {
   items: QueueItem[];
@@ -16,7 +16,9 @@ interface QueueState {
   
   // Actions
   initialize: () => Promise<void>;
-  addToQueue: (config: DownloadConfig) => Promise<QueueItem>;
+  addToQueue: (config: DownloadConfig, force?: boolean, schedule?: QueueSchedule) => Promise<QueueAddResult>;
+  addPlaylistToQueue: (playlist: PlaylistInfo, selection: PlaylistSelection, config: DownloadConfig, force?: boolean) => Promise<PlaylistAddResult>;
+  addBatchToQueue: (entries: BatchEntry[], config: DownloadConfig, force?: boolean) => Promise<BatchAddResult>;
   cancelItem: (id: number) => Promise<void>;
   removeItem: (id: number) => Promise<void>;
   clearCompleted: () => Promise<void>;
@@ -37,8 +39,8 @@ interface QueueState {
 
 // Helper to calculate counts
 const calculateCounts = (items: QueueItem[]) => ({
-  pendingCount: items.filter((i) => i.status === 'pending').length,
-  activeCount: items.filter((i) => i.status === 'downloading' || i.status === 'merging').length,
+  pendingCount: items.filter((i) => i.status === 'pending' || i.status === 'scheduled').length,
+  activeCount: items.filter((i) => i.status === 'downloading' || i.status === 'waitingForLive' || i.status === 'recording' || i.status === 'merging' || i.status === 'transcoding').length,
   completedCount: items.filter((i) => i.status === 'completed').length,
   failedCount: items.filter((i) => i.status === 'failed').length,
 });
@@ -71,6 +73,12 @@ export const useQueueStore = create<QueueState>()(
                 return { items: newItems, ...calculateCounts(newItems) };
               });
               break;
+            case 'itemsAdded':
+              set((state) => {
+                const newItems = [...state.items, ...queueEvent.items];
+                return { items: newItems, ...calculateCounts(newItems) };
+              });
+              break;
             case 'itemUpdated':
               get()._updateItem(queueEvent.item);
               break;
@@ -98,20 +106,27 @@ export const useQueueStore = create<QueueState>()(
         }
       },
 
-      addToQueue: async (config: DownloadConfig) => {
+      addToQueue: async (config: DownloadConfig, force = false, schedule?: QueueSchedule) => {
         // Optimistic update - add placeholder
         const tempId = Date.now();
+        const isScheduled = schedule?.notBefore != null && schedule.notBefore * 1000 > Date.now();
         const optimisticItem: QueueItem = {
           id: tempId,
           config,
-          status: 'pending',
+          status: isScheduled ? 'scheduled' : 'pending',
           progress: 0,
           speed: '--',
           etaSeconds: null,
+          elapsedSeconds: null,
           error: null,
+          skipReason: null,
           filePath: null,
+          filePaths: [],
+          subtitleFiles: [],
           title: config.url,
           thumbnail: null,
+          schedule: schedule ?? null,
+          groupId: null,
         };
         
         set((state) => {
@@ -120,13 +135,15 @@ export const useQueueStore = create<QueueState>()(
         });
         
         try {
-          const item = await invoke<QueueItem>('queue_add', { config });
-          // Replace optimistic item with real one
+          const result = await invoke<QueueAddResult>('queue_add', { config, force, schedule: schedule ?? null });
+          // Replace optimistic item with real one (or drop it for duplicates)
           set((state) => {
-            const newItems = state.items.map((i) => (i.id === tempId ? item : i));
+            const newItems = result.type === 'added'
+              ? state.items.map((i) => (i.id === tempId ? result.item : i))
+              : state.items.filter((i) => i.id !== tempId);
             return { items: newItems, ...calculateCounts(newItems) };
           });
-          return item;
+          return result;
         } catch (err) {
           // Rollback optimistic update
           set((state) => {
@@ -137,6 +154,16 @@ export const useQueueStore = create<QueueState>()(
         }
       },
 
+      addPlaylistToQueue: async (playlist, selection, config, force = false) => {
+        // Items arrive through 'itemAdded' events
+        return invoke<PlaylistAddResult>('queue_add_playlist', { playlist, selection, config, force });
+      },
+
+      addBatchToQueue: async (entries, config, force = false) => {
+        // Items arrive in one 'itemsAdded' event
+        return invoke<BatchAddResult>('queue_add_batch', { entries, config, force });
+      },
+
       cancelItem: async (id: number) => {
         // Optimistic update
         set((state) => {
@@ -177,7 +204,7 @@ export const useQueueStore = create<QueueState>()(
         const previousItems = get().items;
         set((state) => {
           const newItems = state.items.filter(
-            (i) => i.status !== 'completed' && i.status !== 'failed' && i.status !== 'cancelled'
+            (i) => i.status !== 'completed' && i.status !== 'skipped' && i.status !== 'failed' && i.status !== 'cancelled'
           );
           return { items: newItems, ...calculateCounts(newItems) };
         });
@@ -274,7 +301,7 @@ export const useQueueStore = create<QueueState>()(
       
       _clearCompletedItems: () => set((state) => {
         const newItems = state.items.filter(
-          (i) => i.status !== 'completed' && i.status !== 'failed' && i.status !== 'cancelled'
+          (i) => i.status !== 'completed' && i.status !== 'skipped' && i.status !== 'failed' && i.status !== 'cancelled'
         );
         return { items: newItems, ...calculateCounts(newItems) };
       }),
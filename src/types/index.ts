@@ -9,8 +9,12 @@ export type DownloadState =
   | 'analyzing'    // Fetching media info
   | 'starting'
   | 'downloading'
+  | 'waitingForLive' // Waiting for a scheduled livestream
+  | 'recording'      // Recording a livestream
   | 'merging'
+  | 'transcoding'
   | 'completed'
+  | 'skipped'        // Nothing to download (archived or filtered out)
   | 'cancelled'
   | 'cancelling'
   | 'failed';
@@ -30,7 +34,10 @@ export type Format =
   | 'audio-opus';
 
 // Quality options - matches Rust quality handling
-export type Quality = 'best' | '1080p' | '720p';
+export type Quality = 'best' | '2160p' | '1440p' | '1080p' | '720p' | '480p' | '360p';
+
+// Preferred video codec for format sorting
+export type VideoCodec = 'av1' | 'vp9' | 'h264';
 
 // Media info fetched from URL
 export interface MediaInfo {
@@ -39,6 +46,12 @@ export interface MediaInfo {
   duration: number | null;
   uploader: string | null;
   filesizeApprox: number | null; // For disk space check
+  id?: string | null;
+  channel?: string | null;
+  uploadDate?: string | null; // YYYYMMDD
+  width?: number | null;
+  height?: number | null;
+  liveStatus?: string | null; // 'is_live' | 'is_upcoming' | 'was_live' | 'not_live' | ...
 }
 
 // Download configuration sent to backend
@@ -52,6 +65,104 @@ export interface DownloadConfig {
   filenameTemplate?: string | null; // Custom filename template
   proxyUrl?: string | null; // Proxy URL (e.g., "http://127.0.0.1:8080")
   cookiesFilePath?: string | null; // Path to custom cookies.txt file
+  maxFps?: number | null; // Frame rate cap (e.g., 30)
+  allowHdr?: boolean; // false prefers SDR formats
+  videoCodec?: VideoCodec | null; // Preferred codec for older hardware
+  sections?: string[]; // Clip time ranges, "hh:mm:ss-hh:mm:ss" (end optional)
+  chapterPatterns?: string[]; // Chapter title regexes to download
+  sponsorblock?: SponsorBlockOptions; // Segments to cut or mark as chapters
+  splitChapters?: boolean; // One file per chapter
+  subtitles?: SubtitleOptions; // Overrides embedSubtitles when enabled
+  organizeRules?: OrganizeRule[]; // Subfolders, outermost first
+  transcodePreset?: TranscodePreset | null; // Re-encode after download
+  loudnessTargetLufs?: number | null; // Normalize audio downloads (EBU R128)
+  live?: LiveOptions; // Livestream recording
+  playlist?: PlaylistContext | null; // Set for items queued from a playlist
+  playlistFilter?: PlaylistFilter; // Skip playlist entries by date, duration or title
+}
+
+// Where a live recording begins
+export type LiveStart = 'fromNow' | 'fromStart';
+
+export interface LiveOptions {
+  enabled: boolean;
+  start: LiveStart;
+  maxDurationSeconds: number | null; // null = until the stream ends
+  waitForStream: boolean; // Wait for scheduled streams to start
+  pollIntervalSeconds: number; // Seconds between checks while waiting
+}
+
+// What happens to a media link copied to the clipboard
+export type ClipboardAction = 'notify' | 'enqueue';
+
+export interface ClipboardOptions {
+  enabled: boolean;
+  action: ClipboardAction;
+  format: Format | null; // Format for queued links (null = default format)
+  quality: Quality | null; // Quality for queued links (null = default quality)
+  allowedDomains: string[]; // Sites links are picked up from, subdomains included
+}
+
+// Payload of the "clipboard-link" event
+export interface ClipboardLink {
+  url: string;
+  item: QueueItem | null; // Set when the link was queued
+  duplicate: QueueDuplicate | null; // Set when queueing was skipped
+}
+
+// Payload of the "deep-link" event (mediagrab://add?url=...)
+export interface DeepLinkResult {
+  link: string; // The deep link as opened
+  url: string | null; // Media link it carried, when valid
+  item: QueueItem | null; // Set when the link was queued
+  duplicate: QueueDuplicate | null; // Set when queueing was skipped
+  error: string | null; // Why the link couldn't be queued
+}
+
+// Local HTTP API for browser extensions and scripts
+export interface ApiOptions {
+  enabled: boolean;
+  port: number; // Port on 127.0.0.1
+}
+
+// Where to reach the local API (api_get_info)
+export interface ApiInfo {
+  url: string; // e.g. http://127.0.0.1:47821/api
+  token: string; // Sent as "Authorization: Bearer <token>"
+}
+
+// Output folder organization rules (one subfolder level each)
+export type OrganizeRule = 'site' | 'uploader' | 'playlist' | 'mediaType' | 'year';
+
+// Post-download ffmpeg presets (the original file is kept)
+export type TranscodePreset = 'phone720p' | 'editingIntra' | 'voiceMemo';
+
+// Loudness measured by the first loudnorm pass
+export interface LoudnessMeasurement {
+  inputI: number; // Integrated loudness before normalization (LUFS)
+  inputTp: number; // True peak (dBTP)
+  inputLra: number; // Loudness range (LU)
+  inputThresh: number; // Gating threshold (LUFS)
+  targetOffset: number; // Offset gain applied in the second pass (LU)
+  targetI: number; // Normalization target (LUFS)
+  outputI?: number | null; // Loudness after normalization (LUFS)
+}
+
+// SponsorBlock segment categories (yt-dlp names)
+export type SponsorBlockCategory =
+  | 'sponsor'
+  | 'intro'
+  | 'outro'
+  | 'selfpromo'
+  | 'preview'
+  | 'filler'
+  | 'interaction'
+  | 'music_offtopic'
+  | 'poi_highlight';
+
+export interface SponsorBlockOptions {
+  remove: SponsorBlockCategory[]; // Cut from the file
+  mark: SponsorBlockCategory[]; // Kept, added as chapters
 }
 
 // Progress event from backend
@@ -62,13 +173,17 @@ export interface ProgressEvent {
   totalBytes: number | null;
   speed: string;             // e.g., "2.5MiB/s"
   etaSeconds: number | null;
-  status: 'downloading' | 'merging';
+  status: 'downloading' | 'recording' | 'merging' | 'transcoding';
+  elapsedSeconds?: number; // Time recorded so far (livestreams only)
 }
 
 // Download result from backend
 export interface DownloadResult {
   success: boolean;
   filePath?: string;
+  filePaths: string[]; // Main file first, then split chapter files
+  subtitleFiles: string[]; // Sidecar subtitle files
+  loudness?: LoudnessMeasurement | null; // Normalized audio downloads only
   error?: string;
 }
 
@@ -88,12 +203,59 @@ export interface Preferences {
   filenameTemplate: string | null; // e.g., "{title} - {uploader} [{quality}]"
   // Custom cookies file
   cookiesFilePath: string | null; // Path to cookies.txt file (Netscape format)
+  // Output folder organization
+  organizeRules: OrganizeRule[]; // e.g., ['site', 'uploader']
+  // Default folders per media type (null = outputFolder)
+  audioOutputFolder: string | null;
+  videoOutputFolder: string | null;
+  formatOutputFolders: Partial<Record<Format, string>>; // Overrides the media type folders
+  // Post-download transcoding
+  transcodePreset: TranscodePreset | null;
+  // Loudness normalization for audio downloads
+  normalizeLoudness: boolean;
+  loudnessTargetLufs: number; // e.g., -16 (podcasts) or -23 (broadcast)
+  // Skip videos recorded in the download archive
+  useDownloadArchive: boolean;
+  // Recording settings for livestream URLs
+  live: LiveOptions;
+  // Picking up media links copied to the clipboard
+  clipboard: ClipboardOptions;
+  // Local HTTP API for browser extensions and scripts
+  api: ApiOptions;
   // Bandwidth limiting (KB/s, 0 = unlimited)
   bandwidthLimit: number | null;
   // Scheduled downloads
   scheduledDownloads: ScheduledDownload[] | null;
 }
 
+// Download archive entry (one line of yt-dlp's --download-archive file)
+export interface ArchiveEntry {
+  extractor: string; // Lowercase extractor key, e.g. "youtube"
+  id: string;
+}
+
+// Subscribed channel or playlist, synced on an interval
+export interface Subscription {
+  id: string;
+  url: string;
+  title: string | null;
+  preset: DownloadConfig; // Settings for queued entries (url is replaced)
+  intervalMinutes: number;
+  enabled: boolean;
+  createdAt: number; // Unix timestamp
+  lastSyncedAt: number | null; // Unix timestamp
+  lastError: string | null;
+  ignoredUrls: string[]; // Entries never to queue (present at subscribe time or already queued), newest last
+}
+
+// Result of syncing a subscription (also sent as 'subscription-synced')
+export interface SubscriptionSyncResult {
+  subscriptionId: string;
+  title: string | null;
+  queued: number; // New entries queued
+  error: string | null;
+}
+
 // Scheduled download configuration
 export interface ScheduledDownload {
   id: string;
@@ -101,7 +263,9 @@ export interface ScheduledDownload {
   format: Format;
   quality: Quality;
   scheduledTime: number; // Unix timestamp
+  deadline?: number | null; // Must finish by (Unix timestamp)
   enabled: boolean;
+  queueItemId?: number | null; // Queue item while the download is waiting in the queue
 }
 
 // Disk space information from backend
@@ -184,9 +348,14 @@ export interface ExecutablesMissingEvent {
 // Queue item status
 export type QueueItemStatus =
   | 'pending'
+  | 'scheduled' // Waiting for its start time
   | 'downloading'
+  | 'waitingForLive'
+  | 'recording'
   | 'merging'
+  | 'transcoding'
   | 'completed'
+  | 'skipped' // Nothing to download, see skipReason
   | 'failed'
   | 'cancelled'
   | 'cancelling';
@@ -199,19 +368,89 @@ export interface QueueItem {
   progress: number;
   speed: string;
   etaSeconds: number | null;
+  elapsedSeconds: number | null; // Livestreams only
   error: string | null;
+  skipReason: string | null; // Why nothing was downloaded, if skipped
   filePath: string | null;
+  filePaths: string[]; // Main file first, then split chapter files
+  subtitleFiles: string[]; // Sidecar subtitle files
   title: string | null;
   thumbnail: string | null;
+  schedule: QueueSchedule | null;
+  groupId: string | null; // Shared by items queued from the same playlist
+}
+
+// When a queue item may run (Unix timestamps in seconds)
+export interface QueueSchedule {
+  notBefore: number | null; // Don't start before this time
+  deadline: number | null; // Fail the item if it hasn't finished by then
 }
 
 // Queue event types
 export type QueueEvent =
   | { type: 'itemAdded'; item: QueueItem }
+  | { type: 'itemsAdded'; items: QueueItem[] }
   | { type: 'itemUpdated'; item: QueueItem }
   | { type: 'itemRemoved'; id: number }
   | { type: 'queueCleared' };
 
+// Existing download found when adding to the queue
+export interface QueueDuplicate {
+  source: 'queue' | 'history';
+  matchedBy: 'url' | 'videoId';
+  url: string; // URL of the existing entry
+  title: string | null;
+  queueItemId: number | null;
+  historyId: string | null;
+  filePath: string | null; // Only set if the file is still on disk
+  downloadedAt: number | null; // Unix timestamp (history only)
+}
+
+// Result of queue_add_playlist
+export interface PlaylistAddResult {
+  groupId: string;
+  items: QueueItem[];
+  duplicates: QueueDuplicate[]; // Selected entries that weren't queued
+}
+
+// Settings a batch import line sets for its links
+export interface BatchOverrides {
+  format?: Format | null;
+  quality?: Quality | null;
+  outputFolder?: string | null;
+}
+
+// A link found by batch_parse, with its 1-based line
+export interface BatchEntry {
+  line: number;
+  url: string;
+  overrides: BatchOverrides;
+}
+
+// A batch line that couldn't be used
+export interface BatchIssue {
+  line: number;
+  text: string;
+  error: string;
+}
+
+// Result of batch_parse
+export interface BatchParse {
+  entries: BatchEntry[];
+  issues: BatchIssue[];
+}
+
+// Result of queue_add_batch
+export interface BatchAddResult {
+  items: QueueItem[];
+  duplicates: QueueDuplicate[]; // Links that weren't queued
+}
+
+// Result of queue_add
+export type QueueAddResult =
+  | { type: 'added'; item: QueueItem }
+  | { type: 'duplicate'; duplicate: QueueDuplicate };
+
 // Retry event from backend
 export interface RetryEvent {
   attempt: number;
@@ -239,6 +478,7 @@ export interface HistoryItem {
   downloadedAt: number; // Unix timestamp
   status: 'completed' | 'failed';
   error: string | null;
+  loudness?: LoudnessMeasurement | null; // Normalized audio downloads only
 }
 
 // Download statistics
@@ -264,6 +504,17 @@ export interface PlaylistEntry {
   duration: number | null;
   playlistIndex: number;
   uploader: string | null;
+  uploadDate?: string | null; // YYYYMMDD, when the listing includes it
+}
+
+// Which playlist entries to download; entries missing a date or duration pass
+export interface PlaylistFilter {
+  dateAfter?: string | null; // YYYYMMDD or YYYY-MM-DD, inclusive
+  dateBefore?: string | null;
+  minDuration?: number | null; // seconds
+  maxDuration?: number | null;
+  titleIncludes?: string[]; // case-insensitive, any of
+  titleExcludes?: string[];
 }
 
 // Playlist information
@@ -274,9 +525,40 @@ export interface PlaylistInfo {
   thumbnail: string | null;
   uploader: string | null;
   videoCount: number;
+  // Size of the whole playlist when the site reports it
+  totalCount: number | null;
   entries: PlaylistEntry[];
 }
 
+// Emitted while a playlist is being listed
+export interface PlaylistProgress {
+  url: string;
+  loaded: number;
+  total: number | null;
+}
+
+// Inclusive range of 1-based playlist positions
+export interface PlaylistRange {
+  start: number;
+  end: number | null; // null = to the end
+}
+
+// Which playlist entries to queue (no indices or ranges = all)
+export interface PlaylistSelection {
+  indices: number[];
+  ranges: PlaylistRange[];
+  newest: number | null; // Keep only the N newest selected entries
+  reverse: boolean; // Queue the last entry first
+}
+
+// Playlist an item was queued from ({playlist_index}/{playlist_title})
+export interface PlaylistContext {
+  groupId: string;
+  index: number;
+  count: number;
+  title: string;
+}
+
 
 // ============================================
 // Subtitle System Types
@@ -302,7 +584,7 @@ export interface SubtitleOptions {
   downloadSubtitles: boolean;
   embedSubtitles: boolean;
   languages: string[];
-  format: 'srt' | 'vtt' | 'ass';
+  format: 'srt' | 'vtt' | 'ass' | 'best';
   includeAuto: boolean;
 }
 